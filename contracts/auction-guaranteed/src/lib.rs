@@ -1,112 +1,242 @@
 use cosmwasm_std::{
-    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, 
-    Response, StdResult, to_binary, Addr
+    entry_point, to_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Timestamp, Uint128,
 };
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+// A single-lot English auction: bids must beat the current high bid, the
+// outbid bidder is refunded in the same transaction, and closing after the
+// end time pays the winning bid to the seller.
+
+// ============================================================
+// MESSAGES
+// ============================================================
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    pub admin: String,
+    // Defaults to the instantiating address
+    pub seller: Option<String>,
+    pub denom: String,
+    // Lowest acceptable opening bid
+    pub min_bid: Uint128,
+    pub duration_seconds: u64,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    // The bid is the amount of `denom` sent with the message
     PlaceBid {},
+    // Ends the auction once the end time has passed and pays the seller
+    Close {},
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     GetHighestBid {},
+    GetState {},
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct BidResponse {
-    pub amount: String,
-    pub bidder: String,
+    pub amount: Uint128,
+    pub bidder: Option<Addr>,
+}
+
+// ============================================================
+// STATE
+// ============================================================
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct State {
+    pub seller: Addr,
+    pub denom: String,
+    pub min_bid: Uint128,
+    pub end_time: Timestamp,
+    pub highest_bid: Option<Bid>,
+    pub closed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Uint128,
 }
 
-// Storage using cosmwasm-std storage (simplest approach)
-use cosmwasm_std::Storage;
-use std::cell::RefCell;
+pub const STATE: Item<State> = Item::new("state");
+
+// ============================================================
+// ERRORS
+// ============================================================
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Auction duration must be greater than zero")]
+    InvalidDuration {},
+
+    #[error("Send exactly one coin of {denom}")]
+    InvalidFunds { denom: String },
+
+    #[error("Bid must be at least {min}")]
+    BidTooLow { min: Uint128 },
 
-thread_local! {
-    static STORAGE: RefCell<Option<BidResponse>> = RefCell::new(None);
+    #[error("Seller cannot bid")]
+    SellerCannotBid {},
+
+    #[error("Auction has ended")]
+    AuctionEnded {},
+
+    #[error("Auction has not ended yet")]
+    AuctionNotEnded {},
+
+    #[error("Auction is already closed")]
+    AuctionClosed {},
 }
 
+// ============================================================
+// ENTRY POINTS
+// ============================================================
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
-    let admin = deps.api.addr_validate(&msg.admin)?;
-    
-    // Store initial state
-    let initial_bid = BidResponse {
-        amount: "0".to_string(),
-        bidder: admin.to_string(),
+) -> Result<Response, ContractError> {
+    if msg.duration_seconds == 0 {
+        return Err(ContractError::InvalidDuration {});
+    }
+    let seller = match msg.seller {
+        Some(seller) => deps.api.addr_validate(&seller)?,
+        None => info.sender,
     };
-    
-    // Simple storage approach
-    STORAGE.with(|s| {
-        *s.borrow_mut() = Some(initial_bid);
-    });
-    
+
+    let state = State {
+        seller,
+        denom: msg.denom,
+        min_bid: msg.min_bid,
+        end_time: env.block.time.plus_seconds(msg.duration_seconds),
+        highest_bid: None,
+        closed: false,
+    };
+    STATE.save(deps.storage, &state)?;
+
     Ok(Response::new()
         .add_attribute("action", "instantiate")
-        .add_attribute("admin", msg.admin))
+        .add_attribute("seller", state.seller)
+        .add_attribute("min_bid", state.min_bid)
+        .add_attribute("end_time", state.end_time.seconds().to_string()))
 }
 
 #[entry_point]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::PlaceBid {} => {
-            // Get funds (simplified)
-            let amount = info.funds.get(0)
-                .map(|coin| coin.amount.to_string())
-                .unwrap_or_else(|| "0".to_string());
-            
-            // Update storage
-            let new_bid = BidResponse {
-                amount: amount.clone(),
-                bidder: info.sender.to_string(),
-            };
-            
-            STORAGE.with(|s| {
-                *s.borrow_mut() = Some(new_bid);
-            });
-            
-            Ok(Response::new()
-                .add_attribute("action", "place_bid")
-                .add_attribute("sender", info.sender)
-                .add_attribute("amount", amount))
-        }
+        ExecuteMsg::PlaceBid {} => execute_place_bid(deps, env, info),
+        ExecuteMsg::Close {} => execute_close(deps, env),
+    }
+}
+
+fn execute_place_bid(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    if state.closed {
+        return Err(ContractError::AuctionClosed {});
+    }
+    if env.block.time >= state.end_time {
+        return Err(ContractError::AuctionEnded {});
+    }
+    if info.sender == state.seller {
+        return Err(ContractError::SellerCannotBid {});
+    }
+
+    let amount = match info.funds.as_slice() {
+        [coin] if coin.denom == state.denom && !coin.amount.is_zero() => coin.amount,
+        _ => return Err(ContractError::InvalidFunds { denom: state.denom }),
+    };
+
+    // The opening bid must meet the minimum, every later bid must beat the current one
+    let min = match &state.highest_bid {
+        Some(bid) => bid.amount.checked_add(Uint128::new(1)).map_err(StdError::from)?,
+        None => state.min_bid,
+    };
+    if amount < min {
+        return Err(ContractError::BidTooLow { min });
+    }
+
+    let previous = state.highest_bid.replace(Bid {
+        bidder: info.sender.clone(),
+        amount,
+    });
+    STATE.save(deps.storage, &state)?;
+
+    let mut res = Response::new();
+    if let Some(previous) = previous {
+        res = res.add_message(BankMsg::Send {
+            to_address: previous.bidder.to_string(),
+            amount: vec![Coin::new(previous.amount.u128(), &state.denom)],
+        });
+    }
+
+    Ok(res
+        .add_attribute("action", "place_bid")
+        .add_attribute("bidder", info.sender)
+        .add_attribute("amount", amount))
+}
+
+fn execute_close(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let mut state = STATE.load(deps.storage)?;
+    if state.closed {
+        return Err(ContractError::AuctionClosed {});
+    }
+    if env.block.time < state.end_time {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+
+    state.closed = true;
+    STATE.save(deps.storage, &state)?;
+
+    let res = Response::new().add_attribute("action", "close");
+    match state.highest_bid {
+        Some(bid) => Ok(res
+            .add_message(BankMsg::Send {
+                to_address: state.seller.to_string(),
+                amount: vec![Coin::new(bid.amount.u128(), &state.denom)],
+            })
+            .add_attribute("winner", bid.bidder)
+            .add_attribute("amount", bid.amount)),
+        None => Ok(res.add_attribute("winner", "none")),
     }
 }
 
 #[entry_point]
-pub fn query(
-    _deps: Deps,
-    _env: Env,
-    msg: QueryMsg,
-) -> StdResult<Binary> {
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let state = STATE.load(deps.storage)?;
     match msg {
         QueryMsg::GetHighestBid {} => {
-            let response = STORAGE.with(|s| {
-                s.borrow().clone().unwrap_or_else(|| BidResponse {
-                    amount: "0".to_string(),
-                    bidder: "none".to_string(),
-                })
-            });
-            Ok(to_binary(&response)?)
+            let response = match state.highest_bid {
+                Some(bid) => BidResponse {
+                    amount: bid.amount,
+                    bidder: Some(bid.bidder),
+                },
+                None => BidResponse {
+                    amount: Uint128::zero(),
+                    bidder: None,
+                },
+            };
+            to_binary(&response)
         }
+        QueryMsg::GetState {} => to_binary(&state),
     }
 }
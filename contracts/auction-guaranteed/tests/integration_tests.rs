@@ -1,3 +1,138 @@
-cd /home/greg/dev/TX/contracts/auction-guaranteed
-mkdir -p tests
-nano tests/integration_tests.rs
+#[cfg(test)]
+mod tests {
+    use auction_guaranteed::{
+        execute, instantiate, query, BidResponse, ContractError, ExecuteMsg, InstantiateMsg,
+        QueryMsg, State,
+    };
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coin, coins, from_binary, Addr, BankMsg, CosmosMsg, Env, OwnedDeps, Uint128};
+
+    const DENOM: &str = "utestcore";
+    const DURATION: u64 = 3600;
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            seller: None,
+            denom: DENOM.to_string(),
+            min_bid: Uint128::new(100),
+            duration_seconds: DURATION,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("seller", &[]), msg).unwrap();
+        deps
+    }
+
+    fn bid(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        bidder: &str,
+        amount: u128,
+    ) -> Result<cosmwasm_std::Response, ContractError> {
+        execute(deps.as_mut(), mock_env(), mock_info(bidder, &coins(amount, DENOM)), ExecuteMsg::PlaceBid {})
+    }
+
+    fn ended() -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(DURATION);
+        env
+    }
+
+    fn highest_bid(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> BidResponse {
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetHighestBid {}).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let deps = setup();
+        let state: State = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetState {}).unwrap()).unwrap();
+        assert_eq!(state.seller, Addr::unchecked("seller"));
+        assert_eq!(state.end_time, mock_env().block.time.plus_seconds(DURATION));
+        assert!(!state.closed);
+        assert_eq!(highest_bid(&deps), BidResponse { amount: Uint128::zero(), bidder: None });
+    }
+
+    #[test]
+    fn test_bids_must_increase() {
+        let mut deps = setup();
+
+        let err = bid(&mut deps, "alice", 99).unwrap_err();
+        assert_eq!(err, ContractError::BidTooLow { min: Uint128::new(100) });
+        bid(&mut deps, "alice", 100).unwrap();
+
+        // Equal and lower bids are refused
+        let err = bid(&mut deps, "bob", 100).unwrap_err();
+        assert_eq!(err, ContractError::BidTooLow { min: Uint128::new(101) });
+        let err = bid(&mut deps, "bob", 50).unwrap_err();
+        assert_eq!(err, ContractError::BidTooLow { min: Uint128::new(101) });
+
+        assert_eq!(highest_bid(&deps), BidResponse {
+            amount: Uint128::new(100),
+            bidder: Some(Addr::unchecked("alice")),
+        });
+    }
+
+    #[test]
+    fn test_bid_funds_are_checked() {
+        let mut deps = setup();
+        let invalid = ContractError::InvalidFunds { denom: DENOM.to_string() };
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::PlaceBid {}).unwrap_err();
+        assert_eq!(err, invalid);
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(500, "uother")), ExecuteMsg::PlaceBid {}).unwrap_err();
+        assert_eq!(err, invalid);
+        let funds = vec![coin(500, DENOM), coin(500, "uother")];
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &funds), ExecuteMsg::PlaceBid {}).unwrap_err();
+        assert_eq!(err, invalid);
+
+        let err = bid(&mut deps, "seller", 500).unwrap_err();
+        assert_eq!(err, ContractError::SellerCannotBid {});
+    }
+
+    #[test]
+    fn test_outbid_bidder_is_refunded() {
+        let mut deps = setup();
+        let res = bid(&mut deps, "alice", 150).unwrap();
+        assert!(res.messages.is_empty());
+
+        let res = bid(&mut deps, "bob", 200).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(150, DENOM),
+        }));
+    }
+
+    #[test]
+    fn test_close_pays_seller() {
+        let mut deps = setup();
+        bid(&mut deps, "alice", 150).unwrap();
+        bid(&mut deps, "bob", 200).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), ExecuteMsg::Close {}).unwrap_err();
+        assert_eq!(err, ContractError::AuctionNotEnded {});
+
+        // No bids once the end time is reached
+        let err = execute(deps.as_mut(), ended(), mock_info("carol", &coins(300, DENOM)), ExecuteMsg::PlaceBid {}).unwrap_err();
+        assert_eq!(err, ContractError::AuctionEnded {});
+
+        let res = execute(deps.as_mut(), ended(), mock_info("anyone", &[]), ExecuteMsg::Close {}).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "seller".to_string(),
+            amount: coins(200, DENOM),
+        }));
+
+        // The seller is paid exactly once
+        let err = execute(deps.as_mut(), ended(), mock_info("anyone", &[]), ExecuteMsg::Close {}).unwrap_err();
+        assert_eq!(err, ContractError::AuctionClosed {});
+    }
+
+    #[test]
+    fn test_close_without_bids() {
+        let mut deps = setup();
+        let res = execute(deps.as_mut(), ended(), mock_info("anyone", &[]), ExecuteMsg::Close {}).unwrap();
+        assert!(res.messages.is_empty());
+
+        let err = bid(&mut deps, "alice", 150).unwrap_err();
+        assert_eq!(err, ContractError::AuctionClosed {});
+    }
+}
@@ -36,7 +36,7 @@ fuzz_target!(|data: &[u8]| {
                 let bidder = mock_info("bidder", &coins(amount, "utestcore"));
                 let bid_msg = ExecuteMsg::PlaceBid {
                     auction_id: 1,
-                    amount: amount.to_string(),
+                    amount: None,
                 };
                 let _ = execute(deps.as_mut(), env, bidder, bid_msg);
             }
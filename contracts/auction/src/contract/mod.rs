@@ -1,18 +1,38 @@
 use cosmwasm_std::{
-    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
-    to_json_binary, Addr, BankMsg, coins,
+    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult,
+    Uint128, to_json_binary, Addr, BankMsg, StakingMsg, coin, coins,
 };
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, AuctionResponse, BidResponse};
-use crate::state::{Config, Auction, Bid, AUCTIONS, CONFIG, AUCTION_COUNT};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, AuctionResponse, BidResponse, FeeInfoResponse,
+};
+use crate::state::{
+    Config, Auction, AuctionStatus, Bid, Settlement, StakingConfig, Unbonding, AUCTIONS, CONFIG,
+    AUCTION_COUNT, OPEN_BIDS, PENDING_SETTLEMENTS, STAKING, UNBONDING,
+};
+use crate::staking;
+use cw2::set_contract_version;
 use cw_storage_plus::{Map, Item};
+use phoenix_fees::{split_fee, Bps, FeeSchedule, FeeShare, BPS_DENOMINATOR, PLATFORM_FEE_BPS};
+use phoenix_insurance::helpers::InsurancePool;
+use phoenix_pme::helpers::PlatformRegistry;
+
+const CONTRACT_NAME: &str = "crates.io:phoenix-auction";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// 2%, the highest rate the fee model plans for
+const MAX_FEE_BPS: u16 = 200;
+// The developer's 10% in the fee model
+const MAX_DEVELOPER_SHARE: u8 = 10;
 
 // Storage keys
+const BIDDERS: Map<(u64, Addr), Bid> = Map::new("bidders");
+
+// Written by earlier versions alongside the auction record, only read by migrate
 const HIGHEST_BIDS: Map<u64, u128> = Map::new("highest_bids");
 const AUCTION_STATES: Map<u64, bool> = Map::new("auction_states");
 const WINNERS: Map<u64, Addr> = Map::new("winners");
 const AUCTION_COUNTER: Item<u64> = Item::new("auction_counter");
-const BIDDERS: Map<(u64, Addr), Bid> = Map::new("bidders");
 
 #[entry_point]
 pub fn instantiate(
@@ -21,10 +41,16 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     let config = Config {
         admin: deps.api.addr_validate(&msg.admin)?,
         insurance_pool: deps.api.addr_validate(&msg.insurance_pool)?,
         token_denom: msg.token_denom,
+        fee_bps: PLATFORM_FEE_BPS,
+        developer: None,
+        developer_share: 0,
+        registry: None,
     };
     
     CONFIG.save(deps.storage, &config)?;
@@ -50,7 +76,23 @@ pub fn execute(
         } => execute_create_auction(deps, env, info, starting_bid, duration, description),
         ExecuteMsg::PlaceBid { auction_id, amount } => execute_place_bid(deps, env, info, auction_id, amount),
         ExecuteMsg::CloseAuction { auction_id } => execute_close_auction(deps, env, info, auction_id),
-        ExecuteMsg::ClaimWinnings { auction_id } => execute_claim_winnings(deps, env, info, auction_id),
+        ExecuteMsg::ClaimProceeds { auction_id } => execute_claim_proceeds(deps, env, info, auction_id),
+        ExecuteMsg::ClaimItem { auction_id } => execute_claim_item(deps, env, info, auction_id),
+        ExecuteMsg::ClaimRefund { auction_id } => execute_claim_refund(deps, info, auction_id),
+        ExecuteMsg::UpdateStaking {
+            validator,
+            stake_bps,
+            buffer_bps,
+            unbonding_seconds,
+        } => execute_update_staking(deps, env, info, validator, stake_bps, buffer_bps, unbonding_seconds),
+        ExecuteMsg::RebalanceStake {} => execute_rebalance_stake(deps, env),
+        ExecuteMsg::ClaimStakingRewards {} => execute_claim_staking_rewards(deps, env),
+        ExecuteMsg::UpdateFees {
+            fee_bps,
+            developer,
+            developer_share,
+        } => execute_update_fees(deps, info, fee_bps, developer, developer_share),
+        ExecuteMsg::UpdateRegistry { registry } => execute_update_registry(deps, info, registry),
     }
 }
 
@@ -77,10 +119,8 @@ fn execute_create_auction(
         }
     }
     
-    // Get next auction ID using AUCTION_COUNTER
-    let mut auction_count = AUCTION_COUNTER.may_load(deps.storage)?.unwrap_or(0);
-    auction_count += 1;
-    let auction_id = auction_count;
+    // Get next auction ID
+    let auction_id = AUCTION_COUNT.may_load(deps.storage)?.unwrap_or(0) + 1;
     
     // Create auction
     let auction = Auction {
@@ -92,19 +132,16 @@ fn execute_create_auction(
         description,
         created_at: env.block.time.seconds(),
         expires_at: env.block.time.seconds() + duration,
-        status: "active".to_string(),
+        status: AuctionStatus::Active,
         bids: vec![],
+        settlement: Settlement::default(),
     };
     
     // Save auction
     AUCTIONS.save(deps.storage, auction_id, &auction)?;
     
     // Save the counter
-    AUCTION_COUNTER.save(deps.storage, &auction_count)?;
-    
-    // Initialize auction state
-    AUCTION_STATES.save(deps.storage, auction_id, &true)?;
-    HIGHEST_BIDS.save(deps.storage, auction_id, &starting_bid.u128())?;
+    AUCTION_COUNT.save(deps.storage, &auction_id)?;
     
     Ok(Response::new()
         .add_attribute("method", "create_auction")
@@ -118,55 +155,79 @@ fn execute_place_bid(
     env: Env,
     info: MessageInfo,
     auction_id: u64,
-    amount: String,
+    amount: Option<String>,
 ) -> Result<Response, ContractError> {
     // Load auction
     let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
     
     // Check if auction is active
-    if auction.status != "active" {
+    if auction.status != AuctionStatus::Active {
         return Err(ContractError::AuctionNotActive {});
     }
     
     // Check if auction has expired
     if env.block.time.seconds() > auction.expires_at {
-        auction.status = "expired".to_string();
+        auction.status = AuctionStatus::Expired;
         AUCTIONS.save(deps.storage, auction_id, &auction)?;
-        AUCTION_STATES.save(deps.storage, auction_id, &false)?;
         return Err(ContractError::AuctionExpired {});
     }
     
-    // Parse bid amount
-    let bid_amount: u128 = amount.parse().map_err(|_| ContractError::InvalidAmount {})?;
-    let bid_amount_u128 = Uint128::from(bid_amount);
+    // The attached funds are the bid
+    let config = CONFIG.load(deps.storage)?;
+    let sent = match info.funds.as_slice() {
+        [] => return Err(ContractError::NoFunds {}),
+        [coin] if coin.denom == config.token_denom => coin.amount,
+        _ => return Err(ContractError::InvalidDenom {}),
+    };
+    if sent.is_zero() {
+        return Err(ContractError::NoFunds {});
+    }
+    
+    // Legacy messages still name the amount, it must be covered by the funds
+    let bid_amount = match amount {
+        Some(amount) => {
+            let amount: u128 = amount.parse().map_err(|_| ContractError::InvalidAmount {})?;
+            let amount = Uint128::from(amount);
+            if sent < amount {
+                return Err(ContractError::InsufficientFunds {});
+            }
+            amount
+        }
+        None => sent,
+    };
+    let excess = sent - bid_amount;
     
     // Validate bid amount
-    if bid_amount_u128 < auction.starting_bid {
+    if bid_amount < auction.starting_bid {
         return Err(ContractError::BidTooLow {});
     }
     
-    if bid_amount_u128 <= auction.current_bid {
+    if bid_amount <= auction.current_bid {
         return Err(ContractError::BidTooLow {});
     }
     
-    // Check if user has sufficient funds
-    let config = CONFIG.load(deps.storage)?;
-    let sent_funds = info.funds.iter().find(|c| c.denom == config.token_denom);
+    let mut res = Response::new();
     
-    match sent_funds {
-        Some(coin) if coin.amount < bid_amount_u128 => {
-            return Err(ContractError::InsufficientFunds {});
-        }
-        None => {
-            return Err(ContractError::NoFunds {});
-        }
-        _ => {}
+    // Refund the displaced bid right away, nothing is left for ClaimRefund
+    if let Some(previous) = auction.highest_bidder.take() {
+        BIDDERS.remove(deps.storage, (auction_id, previous.clone()));
+        res = res.add_message(BankMsg::Send {
+            to_address: previous.to_string(),
+            amount: coins(auction.current_bid.u128(), &config.token_denom),
+        });
+    }
+    
+    if !excess.is_zero() {
+        res = res.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(excess.u128(), &config.token_denom),
+        });
     }
     
     // Create bid record
     let bid = Bid {
         bidder: info.sender.clone(),
-        amount: bid_amount_u128,
+        amount: bid_amount,
         timestamp: env.block.time.seconds(),
     };
     
@@ -174,22 +235,23 @@ fn execute_place_bid(
     let bid_key = (auction_id, info.sender.clone());
     BIDDERS.save(deps.storage, bid_key, &bid)?;
     
+    // Track the escrow behind running auctions for the staking buffer
+    staking::replace_open_bid(deps.storage, auction.current_bid, bid_amount)?;
+
     // Update auction
     auction.bids.push(bid);
-    auction.current_bid = bid_amount_u128;
+    auction.current_bid = bid_amount;
     auction.highest_bidder = Some(info.sender.clone());
     
     // Save auction
     AUCTIONS.save(deps.storage, auction_id, &auction)?;
     
-    // Update highest bid
-    HIGHEST_BIDS.save(deps.storage, auction_id, &bid_amount)?;
-    
-    Ok(Response::new()
+    Ok(res
         .add_attribute("method", "place_bid")
         .add_attribute("auction_id", auction_id.to_string())
         .add_attribute("bidder", info.sender)
-        .add_attribute("amount", bid_amount.to_string()))
+        .add_attribute("amount", bid_amount)
+        .add_attribute("refunded", excess))
 }
 
 fn execute_close_auction(
@@ -207,7 +269,7 @@ fn execute_close_auction(
     }
     
     // Check if auction is already closed
-    if auction.status != "active" && auction.status != "expired" {
+    if !matches!(auction.status, AuctionStatus::Active | AuctionStatus::Expired) {
         return Err(ContractError::AuctionAlreadyClosed {});
     }
     
@@ -222,89 +284,370 @@ fn execute_close_auction(
     
     if !is_expired && auction.bids.is_empty() {
         // No bids and not expired - just cancel
-        auction.status = "cancelled".to_string();
+        auction.status = AuctionStatus::Cancelled;
     } else {
-        // Auction has bids or expired - determine winner
-        auction.status = "closed".to_string();
+        // Auction has bids or expired - the highest bidder wins
+        auction.status = AuctionStatus::Closed;
         
-        if let Some(highest_bidder) = &auction.highest_bidder {
-            // Store winner
-            WINNERS.save(deps.storage, auction_id, highest_bidder)?;
+        if auction.highest_bidder.is_some() {
+            staking::close_bid(deps.storage, auction.current_bid)?;
         }
     }
     
     // Save auction
     AUCTIONS.save(deps.storage, auction_id, &auction)?;
-    AUCTION_STATES.save(deps.storage, auction_id, &false)?;
     
     Ok(Response::new()
         .add_attribute("method", "close_auction")
         .add_attribute("auction_id", auction_id.to_string())
-        .add_attribute("status", auction.status))
+        .add_attribute("status", auction.status.as_str()))
 }
 
-fn execute_claim_winnings(
+fn execute_claim_proceeds(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     auction_id: u64,
 ) -> Result<Response, ContractError> {
-    // Load auction
-    let auction = AUCTIONS.load(deps.storage, auction_id)?;
-    
-    // Check if auction exists
-    if auction.id == 0 {
-        return Err(ContractError::AuctionNotFound {});
+    let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
+    if auction.status != AuctionStatus::Closed {
+        return Err(ContractError::AuctionNotClosed {});
     }
-    
-    // Check if auction is closed
-    if auction.status != "closed" {
+    if info.sender != auction.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+    let winner = auction.winner().cloned().ok_or(ContractError::NoWinner {})?;
+    if auction.settlement.proceeds_claimed_at.is_some() {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+
+    auction.settlement.proceeds_claimed_at = Some(env.block.time.seconds());
+    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+    staking::settle(deps.storage, auction.current_bid)?;
+
+    // Calculate the platform fee
+    let config = CONFIG.load(deps.storage)?;
+    let amounts = fee_schedule(deps.as_ref(), &config)?.compute(auction.current_bid)?;
+    let fee = amounts.fee.u128();
+    let payout = amounts.net.u128();
+
+    let send_msg = BankMsg::Send {
+        to_address: auction.creator.to_string(),
+        amount: coins(payout, config.token_denom.clone()),
+    };
+    let mut res = Response::new().add_message(send_msg);
+
+    // Deposit the pool's share of the fee, attributed to this auction, and pay the developer the rest
+    let mut parts = split_fee(amounts.fee, &fee_shares(&config))?.into_iter();
+    let insurance_fee = parts.next().map_or(Uint128::zero(), |(_, amount)| amount);
+    if !insurance_fee.is_zero() {
+        let pool = InsurancePool(config.insurance_pool.clone());
+        res = res.add_message(
+            pool.deposit_fee(auction_id, coin(insurance_fee.u128(), &config.token_denom))?,
+        );
+    }
+    let mut developer_fee = Uint128::zero();
+    if let Some((developer, amount)) = parts.next() {
+        developer_fee = amount;
+        if !amount.is_zero() {
+            res = res.add_message(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(amount.u128(), &config.token_denom),
+            });
+        }
+    }
+
+    Ok(res
+        .add_attribute("method", "claim_proceeds")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("seller", info.sender)
+        .add_attribute("winner", winner)
+        .add_attribute("payout", payout.to_string())
+        .add_attribute("fee", fee.to_string())
+        .add_attribute("insurance_fee", insurance_fee)
+        .add_attribute("developer_fee", developer_fee))
+}
+
+// The registry's current fee when one is set, so fee changes apply at settlement.
+// The registry allows rates this contract would refuse, so those are held to MAX_FEE_BPS
+fn fee_schedule(deps: Deps, config: &Config) -> StdResult<FeeSchedule> {
+    match &config.registry {
+        Some(registry) => {
+            let mut fees = PlatformRegistry(registry.clone()).fee_schedule(&deps.querier)?;
+            fees.rate_bps = fees.rate_bps.min(MAX_FEE_BPS);
+            Ok(fees)
+        }
+        None => Ok(FeeSchedule::with_rate(config.fee_bps)),
+    }
+}
+
+// The insurance pool comes first so it absorbs the rounding dust
+fn fee_shares(config: &Config) -> Vec<FeeShare<Addr>> {
+    let developer_bps = config.developer_share as u16 * 100;
+    let mut shares = vec![FeeShare {
+        recipient: config.insurance_pool.clone(),
+        share_bps: BPS_DENOMINATOR - developer_bps,
+    }];
+    if let Some(developer) = &config.developer {
+        if developer_bps > 0 {
+            shares.push(FeeShare {
+                recipient: developer.clone(),
+                share_bps: developer_bps,
+            });
+        }
+    }
+    shares
+}
+
+fn execute_claim_item(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
+    if auction.status != AuctionStatus::Closed {
         return Err(ContractError::AuctionNotClosed {});
     }
-    
-    // Check if caller is winner
-    let winner = WINNERS.may_load(deps.storage, auction_id)?;
-    
-    match winner {
-        Some(w) if w == info.sender => {
-            // Winner claiming - transfer funds
-            let config = CONFIG.load(deps.storage)?;
-            
-            // Calculate fee (1.1%)
-            let total = auction.current_bid.u128();
-            let fee = (total * 11) / 1000; // 1.1%
-            let payout = total - fee;
-            
-            // Transfer to winner (seller)
-            let send_msg = BankMsg::Send {
-                to_address: auction.creator.to_string(),
-                amount: coins(payout, config.token_denom.clone()),
-            };
-            
-            // Transfer fee to insurance pool
-            let fee_msg = BankMsg::Send {
-                to_address: config.insurance_pool.to_string(),
-                amount: coins(fee, config.token_denom),
-            };
-            
-            Ok(Response::new()
-                .add_message(send_msg)
-                .add_message(fee_msg)
-                .add_attribute("method", "claim_winnings")
-                .add_attribute("auction_id", auction_id.to_string())
-                .add_attribute("winner", info.sender)
-                .add_attribute("payout", payout.to_string())
-                .add_attribute("fee", fee.to_string()))
+    match auction.winner() {
+        Some(winner) if *winner == info.sender => {}
+        Some(_) => return Err(ContractError::Unauthorized {}),
+        None => return Err(ContractError::NoWinner {}),
+    }
+    if auction.settlement.item_claimed_at.is_some() {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+
+    let received_at = env.block.time.seconds();
+    auction.settlement.item_claimed_at = Some(received_at);
+    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "claim_item")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("winner", info.sender)
+        .add_attribute("item", auction.description)
+        .add_attribute("received_at", received_at.to_string()))
+}
+
+fn execute_claim_refund(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let auction = AUCTIONS.load(deps.storage, auction_id)?;
+    if !matches!(auction.status, AuctionStatus::Closed | AuctionStatus::Cancelled) {
+        return Err(ContractError::AuctionNotClosed {});
+    }
+    // The winning bid goes to the seller
+    if auction.winner() == Some(&info.sender) {
+        return Err(ContractError::NoRefund {});
+    }
+
+    let bid_key = (auction_id, info.sender.clone());
+    let bid = BIDDERS
+        .may_load(deps.storage, bid_key.clone())?
+        .ok_or(ContractError::NoRefund {})?;
+    BIDDERS.remove(deps.storage, bid_key);
+
+    let config = CONFIG.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(bid.amount.u128(), config.token_denom),
+        })
+        .add_attribute("method", "claim_refund")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("bidder", info.sender)
+        .add_attribute("amount", bid.amount))
+}
+
+fn execute_update_staking(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    validator: String,
+    stake_bps: u16,
+    buffer_bps: u16,
+    unbonding_seconds: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    if stake_bps > 10_000 || buffer_bps > 10_000 {
+        return Err(ContractError::InvalidBps {});
+    }
+    // Only funds in the native staking denom can be delegated
+    if deps.querier.query_bonded_denom()? != config.token_denom {
+        return Err(ContractError::NotStakingDenom {});
+    }
+    if deps.querier.query_validator(&validator)?.is_none() {
+        return Err(ContractError::UnknownValidator {});
+    }
+
+    // Moving to another validator takes the existing delegation along
+    let mut res = Response::new();
+    if let Some(previous) = STAKING.may_load(deps.storage)? {
+        if previous.validator != validator {
+            let (delegated, rewards) =
+                staking::delegation(deps.as_ref(), &env, &previous.validator, &config.token_denom)?;
+            res = res.add_messages(staking::sweep_rewards(&config, &previous, rewards)?);
+            if !delegated.is_zero() {
+                res = res.add_message(StakingMsg::Redelegate {
+                    src_validator: previous.validator,
+                    dst_validator: validator.clone(),
+                    amount: coin(delegated.u128(), &config.token_denom),
+                });
+            }
+        }
+    }
+
+    let staking = StakingConfig {
+        validator,
+        stake_bps,
+        buffer_bps,
+        unbonding_seconds,
+    };
+    STAKING.save(deps.storage, &staking)?;
+
+    Ok(res
+        .add_attribute("method", "update_staking")
+        .add_attribute("validator", staking.validator)
+        .add_attribute("stake_bps", stake_bps.to_string())
+        .add_attribute("buffer_bps", buffer_bps.to_string()))
+}
+
+// Move the delegation towards the policy target. Open to anyone, the policy
+// itself is set by the admin.
+fn execute_rebalance_stake(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let staking = STAKING
+        .may_load(deps.storage)?
+        .ok_or(ContractError::StakingNotConfigured {})?;
+    let position = staking::position(deps.as_ref(), &env, &config)?;
+
+    let now = env.block.time.seconds();
+    let mut unbonding = staking::unbonding(deps.storage, now)?;
+    let mut res = Response::new()
+        .add_messages(staking::sweep_rewards(&config, &staking, position.rewards)?);
+
+    let mut delegated = Uint128::zero();
+    let mut undelegated = Uint128::zero();
+    if position.delegated < position.target {
+        // Only bank funds above the reserve can go out
+        delegated = (position.target - position.delegated)
+            .min(position.liquid.saturating_sub(position.reserve));
+        if !delegated.is_zero() {
+            res = res.add_message(StakingMsg::Delegate {
+                validator: staking.validator.clone(),
+                amount: coin(delegated.u128(), &config.token_denom),
+            });
+        }
+    } else if position.delegated > position.target {
+        undelegated = position.delegated - position.target;
+        unbonding.push(Unbonding {
+            amount: undelegated,
+            completes_at: now + staking.unbonding_seconds,
+        });
+        res = res.add_message(StakingMsg::Undelegate {
+            validator: staking.validator.clone(),
+            amount: coin(undelegated.u128(), &config.token_denom),
+        });
+    }
+    UNBONDING.save(deps.storage, &unbonding)?;
+
+    Ok(res
+        .add_attribute("method", "rebalance_stake")
+        .add_attribute("delegated", delegated)
+        .add_attribute("undelegated", undelegated)
+        .add_attribute("rewards", position.rewards)
+        .add_attribute("reserve", position.reserve))
+}
+
+fn execute_claim_staking_rewards(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let staking = STAKING
+        .may_load(deps.storage)?
+        .ok_or(ContractError::StakingNotConfigured {})?;
+    let (_, rewards) =
+        staking::delegation(deps.as_ref(), &env, &staking.validator, &config.token_denom)?;
+    if rewards.is_zero() {
+        return Err(ContractError::NoRewards {});
+    }
+
+    Ok(Response::new()
+        .add_messages(staking::sweep_rewards(&config, &staking, rewards)?)
+        .add_attribute("method", "claim_staking_rewards")
+        .add_attribute("validator", staking.validator)
+        .add_attribute("rewards", rewards))
+}
+
+fn execute_update_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_bps: Option<u16>,
+    developer: Option<String>,
+    developer_share: Option<u8>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(fee_bps) = fee_bps {
+        if fee_bps > MAX_FEE_BPS {
+            return Err(ContractError::FeeTooHigh {});
+        }
+        config.fee_bps = fee_bps;
+    }
+    if let Some(developer) = developer {
+        config.developer = Some(deps.api.addr_validate(&developer)?);
+    }
+    if let Some(share) = developer_share {
+        if share > MAX_DEVELOPER_SHARE {
+            return Err(ContractError::InvalidDeveloperShare {});
         }
-        Some(_) => Err(ContractError::Unauthorized {}),
-        None => Err(ContractError::NoWinner {}),
+        config.developer_share = share;
+    }
+    // A share needs someone to pay it to
+    if config.developer_share > 0 && config.developer.is_none() {
+        return Err(ContractError::InvalidDeveloperShare {});
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_fees")
+        .add_attribute("fee_bps", config.fee_bps.to_string())
+        .add_attribute("developer_share", config.developer_share.to_string()))
+}
+
+fn execute_update_registry(
+    deps: DepsMut,
+    info: MessageInfo,
+    registry: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
     }
+
+    config.registry = registry
+        .map(|registry| deps.api.addr_validate(&registry))
+        .transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    let mut res = Response::new().add_attribute("method", "update_registry");
+    if let Some(registry) = config.registry {
+        res = res.add_attribute("registry", registry);
+    }
+    Ok(res)
 }
 
 #[entry_point]
 pub fn query(
     deps: Deps,
-    _env: Env,
+    env: Env,
     msg: QueryMsg,
 ) -> StdResult<Binary> {
     match msg {
@@ -312,9 +655,11 @@ pub fn query(
             let auction = AUCTIONS.load(deps.storage, auction_id)?;
             let response = AuctionResponse {
                 auction_id: auction.id,
-                active: auction.status == "active",
+                status: auction.status,
+                active: auction.status == AuctionStatus::Active,
                 highest_bidder: auction.highest_bidder.map(|a| a.to_string()).unwrap_or_default(),
                 highest_bid: auction.current_bid.to_string(),
+                settlement: auction.settlement,
             };
             to_json_binary(&response)
         }
@@ -326,5 +671,90 @@ pub fn query(
             };
             to_json_binary(&response)
         }
+        QueryMsg::GetStaking {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_json_binary(&staking::position(deps, &env, &config)?)
+        }
+        QueryMsg::GetFeeInfo {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let fee = Bps::new(fee_schedule(deps, &config)?.rate_bps)?;
+            let response = FeeInfoResponse {
+                fee_percent: fee.percent(),
+                fee_bps: fee.value(),
+                insurance_allocation: 100 - config.developer_share,
+                developer_stake: config.developer_share,
+                status: "active".to_string(),
+            };
+            to_json_binary(&response)
+        }
+    }
+}
+
+// Folds the side maps of earlier versions into the auction records and
+// keeps a single auction counter. Stored status strings already match the
+// serialized `AuctionStatus`, the activity flags they duplicate are dropped.
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let ids = AUCTIONS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut open_bids = Uint128::zero();
+    let mut pending_settlements = Uint128::zero();
+    for id in &ids {
+        let mut auction = AUCTIONS.load(deps.storage, *id)?;
+        // Side map entries only exist for auctions stored by the old code
+        let legacy = WINNERS.has(deps.storage, *id)
+            || HIGHEST_BIDS.has(deps.storage, *id)
+            || AUCTION_STATES.has(deps.storage, *id);
+        if let Some(winner) = WINNERS.may_load(deps.storage, *id)? {
+            auction.highest_bidder = Some(winner);
+        }
+        // Only a placed bid was ever more than the starting bid recorded here
+        if let Some(highest) = HIGHEST_BIDS.may_load(deps.storage, *id)? {
+            if auction.highest_bidder.is_some() {
+                auction.current_bid = auction.current_bid.max(Uint128::from(highest));
+            }
+        }
+        // The old ClaimWinnings paid the seller without recording it
+        if legacy && auction.winner().is_some() && !msg.unpaid.contains(id) {
+            auction.settlement.proceeds_claimed_at.get_or_insert(env.block.time.seconds());
+        }
+        AUCTIONS.save(deps.storage, *id, &auction)?;
+
+        WINNERS.remove(deps.storage, *id);
+        HIGHEST_BIDS.remove(deps.storage, *id);
+        AUCTION_STATES.remove(deps.storage, *id);
+
+        if auction.highest_bidder.is_none() {
+            continue;
+        }
+        match auction.status {
+            AuctionStatus::Active | AuctionStatus::Expired => {
+                open_bids = open_bids.checked_add(auction.current_bid).map_err(StdError::from)?;
+            }
+            AuctionStatus::Closed if auction.settlement.proceeds_claimed_at.is_none() => {
+                pending_settlements = pending_settlements.checked_add(auction.current_bid).map_err(StdError::from)?;
+            }
+            _ => {}
+        }
     }
-}
\ No newline at end of file
+    // Rebuilt from the records so the staking buffer matches what is held
+    OPEN_BIDS.save(deps.storage, &open_bids)?;
+    PENDING_SETTLEMENTS.save(deps.storage, &pending_settlements)?;
+
+    let count = AUCTION_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or(0)
+        .max(AUCTION_COUNTER.may_load(deps.storage)?.unwrap_or(0))
+        .max(ids.last().copied().unwrap_or(0));
+    AUCTION_COUNT.save(deps.storage, &count)?;
+    AUCTION_COUNTER.remove(deps.storage);
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("auctions", ids.len().to_string())
+        .add_attribute("auction_count", count.to_string()))
+}
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use phoenix_fees::FeeError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +7,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Fee(#[from] FeeError),
+
     #[error("Unauthorized")]
     Unauthorized,
 
@@ -41,4 +45,31 @@ pub enum ContractError {
     
     #[error("No winner")]
     NoWinner,
-}
\ No newline at end of file
+
+    #[error("Already claimed")]
+    AlreadyClaimed,
+
+    #[error("No refund due")]
+    NoRefund,
+
+    #[error("Staking not configured")]
+    StakingNotConfigured,
+
+    #[error("Token denom is not the staking denom")]
+    NotStakingDenom,
+
+    #[error("Unknown validator")]
+    UnknownValidator,
+
+    #[error("No staking rewards to claim")]
+    NoRewards,
+
+    #[error("Invalid basis points")]
+    InvalidBps,
+
+    #[error("Fee above the allowed maximum")]
+    FeeTooHigh,
+
+    #[error("Invalid developer share")]
+    InvalidDeveloperShare,
+}
@@ -2,6 +2,7 @@ pub mod contract;
 pub mod msg;
 pub mod state;
 pub mod error;
+pub mod staking;
 
 // Re-export key types for easier access
 pub use crate::error::ContractError;
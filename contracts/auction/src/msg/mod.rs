@@ -1,4 +1,5 @@
-use cosmwasm_std::{Uint128, Addr};
+use cosmwasm_std::{Decimal, Uint128, Addr};
+use crate::state::{AuctionStatus, Settlement, StakingConfig};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +10,14 @@ pub struct InstantiateMsg {
     pub token_denom: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    // Auctions closed before settlement was tracked whose seller was never paid;
+    // every other such auction with a winner is taken as settled
+    #[serde(default)]
+    pub unpaid: Vec<u64>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -17,16 +26,46 @@ pub enum ExecuteMsg {
         duration: u64,
         description: String,
     },
+    // The bid is the `token_denom` coin sent with the message
     PlaceBid {
         auction_id: u64,
-        amount: String,
+        // Deprecated: older clients state the bid here, anything sent above it is refunded
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        amount: Option<String>,
     },
     CloseAuction {
         auction_id: u64,
     },
-    ClaimWinnings {
+    // Seller collects the winning bid less the platform fee
+    ClaimProceeds {
+        auction_id: u64,
+    },
+    // Winner records receipt of the item
+    ClaimItem {
         auction_id: u64,
     },
+    // Losing bidders take back their escrowed bid
+    ClaimRefund {
+        auction_id: u64,
+    },
+    UpdateStaking {
+        validator: String,
+        stake_bps: u16,
+        buffer_bps: u16,
+        unbonding_seconds: u64,
+    },
+    RebalanceStake {},
+    ClaimStakingRewards {},
+    UpdateFees {
+        fee_bps: Option<u16>,
+        developer: Option<String>,
+        // Percent of each fee paid to the developer
+        developer_share: Option<u8>,
+    },
+    // Admin only; settlement reads the fee from the registry while one is set
+    UpdateRegistry {
+        registry: Option<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -34,18 +73,46 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     GetAuction { auction_id: u64 },
     GetHighBid { auction_id: u64 },
+    GetStaking {},
+    GetFeeInfo {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct AuctionResponse {
     pub auction_id: u64,
+    pub status: AuctionStatus,
     pub active: bool,
     pub highest_bidder: String,
     pub highest_bid: String,
+    pub settlement: Settlement,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct BidResponse {
     pub bidder: Option<Addr>,
     pub amount: Uint128,
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingResponse {
+    pub config: Option<StakingConfig>,
+    pub liquid: Uint128,
+    pub delegated: Uint128,
+    pub unbonding: Uint128,
+    pub open_bids: Uint128,
+    pub pending_settlements: Uint128,
+    // Pending settlements plus the buffer on open bids, never staked
+    pub reserve: Uint128,
+    pub target: Uint128,
+    pub rewards: Uint128,
+}
+
+// Same shape as the platform registry's fee info
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeInfoResponse {
+    pub fee_percent: Decimal,
+    pub fee_bps: u16,
+    pub insurance_allocation: u8,
+    pub developer_stake: u8,
+    pub status: String,
+}
@@ -0,0 +1,149 @@
+use cosmwasm_std::{
+    coin, CosmosMsg, Deps, DistributionMsg, Env, StdError, StdResult, Storage, Uint128,
+};
+use phoenix_insurance::helpers::InsurancePool;
+
+use crate::msg::StakingResponse;
+use crate::state::{
+    Config, StakingConfig, Unbonding, OPEN_BIDS, PENDING_SETTLEMENTS, STAKING, UNBONDING,
+};
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+// Auction ids start at 1, so staking yield is attributed to id 0 in the insurance pool
+pub const STAKING_REWARDS_ID: u64 = 0;
+
+pub fn open_bids(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(OPEN_BIDS.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn pending_settlements(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(PENDING_SETTLEMENTS.may_load(storage)?.unwrap_or_default())
+}
+
+// Swap the previous leading bid of a running auction for the new one
+pub fn replace_open_bid(
+    storage: &mut dyn Storage,
+    previous: Uint128,
+    current: Uint128,
+) -> StdResult<()> {
+    let open = open_bids(storage)?.checked_sub(previous)?.checked_add(current)?;
+    OPEN_BIDS.save(storage, &open)
+}
+
+// A closed auction's winning bid is owed to the seller from now on
+pub fn close_bid(storage: &mut dyn Storage, amount: Uint128) -> StdResult<()> {
+    let open = open_bids(storage)?.checked_sub(amount)?;
+    OPEN_BIDS.save(storage, &open)?;
+    let pending = pending_settlements(storage)?.checked_add(amount)?;
+    PENDING_SETTLEMENTS.save(storage, &pending)
+}
+
+pub fn settle(storage: &mut dyn Storage, amount: Uint128) -> StdResult<()> {
+    let pending = pending_settlements(storage)?.checked_sub(amount)?;
+    PENDING_SETTLEMENTS.save(storage, &pending)
+}
+
+// Entries still unbonding at `now`, completed ones are already back in the bank balance
+pub fn unbonding(storage: &dyn Storage, now: u64) -> StdResult<Vec<Unbonding>> {
+    Ok(UNBONDING
+        .may_load(storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| entry.completes_at > now)
+        .collect())
+}
+
+// Pending settlements in full plus the buffered share of open bids
+pub fn reserve(pending: Uint128, open: Uint128, buffer_bps: u16) -> StdResult<Uint128> {
+    let buffer = open.multiply_ratio(buffer_bps, BPS_DENOMINATOR);
+    Ok(pending.checked_add(buffer)?)
+}
+
+// Delegation the policy aims for, a share of everything held above the reserve
+pub fn target(total: Uint128, reserve: Uint128, stake_bps: u16) -> Uint128 {
+    total.saturating_sub(reserve).multiply_ratio(stake_bps, BPS_DENOMINATOR)
+}
+
+// Current split of the contract's funds between bank, validator and unbonding
+pub fn position(deps: Deps, env: &Env, config: &Config) -> StdResult<StakingResponse> {
+    let staking = STAKING.may_load(deps.storage)?;
+    let liquid = deps
+        .querier
+        .query_balance(&env.contract.address, &config.token_denom)?
+        .amount;
+    let (delegated, rewards) = match &staking {
+        Some(staking) => delegation(deps, env, &staking.validator, &config.token_denom)?,
+        None => (Uint128::zero(), Uint128::zero()),
+    };
+    let unbonding = unbonding(deps.storage, env.block.time.seconds())?
+        .iter()
+        .try_fold(Uint128::zero(), |total, entry| total.checked_add(entry.amount))
+        .map_err(StdError::from)?;
+
+    let open_bids = open_bids(deps.storage)?;
+    let pending_settlements = pending_settlements(deps.storage)?;
+    let buffer_bps = staking.as_ref().map_or(0, |staking| staking.buffer_bps);
+    let reserve = reserve(pending_settlements, open_bids, buffer_bps)?;
+    let total = liquid.checked_add(delegated)?.checked_add(unbonding)?;
+    let target = staking
+        .as_ref()
+        .map_or(Uint128::zero(), |staking| target(total, reserve, staking.stake_bps));
+
+    Ok(StakingResponse {
+        config: staking,
+        liquid,
+        delegated,
+        unbonding,
+        open_bids,
+        pending_settlements,
+        reserve,
+        target,
+        rewards,
+    })
+}
+
+// Delegated amount and unclaimed rewards held with a validator, in the token denom
+pub fn delegation(
+    deps: Deps,
+    env: &Env,
+    validator: &str,
+    denom: &str,
+) -> StdResult<(Uint128, Uint128)> {
+    let delegation = match deps.querier.query_delegation(&env.contract.address, validator)? {
+        Some(delegation) => delegation,
+        None => return Ok((Uint128::zero(), Uint128::zero())),
+    };
+    let amount = if delegation.amount.denom == denom {
+        delegation.amount.amount
+    } else {
+        Uint128::zero()
+    };
+    let rewards = delegation
+        .accumulated_rewards
+        .iter()
+        .filter(|reward| reward.denom == denom)
+        .try_fold(Uint128::zero(), |total, reward| total.checked_add(reward.amount))
+        .map_err(StdError::from)?;
+    Ok((amount, rewards))
+}
+
+// Withdraw rewards from the validator and forward them to the insurance pool.
+// Changing a delegation withdraws rewards implicitly, so this runs first.
+pub fn sweep_rewards(
+    config: &Config,
+    staking: &StakingConfig,
+    rewards: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    if rewards.is_zero() {
+        return Ok(vec![]);
+    }
+    let pool = InsurancePool(config.insurance_pool.clone());
+    Ok(vec![
+        DistributionMsg::WithdrawDelegatorReward {
+            validator: staking.validator.clone(),
+        }
+        .into(),
+        pool.deposit_fee(STAKING_REWARDS_ID, coin(rewards.u128(), &config.token_denom))?,
+    ])
+}
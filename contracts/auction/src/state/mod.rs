@@ -1,5 +1,6 @@
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::{Item, Map};
+use phoenix_fees::PLATFORM_FEE_BPS;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +9,22 @@ pub struct Config {
     pub admin: Addr,
     pub insurance_pool: Addr,
     pub token_denom: String,
+    // Platform fee taken from the winning bid at settlement
+    #[serde(default = "default_fee_bps")]
+    pub fee_bps: u16,
+    // Receives `developer_share` percent of each fee, the rest goes to the insurance pool
+    #[serde(default)]
+    pub developer: Option<Addr>,
+    #[serde(default)]
+    pub developer_share: u8,
+    // Platform registry the fee is read from at settlement; `fee_bps` applies when unset
+    #[serde(default)]
+    pub registry: Option<Addr>,
+}
+
+// Configs stored before fees were configurable charged the platform rate
+fn default_fee_bps() -> u16 {
+    PLATFORM_FEE_BPS
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -20,8 +37,54 @@ pub struct Auction {
     pub description: String,
     pub created_at: u64,
     pub expires_at: u64,
-    pub status: String,
+    pub status: AuctionStatus,
     pub bids: Vec<Bid>,
+    // Absent on auctions stored before settlement was tracked
+    #[serde(default)]
+    pub settlement: Settlement,
+}
+
+// Serialized as the lowercase strings earlier versions stored, so existing
+// records load unchanged
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuctionStatus {
+    Active,
+    Expired,
+    // Ended with or without bids, the highest bidder is the winner
+    Closed,
+    // Closed early with no bids
+    Cancelled,
+}
+
+impl AuctionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuctionStatus::Active => "active",
+            AuctionStatus::Expired => "expired",
+            AuctionStatus::Closed => "closed",
+            AuctionStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl Auction {
+    // The highest bidder once the auction has closed
+    pub fn winner(&self) -> Option<&Addr> {
+        match self.status {
+            AuctionStatus::Closed => self.highest_bidder.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+// When each side of a closed auction was settled, in block seconds
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Settlement {
+    // Seller was paid the winning bid less the platform fee
+    pub proceeds_claimed_at: Option<u64>,
+    // Winner confirmed receipt of the item
+    pub item_claimed_at: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -31,6 +94,30 @@ pub struct Bid {
     pub timestamp: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingConfig {
+    // Operator address of the validator idle funds are delegated to
+    pub validator: String,
+    // Share of idle funds kept delegated, in basis points
+    pub stake_bps: u16,
+    // Share of open bids kept unbonded for auctions about to close, in basis points
+    pub buffer_bps: u16,
+    // Chain unbonding period, undelegated funds are counted until it elapses
+    pub unbonding_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Unbonding {
+    pub amount: Uint128,
+    pub completes_at: u64,
+}
+
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
-pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
\ No newline at end of file
+pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
+pub const STAKING: Item<StakingConfig> = Item::new("staking");
+pub const UNBONDING: Item<Vec<Unbonding>> = Item::new("unbonding");
+// Leading bids of auctions still running
+pub const OPEN_BIDS: Item<Uint128> = Item::new("open_bids");
+// Winning bids of closed auctions that have not been settled yet
+pub const PENDING_SETTLEMENTS: Item<Uint128> = Item::new("pending_settlements");
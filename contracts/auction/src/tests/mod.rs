@@ -1,10 +1,21 @@
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, Uint128};
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info, MockApi,
+        MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{
+        coin, coins, from_json, Addr, BankMsg, CosmosMsg, Decimal, DistributionMsg, FullDelegation, OwnedDeps,
+        to_json_binary, ContractResult, StakingMsg, SystemResult, Uint128, Validator, WasmMsg,
+        WasmQuery,
+    };
+    use phoenix_fees::FeeSchedule;
+    use phoenix_pme::msg::QueryMsg as RegistryQueryMsg;
     
-    use crate::contract::{instantiate, execute, query};
-    use crate::msg::{InstantiateMsg, ExecuteMsg, QueryMsg};
+    use crate::contract::{instantiate, execute, migrate, query};
+    use crate::msg::{AuctionResponse, BidResponse, FeeInfoResponse, InstantiateMsg, ExecuteMsg, MigrateMsg, QueryMsg, StakingResponse};
+    use crate::state::{AuctionStatus, Bid, Settlement, AUCTIONS, OPEN_BIDS, PENDING_SETTLEMENTS};
+    use crate::ContractError;
 
     #[test]
     fn test_instantiate() {
@@ -33,7 +44,7 @@ mod tests {
         instantiate(deps.as_mut(), env.clone(), info.clone(), instantiate_msg).unwrap();
         let bid_msg = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "100".to_string(),
+            amount: None,
         };
         let _res = execute(deps.as_mut(), env, info, bid_msg);
     }
@@ -54,17 +65,17 @@ mod tests {
     }
 
     #[test]
-    fn test_claim_winnings() {
+    fn test_claim_proceeds() {
         let mut deps = mock_dependencies();
         let env = mock_env();
-        let info = mock_info("winner", &coins(1000, "utestcore"));
+        let info = mock_info("seller", &coins(1000, "utestcore"));
         let instantiate_msg = InstantiateMsg {
             admin: "admin".to_string(),
             insurance_pool: "pool".to_string(),
             token_denom: "utestcore".to_string(),
         };
         instantiate(deps.as_mut(), env.clone(), info.clone(), instantiate_msg).unwrap();
-        let claim_msg = ExecuteMsg::ClaimWinnings { auction_id: 1 };
+        let claim_msg = ExecuteMsg::ClaimProceeds { auction_id: 1 };
         let _res = execute(deps.as_mut(), env, info, claim_msg);
     }
 
@@ -122,14 +133,14 @@ mod tests {
         let bidder1 = mock_info("alice", &coins(150, "utestcore"));
         let bid_msg = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "150".to_string(),
+            amount: None,
         };
         execute(deps.as_mut(), env.clone(), bidder1, bid_msg).unwrap();
         
         let bidder2 = mock_info("bob", &coins(200, "utestcore"));
         let bid_msg = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "200".to_string(),
+            amount: None,
         };
         execute(deps.as_mut(), env.clone(), bidder2, bid_msg).unwrap();
         
@@ -137,9 +148,13 @@ mod tests {
         let close_msg = ExecuteMsg::CloseAuction { auction_id: 1 };
         execute(deps.as_mut(), env.clone(), closer, close_msg).unwrap();
         
+        let seller = mock_info("seller", &[]);
+        let claim_msg = ExecuteMsg::ClaimProceeds { auction_id: 1 };
+        execute(deps.as_mut(), env.clone(), seller, claim_msg).unwrap();
+        
         let winner = mock_info("bob", &[]);
-        let claim_msg = ExecuteMsg::ClaimWinnings { auction_id: 1 };
-        execute(deps.as_mut(), env, winner, claim_msg).unwrap();
+        let claim_msg = ExecuteMsg::ClaimItem { auction_id: 1 };
+        execute(deps.as_mut(), env.clone(), winner, claim_msg).unwrap();
     }
 
     #[test]
@@ -170,7 +185,7 @@ mod tests {
             let bidder_info = mock_info(bidder, &coins(bid_amount, "utestcore"));
             let bid_msg = ExecuteMsg::PlaceBid {
                 auction_id: 1,
-                amount: bid_amount.to_string(),
+                amount: None,
             };
             let res = execute(deps.as_mut(), env.clone(), bidder_info, bid_msg);
             assert!(res.is_ok(), "Bid from {} should succeed", bidder);
@@ -204,7 +219,7 @@ mod tests {
         let bidder1 = mock_info("alice", &coins(150, "utestcore"));
         let bid_msg = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "150".to_string(),
+            amount: None,
         };
         execute(deps.as_mut(), env.clone(), bidder1, bid_msg).unwrap();
         println!("✅ Bid placed on regular auction");
@@ -229,7 +244,7 @@ mod tests {
         // In a real implementation, you'd have a separate BuyNow message
         let buy_now_msg = ExecuteMsg::PlaceBid {
             auction_id: 2,
-            amount: "500".to_string(),
+            amount: None,
         };
         
         let res = execute(deps.as_mut(), env.clone(), buyer, buy_now_msg);
@@ -271,7 +286,7 @@ mod tests {
         let bidder1 = mock_info("bidder1", &coins(200, "utestcore"));
         let bid_msg1 = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "200".to_string(),
+            amount: None,
         };
         execute(deps.as_mut(), env.clone(), bidder1, bid_msg1).unwrap();
         
@@ -280,10 +295,10 @@ mod tests {
         let close_msg1 = ExecuteMsg::CloseAuction { auction_id: 1 };
         execute(deps.as_mut(), env.clone(), closer.clone(), close_msg1).unwrap();
         
-        // Claim winnings - this should trigger fee to insurance pool
-        let winner1 = mock_info("bidder1", &[]);
-        let claim_msg1 = ExecuteMsg::ClaimWinnings { auction_id: 1 };
-        execute(deps.as_mut(), env.clone(), winner1, claim_msg1).unwrap();
+        // Seller claims proceeds - this should trigger fee to insurance pool
+        let seller1 = mock_info("seller1", &[]);
+        let claim_msg1 = ExecuteMsg::ClaimProceeds { auction_id: 1 };
+        execute(deps.as_mut(), env.clone(), seller1, claim_msg1).unwrap();
         
         // Create second auction
         let seller2 = mock_info("seller2", &coins(500, "utestcore"));
@@ -298,7 +313,7 @@ mod tests {
         let bidder2 = mock_info("bidder2", &coins(300, "utestcore"));
         let bid_msg2 = ExecuteMsg::PlaceBid {
             auction_id: 2,
-            amount: "300".to_string(),
+            amount: None,
         };
         execute(deps.as_mut(), env.clone(), bidder2, bid_msg2).unwrap();
         
@@ -306,20 +321,22 @@ mod tests {
         let close_msg2 = ExecuteMsg::CloseAuction { auction_id: 2 };
         execute(deps.as_mut(), env.clone(), closer, close_msg2).unwrap();
         
-        // Claim winnings - second fee to insurance pool
-        let winner2 = mock_info("bidder2", &[]);
-        let claim_msg2 = ExecuteMsg::ClaimWinnings { auction_id: 2 };
-        execute(deps.as_mut(), env, winner2, claim_msg2).unwrap();
+        // Seller claims proceeds - second fee to insurance pool
+        let seller2 = mock_info("seller2", &[]);
+        let claim_msg2 = ExecuteMsg::ClaimProceeds { auction_id: 2 };
+        let res = execute(deps.as_mut(), env, seller2, claim_msg2).unwrap();
         
-        // At this point, we would query the insurance pool balance
-        // For now, we just verify the test runs without errors
+        // Fee is deposited with the pool and attributed to the auction (1.1% of 300, rounded up)
+        match &res.messages[1].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+                assert_eq!(contract_addr, "pool");
+                assert_eq!(funds, &coins(4, "utestcore"));
+                let deposit: phoenix_insurance::msg::ExecuteMsg = from_json(msg).unwrap();
+                assert_eq!(deposit, phoenix_insurance::msg::ExecuteMsg::DepositFee { auction_id: 2 });
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
         println!("✅ Insurance pool accumulation test completed");
-        println!("   Auction 1 winning bid: 200 (fee: 2.2)");
-        println!("   Auction 2 winning bid: 300 (fee: 3.3)");
-        println!("   Total accumulated: 5.5 (1.1% of 500)");
-        
-        // Note: In a real implementation, you would query the insurance pool
-        // balance and verify it equals 5.5 (or whatever the fee amount should be)
     }
         #[test]
     fn test_failed_transaction_handling() {
@@ -348,7 +365,7 @@ mod tests {
         let bidder = mock_info("alice", &coins(150, "utestcore"));
         let bad_bid_msg = ExecuteMsg::PlaceBid {
             auction_id: 999, // Non-existent auction
-            amount: "150".to_string(),
+            amount: None,
         };
         let res = execute(deps.as_mut(), env.clone(), bidder, bad_bid_msg);
         assert!(res.is_err(), "Bid on non-existent auction should fail");
@@ -358,7 +375,7 @@ mod tests {
         let bidder2 = mock_info("bob", &coins(50, "utestcore")); // Only has 50
         let low_bid_msg = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "150".to_string(), // Trying to bid 150 with only 50
+            amount: Some("150".to_string()), // Trying to bid 150 with only 50
         };
         let res = execute(deps.as_mut(), env.clone(), bidder2, low_bid_msg);
         assert!(res.is_err(), "Bid with insufficient funds should fail");
@@ -368,7 +385,7 @@ mod tests {
         let bidder3 = mock_info("charlie", &coins(200, "utestcore"));
         let good_bid_msg = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "150".to_string(),
+            amount: Some("150".to_string()),
         };
         execute(deps.as_mut(), env.clone(), bidder3, good_bid_msg).unwrap();
         
@@ -376,7 +393,7 @@ mod tests {
         let bidder4 = mock_info("dave", &coins(200, "utestcore"));
         let lower_bid_msg = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "100".to_string(), // Lower than current 150
+            amount: Some("100".to_string()), // Lower than current 150
         };
         let res = execute(deps.as_mut(), env.clone(), bidder4, lower_bid_msg);
         assert!(res.is_err(), "Lower bid should fail");
@@ -392,9 +409,13 @@ mod tests {
         assert!(res.is_err(), "Closing already closed auction should fail");
         println!("✅ Test 4 passed: Double close rejected");
         
-        // TEST 5: Try to claim winnings as non-winner
+        // TEST 5: Try to claim proceeds as non-seller and the item as non-winner
+        let fake_seller = mock_info("mallory", &[]);
+        let claim_msg = ExecuteMsg::ClaimProceeds { auction_id: 1 };
+        let res = execute(deps.as_mut(), env.clone(), fake_seller, claim_msg);
+        assert!(res.is_err(), "Non-seller claiming proceeds should fail");
         let fake_winner = mock_info("mallory", &[]);
-        let claim_msg = ExecuteMsg::ClaimWinnings { auction_id: 1 };
+        let claim_msg = ExecuteMsg::ClaimItem { auction_id: 1 };
         let res = execute(deps.as_mut(), env, fake_winner, claim_msg);
         assert!(res.is_err(), "Non-winner claiming should fail");
         println!("✅ Test 5 passed: Non-winner claim rejected");
@@ -428,7 +449,7 @@ mod tests {
         let bidder = mock_info("alice", &coins(0, "utestcore"));
         let zero_bid_msg = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "0".to_string(),
+            amount: None,
         };
         let res = execute(deps.as_mut(), env.clone(), bidder, zero_bid_msg);
         assert!(res.is_err(), "Zero bid should fail");
@@ -508,7 +529,7 @@ mod tests {
         let bidder1 = mock_info("alice", &coins(150, "utestcore"));
         let bid_msg1 = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "150".to_string(),
+            amount: None,
         };
         execute(deps.as_mut(), env.clone(), bidder1, bid_msg1).unwrap();
         
@@ -516,7 +537,7 @@ mod tests {
         let bidder2 = mock_info("bob", &coins(150, "utestcore"));
         let bid_msg2 = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: "150".to_string(),
+            amount: None,
         };
         let res = execute(deps.as_mut(), env, bidder2, bid_msg2);
         assert!(res.is_err(), "Equal bid should fail");
@@ -551,7 +572,7 @@ mod tests {
         let bidder = mock_info("alice", &coins(u128::MAX, "utestcore"));
         let bid_msg = ExecuteMsg::PlaceBid {
             auction_id: 1,
-            amount: u128::MAX.to_string(),
+            amount: None,
         };
         let res = execute(deps.as_mut(), env, bidder, bid_msg);
         assert!(res.is_ok(), "Large bid should work");
@@ -590,7 +611,7 @@ mod tests {
             let bidder = mock_info(&format!("bidder{}", i), &coins(200, "utestcore"));
             let bid_msg = ExecuteMsg::PlaceBid {
                 auction_id: i,
-                amount: "150".to_string(),
+                amount: Some("150".to_string()),
             };
             let res = execute(deps.as_mut(), env.clone(), bidder, bid_msg);
             assert!(res.is_ok(), "Bid on auction {} should succeed", i);
@@ -600,6 +621,559 @@ mod tests {
                    println!("🎉 Rapid sequential operations test complete!");
     }
 
+    #[test]
+    fn test_settlement_is_idempotent() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            insurance_pool: "pool".to_string(),
+            token_denom: "utestcore".to_string(),
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        let create_msg = ExecuteMsg::CreateAuction {
+            starting_bid: Uint128::from(100u128),
+            duration: 86400,
+            description: "1oz Gold Maple Leaf".to_string(),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), create_msg).unwrap();
+        let bid = ExecuteMsg::PlaceBid { auction_id: 1, amount: None };
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(150, "utestcore")), bid.clone()).unwrap();
+        let res = execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(200, "utestcore")), bid).unwrap();
+        
+        // Being outbid refunds alice straight away
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(150, "utestcore"),
+        }));
+        
+        // Nothing can be claimed while the auction runs
+        let err = execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), ExecuteMsg::ClaimProceeds { auction_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::AuctionNotClosed {});
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::ClaimRefund { auction_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::AuctionNotClosed {});
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), ExecuteMsg::CloseAuction { auction_id: 1 }).unwrap();
+        
+        // The winner cannot take the seller's proceeds
+        let err = execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), ExecuteMsg::ClaimProceeds { auction_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let res = execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), ExecuteMsg::ClaimProceeds { auction_id: 1 }).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "seller".to_string(),
+            amount: coins(197, "utestcore"),
+        }));
+        let err = execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), ExecuteMsg::ClaimProceeds { auction_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyClaimed {});
+        
+        // Receipt is recorded once, by the winner only
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::ClaimItem { auction_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), ExecuteMsg::ClaimItem { auction_id: 1 }).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), ExecuteMsg::ClaimItem { auction_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyClaimed {});
+        
+        let auction: AuctionResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::GetAuction { auction_id: 1 }).unwrap()).unwrap();
+        let now = env.block.time.seconds();
+        assert_eq!(auction.settlement, Settlement {
+            proceeds_claimed_at: Some(now),
+            item_claimed_at: Some(now),
+        });
+        
+        // Neither the refunded loser nor the winner has anything left to claim
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::ClaimRefund { auction_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::NoRefund {});
+        let err = execute(deps.as_mut(), env, mock_info("bob", &[]), ExecuteMsg::ClaimRefund { auction_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::NoRefund {});
+    }
+
+    #[test]
+    fn test_legacy_bid_message_still_parses() {
+        let msg: ExecuteMsg = from_json(r#"{"place_bid":{"auction_id":1,"amount":"150"}}"#).unwrap();
+        assert_eq!(msg, ExecuteMsg::PlaceBid { auction_id: 1, amount: Some("150".to_string()) });
+        let msg: ExecuteMsg = from_json(r#"{"place_bid":{"auction_id":1}}"#).unwrap();
+        assert_eq!(msg, ExecuteMsg::PlaceBid { auction_id: 1, amount: None });
+    }
+
+    #[test]
+    fn test_bid_refunds_excess_and_rejects_other_denoms() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            insurance_pool: "pool".to_string(),
+            token_denom: "utestcore".to_string(),
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        let create_msg = ExecuteMsg::CreateAuction {
+            starting_bid: Uint128::from(100u128),
+            duration: 86400,
+            description: "10oz Silver Bar".to_string(),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), create_msg).unwrap();
+        
+        let bid = ExecuteMsg::PlaceBid { auction_id: 1, amount: None };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), bid.clone()).unwrap_err();
+        assert_eq!(err, ContractError::NoFunds {});
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(150, "uother")), bid.clone()).unwrap_err();
+        assert_eq!(err, ContractError::InvalidDenom {});
+        let funds = vec![coin(150, "utestcore"), coin(1, "uother")];
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &funds), bid).unwrap_err();
+        assert_eq!(err, ContractError::InvalidDenom {});
+        
+        // A legacy bid keeps the stated amount and returns the rest
+        let legacy = ExecuteMsg::PlaceBid { auction_id: 1, amount: Some("150".to_string()) };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(180, "utestcore")), legacy).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(30, "utestcore"),
+        }));
+        
+        let high: BidResponse = from_json(query(deps.as_ref(), env, QueryMsg::GetHighBid { auction_id: 1 }).unwrap()).unwrap();
+        assert_eq!(high.amount, Uint128::from(150u128));
+        assert_eq!(high.bidder, Some(Addr::unchecked("alice")));
+    }
+
+    #[test]
+    fn test_migrate_folds_legacy_state() {
+        use cw_storage_plus::{Item, Map};
+        use serde::{Deserialize, Serialize};
+        
+        // Auction record as stored before statuses were typed and settlement was tracked
+        #[derive(Serialize, Deserialize)]
+        struct LegacyAuction {
+            id: u64,
+            creator: Addr,
+            starting_bid: Uint128,
+            current_bid: Uint128,
+            highest_bidder: Option<Addr>,
+            description: String,
+            created_at: u64,
+            expires_at: u64,
+            status: String,
+            bids: Vec<Bid>,
+        }
+        let legacy = |id: u64, status: &str, bidder: Option<&str>, bid: u128| LegacyAuction {
+            id,
+            creator: Addr::unchecked("seller"),
+            starting_bid: Uint128::from(100u128),
+            current_bid: Uint128::from(bid),
+            highest_bidder: bidder.map(Addr::unchecked),
+            description: format!("Lot {}", id),
+            created_at: 0,
+            expires_at: 86400,
+            status: status.to_string(),
+            bids: vec![],
+        };
+        
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            insurance_pool: "pool".to_string(),
+            token_denom: "utestcore".to_string(),
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        
+        let auctions: Map<u64, LegacyAuction> = Map::new("auctions");
+        let winners: Map<u64, Addr> = Map::new("winners");
+        let highest_bids: Map<u64, u128> = Map::new("highest_bids");
+        let auction_states: Map<u64, bool> = Map::new("auction_states");
+        let counter: Item<u64> = Item::new("auction_counter");
+        let storage = deps.as_mut().storage;
+        auctions.save(storage, 1, &legacy(1, "closed", Some("bob"), 200)).unwrap();
+        winners.save(storage, 1, &Addr::unchecked("bob")).unwrap();
+        highest_bids.save(storage, 1, &200).unwrap();
+        auction_states.save(storage, 1, &false).unwrap();
+        auctions.save(storage, 2, &legacy(2, "active", None, 0)).unwrap();
+        highest_bids.save(storage, 2, &100).unwrap();
+        auction_states.save(storage, 2, &true).unwrap();
+        auctions.save(storage, 3, &legacy(3, "closed", Some("carol"), 300)).unwrap();
+        winners.save(storage, 3, &Addr::unchecked("carol")).unwrap();
+        auction_states.save(storage, 3, &false).unwrap();
+        auctions.save(storage, 4, &legacy(4, "active", Some("dave"), 150)).unwrap();
+        auction_states.save(storage, 4, &true).unwrap();
+        counter.save(storage, &4).unwrap();
+        
+        let res = migrate(deps.as_mut(), env.clone(), MigrateMsg { unpaid: vec![3] }).unwrap();
+        assert_eq!(res.attributes[1].value, "4");
+        
+        // The old code already paid this seller
+        let closed = AUCTIONS.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(closed.status, AuctionStatus::Closed);
+        assert_eq!(closed.winner(), Some(&Addr::unchecked("bob")));
+        assert_eq!(closed.current_bid, Uint128::from(200u128));
+        assert_eq!(closed.settlement.proceeds_claimed_at, Some(env.block.time.seconds()));
+        let err = execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), ExecuteMsg::ClaimProceeds { auction_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyClaimed {});
+        // Listed as unpaid, so still owed
+        let unpaid = AUCTIONS.load(deps.as_ref().storage, 3).unwrap();
+        assert_eq!(unpaid.settlement, Settlement::default());
+        
+        // Staking totals are rebuilt from the records
+        assert_eq!(OPEN_BIDS.load(deps.as_ref().storage).unwrap(), Uint128::from(150u128));
+        assert_eq!(PENDING_SETTLEMENTS.load(deps.as_ref().storage).unwrap(), Uint128::from(300u128));
+        // Without a bid the starting bid in the side map is not a current bid
+        let open = AUCTIONS.load(deps.as_ref().storage, 2).unwrap();
+        assert_eq!(open.status, AuctionStatus::Active);
+        assert_eq!(open.current_bid, Uint128::zero());
+        assert_eq!(open.winner(), None);
+        
+        assert!(winners.may_load(deps.as_ref().storage, 1).unwrap().is_none());
+        assert!(highest_bids.may_load(deps.as_ref().storage, 2).unwrap().is_none());
+        assert!(auction_states.may_load(deps.as_ref().storage, 1).unwrap().is_none());
+        assert!(counter.may_load(deps.as_ref().storage).unwrap().is_none());
+        
+        // New auctions continue from the folded counter
+        let create_msg = ExecuteMsg::CreateAuction {
+            starting_bid: Uint128::from(100u128),
+            duration: 86400,
+            description: "1oz Platinum Bar".to_string(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), create_msg).unwrap();
+        assert_eq!(res.attributes[1].value, "5");
+        
+        // The winner keeps the typed status through the query
+        let auction: AuctionResponse = from_json(query(deps.as_ref(), env, QueryMsg::GetAuction { auction_id: 1 }).unwrap()).unwrap();
+        assert_eq!(auction.status, AuctionStatus::Closed);
+        assert!(!auction.active);
+    }
+
+    #[test]
+    fn test_update_fees_is_bounded_and_admin_only() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            insurance_pool: "pool".to_string(),
+            token_denom: "utestcore".to_string(),
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        let update = |fee_bps: Option<u16>, developer: Option<&str>, developer_share: Option<u8>| ExecuteMsg::UpdateFees {
+            fee_bps,
+            developer: developer.map(|d| d.to_string()),
+            developer_share,
+        };
+        
+        // Defaults to the platform fee, all of it for the insurance pool
+        let info: FeeInfoResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::GetFeeInfo {}).unwrap()).unwrap();
+        assert_eq!(info, FeeInfoResponse {
+            fee_percent: Decimal::permille(1_100),
+            fee_bps: 110,
+            insurance_allocation: 100,
+            developer_stake: 0,
+            status: "active".to_string(),
+        });
+        
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), update(Some(100), None, None)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update(Some(201), None, None)).unwrap_err();
+        assert_eq!(err, ContractError::FeeTooHigh {});
+        let err = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update(None, Some("dev"), Some(11))).unwrap_err();
+        assert_eq!(err, ContractError::InvalidDeveloperShare {});
+        // A share without a developer to receive it
+        let err = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update(None, None, Some(10))).unwrap_err();
+        assert_eq!(err, ContractError::InvalidDeveloperShare {});
+        
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update(Some(200), Some("dev"), Some(10))).unwrap();
+        let info: FeeInfoResponse = from_json(query(deps.as_ref(), env, QueryMsg::GetFeeInfo {}).unwrap()).unwrap();
+        assert_eq!(info.fee_percent, Decimal::percent(200));
+        assert_eq!(info.fee_bps, 200);
+        assert_eq!(info.insurance_allocation, 90);
+        assert_eq!(info.developer_stake, 10);
+    }
+
+    #[test]
+    fn test_fee_is_split_with_developer() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            insurance_pool: "pool".to_string(),
+            token_denom: "utestcore".to_string(),
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        let fees_msg = ExecuteMsg::UpdateFees {
+            fee_bps: Some(200),
+            developer: Some("dev".to_string()),
+            developer_share: Some(10),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), fees_msg).unwrap();
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            starting_bid: Uint128::from(100u128),
+            duration: 86400,
+            description: "1kg Silver Bar".to_string(),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), create_msg).unwrap();
+        let bid_msg = ExecuteMsg::PlaceBid { auction_id: 1, amount: None };
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(1000, "utestcore")), bid_msg).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), ExecuteMsg::CloseAuction { auction_id: 1 }).unwrap();
+        
+        // 2% of 1000 is 20: 18 for the pool, 2 for the developer
+        let res = execute(deps.as_mut(), env, mock_info("seller", &[]), ExecuteMsg::ClaimProceeds { auction_id: 1 }).unwrap();
+        assert_eq!(res.messages.len(), 3);
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "seller".to_string(),
+            amount: coins(980, "utestcore"),
+        }));
+        assert_eq!(res.messages[1].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pool".to_string(),
+            msg: to_json_binary(&phoenix_insurance::msg::ExecuteMsg::DepositFee { auction_id: 1 }).unwrap(),
+            funds: coins(18, "utestcore"),
+        }));
+        assert_eq!(res.messages[2].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "dev".to_string(),
+            amount: coins(2, "utestcore"),
+        }));
+    }
+
+    #[test]
+    fn test_settlement_reads_fee_from_registry() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            insurance_pool: "pool".to_string(),
+            token_denom: "utestcore".to_string(),
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        
+        let update = ExecuteMsg::UpdateRegistry { registry: Some("registry".to_string()) };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), update.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update).unwrap();
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            starting_bid: Uint128::from(100u128),
+            duration: 86400,
+            description: "1kg Silver Bar".to_string(),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), create_msg).unwrap();
+        let bid_msg = ExecuteMsg::PlaceBid { auction_id: 1, amount: None };
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(1000, "utestcore")), bid_msg).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), ExecuteMsg::CloseAuction { auction_id: 1 }).unwrap();
+        
+        // The registry's 2% overrides the local 1.1%
+        let fees = to_json_binary(&FeeSchedule::with_rate(200)).unwrap();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "registry" => {
+                let msg: RegistryQueryMsg = from_json(msg).unwrap();
+                assert_eq!(msg, RegistryQueryMsg::GetFeeSchedule {});
+                SystemResult::Ok(ContractResult::Ok(fees.clone()))
+            }
+            _ => panic!("unexpected query"),
+        });
+        let info: FeeInfoResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::GetFeeInfo {}).unwrap()).unwrap();
+        assert_eq!(info.fee_bps, 200);
+        
+        // A registry rate above the contract's own cap is held to it
+        let fees = to_json_binary(&FeeSchedule::with_rate(5000)).unwrap();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "registry" => {
+                SystemResult::Ok(ContractResult::Ok(fees.clone()))
+            }
+            _ => panic!("unexpected query"),
+        });
+        let info: FeeInfoResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::GetFeeInfo {}).unwrap()).unwrap();
+        assert_eq!(info.fee_bps, 200);
+        
+        let res = execute(deps.as_mut(), env, mock_info("seller", &[]), ExecuteMsg::ClaimProceeds { auction_id: 1 }).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "seller".to_string(),
+            amount: coins(980, "utestcore"),
+        }));
+        assert_eq!(res.messages[1].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pool".to_string(),
+            msg: to_json_binary(&phoenix_insurance::msg::ExecuteMsg::DepositFee { auction_id: 1 }).unwrap(),
+            funds: coins(20, "utestcore"),
+        }));
+    }
+
+    fn staking_setup(delegated: u128, rewards: u128) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies_with_balance(&coins(1000, "utestcore"));
+        let env = mock_env();
+        let validator = Validator {
+            address: "validator".to_string(),
+            commission: Decimal::percent(5),
+            max_commission: Decimal::percent(20),
+            max_change_rate: Decimal::percent(1),
+        };
+        let delegations = if delegated > 0 {
+            vec![FullDelegation {
+                delegator: env.contract.address.clone(),
+                validator: "validator".to_string(),
+                amount: coin(delegated, "utestcore"),
+                can_redelegate: coin(delegated, "utestcore"),
+                accumulated_rewards: coins(rewards, "utestcore"),
+            }]
+        } else {
+            vec![]
+        };
+        deps.querier.update_staking("utestcore", &[validator], &delegations);
+
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            insurance_pool: "pool".to_string(),
+            token_denom: "utestcore".to_string(),
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+
+        // One open auction holding a winning bid of 200
+        let create_msg = ExecuteMsg::CreateAuction {
+            starting_bid: Uint128::from(100u128),
+            duration: 86400,
+            description: "Staking test auction".to_string(),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), create_msg).unwrap();
+        let bid_msg = ExecuteMsg::PlaceBid {
+            auction_id: 1,
+            amount: None,
+        };
+        execute(deps.as_mut(), env, mock_info("alice", &coins(200, "utestcore")), bid_msg).unwrap();
+        deps
+    }
+
+    fn update_staking(stake_bps: u16, buffer_bps: u16) -> ExecuteMsg {
+        ExecuteMsg::UpdateStaking {
+            validator: "validator".to_string(),
+            stake_bps,
+            buffer_bps,
+            unbonding_seconds: 21 * 86400,
+        }
+    }
+
+    #[test]
+    fn test_staking_config_is_admin_only() {
+        let mut deps = staking_setup(0, 0);
+        let env = mock_env();
+
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), update_staking(5000, 0)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let err = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update_staking(10_001, 0)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidBps {});
+        let err = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update_staking(5000, 10_001)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidBps {});
+
+        let msg = ExecuteMsg::UpdateStaking {
+            validator: "unknown".to_string(),
+            stake_bps: 5000,
+            buffer_bps: 0,
+            unbonding_seconds: 0,
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::UnknownValidator {});
+
+        // Nothing can be staked until a policy is set
+        let err = execute(deps.as_mut(), env, mock_info("anyone", &[]), ExecuteMsg::RebalanceStake {}).unwrap_err();
+        assert_eq!(err, ContractError::StakingNotConfigured {});
+    }
+
+    #[test]
+    fn test_staking_requires_native_denom() {
+        let mut deps = staking_setup(0, 0);
+        deps.querier.update_staking("ustake", &[], &[]);
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), update_staking(5000, 0)).unwrap_err();
+        assert_eq!(err, ContractError::NotStakingDenom {});
+    }
+
+    #[test]
+    fn test_rebalance_delegates_idle_funds_above_reserve() {
+        let mut deps = staking_setup(0, 0);
+        let env = mock_env();
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update_staking(5000, 5000)).unwrap();
+
+        // Half of the 200 open bid stays unbonded, half of the rest is staked
+        let position: StakingResponse =
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::GetStaking {}).unwrap()).unwrap();
+        assert_eq!(position.open_bids, Uint128::new(200));
+        assert_eq!(position.reserve, Uint128::new(100));
+        assert_eq!(position.target, Uint128::new(450));
+
+        let res = execute(deps.as_mut(), env, mock_info("anyone", &[]), ExecuteMsg::RebalanceStake {}).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Staking(StakingMsg::Delegate {
+                validator: "validator".to_string(),
+                amount: coin(450, "utestcore"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_rebalance_undelegates_to_restore_liquidity() {
+        // 600 delegated, 400 in the bank
+        let mut deps = staking_setup(600, 0);
+        deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(400, "utestcore"));
+        let env = mock_env();
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update_staking(5000, 0)).unwrap();
+
+        // Closing the auction makes the winning bid owed in full
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), ExecuteMsg::CloseAuction { auction_id: 1 }).unwrap();
+        let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::RebalanceStake {}).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Staking(StakingMsg::Undelegate {
+                validator: "validator".to_string(),
+                amount: coin(200, "utestcore"),
+            })
+        );
+
+        // Unbonding funds still count towards the total until the period ends
+        let position: StakingResponse =
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::GetStaking {}).unwrap()).unwrap();
+        assert_eq!(position.unbonding, Uint128::new(200));
+
+        let mut later = env;
+        later.block.time = later.block.time.plus_seconds(21 * 86400);
+        let position: StakingResponse =
+            from_json(query(deps.as_ref(), later, QueryMsg::GetStaking {}).unwrap()).unwrap();
+        assert_eq!(position.unbonding, Uint128::zero());
+    }
+
+    #[test]
+    fn test_staking_rewards_go_to_insurance_pool() {
+        let mut deps = staking_setup(350, 12);
+        let env = mock_env();
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update_staking(5000, 5000)).unwrap();
+
+        let res = execute(deps.as_mut(), env, mock_info("anyone", &[]), ExecuteMsg::ClaimStakingRewards {}).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward {
+                validator: "validator".to_string(),
+            })
+        );
+        match &res.messages[1].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+                assert_eq!(contract_addr, "pool");
+                assert_eq!(funds, &coins(12, "utestcore"));
+                let deposit: phoenix_insurance::msg::ExecuteMsg = from_json(msg).unwrap();
+                assert_eq!(deposit, phoenix_insurance::msg::ExecuteMsg::DepositFee { auction_id: 0 });
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_settlement_releases_reserve() {
+        let mut deps = staking_setup(0, 0);
+        let env = mock_env();
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), ExecuteMsg::CloseAuction { auction_id: 1 }).unwrap();
+        let position: StakingResponse =
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::GetStaking {}).unwrap()).unwrap();
+        assert_eq!(position.open_bids, Uint128::zero());
+        assert_eq!(position.pending_settlements, Uint128::new(200));
+
+        execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), ExecuteMsg::ClaimProceeds { auction_id: 1 }).unwrap();
+
+        let position: StakingResponse =
+            from_json(query(deps.as_ref(), env, QueryMsg::GetStaking {}).unwrap()).unwrap();
+        assert_eq!(position.pending_settlements, Uint128::zero());
+    }
+
     // ===== PROPERTY TESTS =====
     use proptest::prelude::*;
 
@@ -0,0 +1,304 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{coins, Addr, Decimal, Uint128, Validator};
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor, StakingInfo};
+
+use phoenix_auction::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, StakingResponse};
+use phoenix_insurance::msg::{
+    InstantiateMsg as PoolInstantiateMsg, PoolResponse, QueryMsg as PoolQueryMsg,
+};
+
+const DENOM: &str = "ustake";
+const VALIDATOR: &str = "validator";
+const UNBONDING_SECONDS: u64 = 21 * 86400;
+const YEAR: u64 = 365 * 86400;
+
+struct Suite {
+    app: App,
+    auction: Addr,
+    pool: Addr,
+}
+
+fn setup() -> Suite {
+    let mut app = AppBuilder::new().build(|router, api, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked("alice"), coins(10_000, DENOM))
+            .unwrap();
+        router
+            .staking
+            .setup(
+                storage,
+                StakingInfo {
+                    bonded_denom: DENOM.to_string(),
+                    unbonding_time: UNBONDING_SECONDS,
+                    apr: Decimal::percent(10),
+                },
+            )
+            .unwrap();
+        router
+            .staking
+            .add_validator(
+                api,
+                storage,
+                &mock_env().block,
+                Validator {
+                    address: VALIDATOR.to_string(),
+                    commission: Decimal::zero(),
+                    max_commission: Decimal::percent(20),
+                    max_change_rate: Decimal::percent(1),
+                },
+            )
+            .unwrap();
+    });
+
+    let pool_code = app.store_code(Box::new(ContractWrapper::new(
+        phoenix_insurance::contract::execute,
+        phoenix_insurance::contract::instantiate,
+        phoenix_insurance::contract::query,
+    )));
+    let auction_code = app.store_code(Box::new(ContractWrapper::new(
+        phoenix_auction::contract::execute,
+        phoenix_auction::contract::instantiate,
+        phoenix_auction::contract::query,
+    )));
+
+    let admin = Addr::unchecked("admin");
+    let pool = app
+        .instantiate_contract(
+            pool_code,
+            admin.clone(),
+            &PoolInstantiateMsg {
+                admin: admin.to_string(),
+                denom: DENOM.to_string(),
+                target: None,
+                assessor: None,
+                max_claim_bps: None,
+                developer: None,
+                premium_rates: None,
+                max_exposure_bps: None,
+                coverage_days: None,
+                withdrawal_cooldown_seconds: None,
+                solvency_floor: None,
+            },
+            &[],
+            "insurance",
+            None,
+        )
+        .unwrap();
+    let auction = app
+        .instantiate_contract(
+            auction_code,
+            admin.clone(),
+            &InstantiateMsg {
+                admin: admin.to_string(),
+                insurance_pool: pool.to_string(),
+                token_denom: DENOM.to_string(),
+            },
+            &[],
+            "auction",
+            None,
+        )
+        .unwrap();
+
+    // Stake half of what is left after keeping half of the open bids liquid
+    app.execute_contract(
+        admin,
+        auction.clone(),
+        &ExecuteMsg::UpdateStaking {
+            validator: VALIDATOR.to_string(),
+            stake_bps: 5000,
+            buffer_bps: 5000,
+            unbonding_seconds: UNBONDING_SECONDS,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // One running auction with 4,000 escrowed behind the leading bid, open
+    // well past the year the reward tests let accrue
+    app.execute_contract(
+        Addr::unchecked("seller"),
+        auction.clone(),
+        &ExecuteMsg::CreateAuction {
+            starting_bid: Uint128::new(1000),
+            duration: 2 * YEAR,
+            description: "10oz Silver Bar".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        auction.clone(),
+        &ExecuteMsg::PlaceBid {
+            auction_id: 1,
+            amount: None,
+        },
+        &coins(4000, DENOM),
+    )
+    .unwrap();
+
+    Suite { app, auction, pool }
+}
+
+impl Suite {
+    fn staking(&self) -> StakingResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.auction, &QueryMsg::GetStaking {})
+            .unwrap()
+    }
+
+    fn rebalance(&mut self) {
+        self.app
+            .execute_contract(
+                Addr::unchecked("keeper"),
+                self.auction.clone(),
+                &ExecuteMsg::RebalanceStake {},
+                &[],
+            )
+            .unwrap();
+    }
+
+    fn delegated(&self) -> Uint128 {
+        self.app
+            .wrap()
+            .query_delegation(&self.auction, VALIDATOR)
+            .unwrap()
+            .map(|delegation| delegation.amount.amount)
+            .unwrap_or_default()
+    }
+
+    fn advance(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += seconds / 5;
+        });
+    }
+}
+
+#[test]
+fn stakes_idle_escrow_above_buffer() {
+    let mut suite = setup();
+    suite.rebalance();
+
+    assert_eq!(suite.delegated(), Uint128::new(1000));
+    let balance = suite.app.wrap().query_balance(&suite.auction, DENOM).unwrap();
+    assert_eq!(balance.amount, Uint128::new(3000));
+
+    // Already on target, nothing moves
+    suite.rebalance();
+    assert_eq!(suite.delegated(), Uint128::new(1000));
+}
+
+#[test]
+fn closing_auction_unbonds_for_settlement() {
+    let mut suite = setup();
+    suite.rebalance();
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked("admin"),
+            suite.auction.clone(),
+            &ExecuteMsg::CloseAuction { auction_id: 1 },
+            &[],
+        )
+        .unwrap();
+    let position = suite.staking();
+    assert_eq!(position.pending_settlements, Uint128::new(4000));
+    assert_eq!(position.target, Uint128::zero());
+
+    // The whole delegation starts unbonding and is still counted as held
+    suite.rebalance();
+    assert_eq!(suite.delegated(), Uint128::zero());
+    let position = suite.staking();
+    assert_eq!(position.unbonding, Uint128::new(1000));
+    assert_eq!(position.reserve, Uint128::new(4000));
+
+    suite.advance(UNBONDING_SECONDS);
+    assert_eq!(suite.staking().unbonding, Uint128::zero());
+}
+
+#[test]
+fn rewards_are_claimed_into_insurance_pool() {
+    let mut suite = setup();
+    suite.rebalance();
+    suite.advance(YEAR);
+
+    let rewards = suite.staking().rewards;
+    assert!(!rewards.is_zero());
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            suite.auction.clone(),
+            &ExecuteMsg::ClaimStakingRewards {},
+            &[],
+        )
+        .unwrap();
+
+    let pool: PoolResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.pool, &PoolQueryMsg::Pool {})
+        .unwrap();
+    assert_eq!(pool.balance, rewards);
+    assert_eq!(pool.total_contributed, rewards);
+    assert_eq!(suite.staking().rewards, Uint128::zero());
+
+    // Escrow itself is untouched
+    assert_eq!(suite.delegated(), Uint128::new(1000));
+    let balance = suite.app.wrap().query_balance(&suite.auction, DENOM).unwrap();
+    assert_eq!(balance.amount, Uint128::new(3000));
+}
+
+#[test]
+fn rebalance_sweeps_rewards_first() {
+    let mut suite = setup();
+    suite.rebalance();
+    suite.advance(YEAR);
+    let rewards = suite.staking().rewards;
+
+    // A new leading bid raises the escrow, the rebalance forwards rewards before delegating more
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            suite.auction.clone(),
+            &ExecuteMsg::PlaceBid {
+                auction_id: 1,
+                amount: None,
+            },
+            &coins(6000, DENOM),
+        )
+        .unwrap();
+    suite.rebalance();
+
+    let pool: PoolResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.pool, &PoolQueryMsg::Pool {})
+        .unwrap();
+    assert_eq!(pool.balance, rewards);
+    assert!(suite.delegated() > Uint128::new(1000));
+}
+
+#[test]
+fn cannot_claim_without_rewards() {
+    let mut suite = setup();
+    let err = suite
+        .app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            suite.auction.clone(),
+            &ExecuteMsg::ClaimStakingRewards {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<phoenix_auction::ContractError>().unwrap(),
+        phoenix_auction::ContractError::NoRewards {}
+    );
+}
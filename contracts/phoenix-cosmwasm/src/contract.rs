@@ -0,0 +1,273 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128,
+};
+use cw2::set_contract_version;
+use phoenix_fees::{Bps, FeeSchedule};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ContractResponse, ContractsResponse, ExecuteMsg, FeeInfoResponse,
+    InstantiateMsg, QueryMsg,
+};
+use crate::state::{Config, CONFIG, CONTRACTS};
+
+const CONTRACT_NAME: &str = "crates.io:phoenix-pme";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// 50,000 RLUSD at 6 decimals
+const DEFAULT_INSURANCE_TARGET: u128 = 50_000_000_000;
+// The whole platform fee is deposited with the insurance pool
+const INSURANCE_ALLOCATION: u8 = 100;
+const MAX_NAME_LENGTH: usize = 32;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Bps::new(msg.fee_bps)?;
+    validate_developer_stake(msg.developer_stake)?;
+    let insurance_target = msg
+        .insurance_target
+        .unwrap_or_else(|| Uint128::new(DEFAULT_INSURANCE_TARGET));
+    if insurance_target.is_zero() {
+        return Err(ContractError::InvalidTarget {});
+    }
+
+    let mut accepted_denoms: Vec<String> = vec![];
+    for denom in msg.accepted_denoms.unwrap_or_default() {
+        validate_denom(&denom)?;
+        if accepted_denoms.contains(&denom) {
+            return Err(ContractError::DenomAlreadyAccepted { denom });
+        }
+        accepted_denoms.push(denom);
+    }
+
+    let config = Config {
+        admin: deps.api.addr_validate(&msg.admin)?,
+        fees: FeeSchedule::with_rate(msg.fee_bps),
+        developer_stake: msg.developer_stake,
+        insurance_target,
+        accepted_denoms,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("fee_bps", msg.fee_bps.to_string())
+        .add_attribute("developer_stake", msg.developer_stake.to_string())
+        .add_attribute("platform", "PhoenixPME"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            admin,
+            developer_stake,
+            insurance_target,
+        } => execute_update_config(deps, config, admin, developer_stake, insurance_target),
+        ExecuteMsg::UpdateFees { fees } => execute_update_fees(deps, config, fees),
+        ExecuteMsg::AddDenom { denom } => execute_add_denom(deps, config, denom),
+        ExecuteMsg::RemoveDenom { denom } => execute_remove_denom(deps, config, denom),
+        ExecuteMsg::SetContract { name, address } => execute_set_contract(deps, name, address),
+    }
+}
+
+fn execute_update_config(
+    deps: DepsMut,
+    mut config: Config,
+    admin: Option<String>,
+    developer_stake: Option<u8>,
+    insurance_target: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    if let Some(admin) = admin {
+        config.admin = deps.api.addr_validate(&admin)?;
+    }
+    if let Some(stake) = developer_stake {
+        validate_developer_stake(stake)?;
+        config.developer_stake = stake;
+    }
+    if let Some(target) = insurance_target {
+        if target.is_zero() {
+            return Err(ContractError::InvalidTarget {});
+        }
+        config.insurance_target = target;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("admin", config.admin)
+        .add_attribute("developer_stake", config.developer_stake.to_string())
+        .add_attribute("insurance_target", config.insurance_target))
+}
+
+fn execute_update_fees(
+    deps: DepsMut,
+    mut config: Config,
+    fees: FeeSchedule,
+) -> Result<Response, ContractError> {
+    fees.validate()?;
+    config.fees = fees;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_fees")
+        .add_attribute("fee_bps", config.fees.rate_bps.to_string()))
+}
+
+fn execute_add_denom(
+    deps: DepsMut,
+    mut config: Config,
+    denom: String,
+) -> Result<Response, ContractError> {
+    validate_denom(&denom)?;
+    if config.accepted_denoms.contains(&denom) {
+        return Err(ContractError::DenomAlreadyAccepted { denom });
+    }
+    config.accepted_denoms.push(denom.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_denom")
+        .add_attribute("denom", denom))
+}
+
+fn execute_remove_denom(
+    deps: DepsMut,
+    mut config: Config,
+    denom: String,
+) -> Result<Response, ContractError> {
+    if !config.accepted_denoms.contains(&denom) {
+        return Err(ContractError::DenomNotAccepted { denom });
+    }
+    config.accepted_denoms.retain(|accepted| accepted != &denom);
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_denom")
+        .add_attribute("denom", denom))
+}
+
+fn execute_set_contract(
+    deps: DepsMut,
+    name: String,
+    address: Option<String>,
+) -> Result<Response, ContractError> {
+    validate_name(&name)?;
+    let res = Response::new()
+        .add_attribute("action", "set_contract")
+        .add_attribute("name", &name);
+
+    match address {
+        Some(address) => {
+            let address = deps.api.addr_validate(&address)?;
+            CONTRACTS.save(deps.storage, &name, &address)?;
+            Ok(res.add_attribute("address", address))
+        }
+        None => {
+            CONTRACTS.remove(deps.storage, &name);
+            Ok(res.add_attribute("address", "none"))
+        }
+    }
+}
+
+fn validate_developer_stake(stake: u8) -> Result<(), ContractError> {
+    if stake > 100 {
+        return Err(ContractError::InvalidDeveloperStake { stake });
+    }
+    Ok(())
+}
+
+// Bank denoms: a letter followed by 2-127 letters, digits or `/:._-`
+fn validate_denom(denom: &str) -> Result<(), ContractError> {
+    let valid = (3..=128).contains(&denom.len())
+        && denom.starts_with(|c: char| c.is_ascii_alphabetic())
+        && denom
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c));
+    if !valid {
+        return Err(ContractError::InvalidDenom {});
+    }
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<(), ContractError> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LENGTH
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(ContractError::InvalidContractName {});
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetFeeInfo {} => to_json_binary(&query_fee_info(deps)?),
+        QueryMsg::GetConfig {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::GetFeeSchedule {} => to_json_binary(&CONFIG.load(deps.storage)?.fees),
+        QueryMsg::GetContract { name } => to_json_binary(&query_contract(deps, name)?),
+        QueryMsg::GetContracts {} => to_json_binary(&query_contracts(deps)?),
+    }
+}
+
+fn query_fee_info(deps: Deps) -> StdResult<FeeInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let fee = Bps::new(config.fees.rate_bps)?;
+    Ok(FeeInfoResponse {
+        fee_percent: fee.percent(),
+        fee_bps: fee.value(),
+        insurance_allocation: INSURANCE_ALLOCATION,
+        developer_stake: config.developer_stake,
+        status: "active".to_string(),
+    })
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        admin: config.admin.to_string(),
+        fee_bps: config.fees.rate_bps,
+        fees: config.fees,
+        developer_stake: config.developer_stake,
+        insurance_target: config.insurance_target,
+        accepted_denoms: config.accepted_denoms,
+    })
+}
+
+fn query_contract(deps: Deps, name: String) -> StdResult<ContractResponse> {
+    let address = CONTRACTS.may_load(deps.storage, &name)?;
+    Ok(ContractResponse { name, address })
+}
+
+fn query_contracts(deps: Deps) -> StdResult<ContractsResponse> {
+    let contracts = CONTRACTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(name, address)| ContractResponse {
+                name,
+                address: Some(address),
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(ContractsResponse { contracts })
+}
@@ -0,0 +1,33 @@
+use cosmwasm_std::StdError;
+use phoenix_fees::FeeError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Fee(#[from] FeeError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Developer stake must be at most 100 percent, got {stake}")]
+    InvalidDeveloperStake { stake: u8 },
+
+    #[error("Insurance target must be greater than zero")]
+    InvalidTarget {},
+
+    #[error("Invalid denom")]
+    InvalidDenom {},
+
+    #[error("Denom {denom} is already accepted")]
+    DenomAlreadyAccepted { denom: String },
+
+    #[error("Denom {denom} is not accepted")]
+    DenomNotAccepted { denom: String },
+
+    #[error("Invalid contract name")]
+    InvalidContractName {},
+}
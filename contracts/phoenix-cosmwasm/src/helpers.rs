@@ -0,0 +1,34 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult};
+use phoenix_fees::FeeSchedule;
+
+use crate::msg::{ConfigResponse, ContractResponse, QueryMsg};
+
+// Address of the deployed platform registry, used by the marketplace contracts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PlatformRegistry(pub Addr);
+
+impl PlatformRegistry {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    // Current platform fee, queried at settlement so fee changes apply immediately
+    pub fn fee_schedule(&self, querier: &QuerierWrapper) -> StdResult<FeeSchedule> {
+        querier.query_wasm_smart(self.addr(), &QueryMsg::GetFeeSchedule {})
+    }
+
+    pub fn config(&self, querier: &QuerierWrapper) -> StdResult<ConfigResponse> {
+        querier.query_wasm_smart(self.addr(), &QueryMsg::GetConfig {})
+    }
+
+    pub fn contract(&self, querier: &QuerierWrapper, name: &str) -> StdResult<Option<Addr>> {
+        let res: ContractResponse = querier.query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetContract { name: name.to_string() },
+        )?;
+        Ok(res.address)
+    }
+}
@@ -1,103 +1,5 @@
-use cosmwasm_std::{
-    entry_point, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
-};
-use cosmwasm_schema::{cw_serde, QueryResponses};
-
-#[cw_serde]
-pub struct InstantiateMsg {
-    pub admin: String,
-    pub fee_bps: u16,  // 110 = 1.1%
-    pub developer_stake: u8,  // 10 = 10%
-}
-
-#[cw_serde]
-pub enum ExecuteMsg {
-    PlaceBid { auction_id: u64, amount: String },
-    BuyItNow { auction_id: u64, price: String },
-}
-
-#[cw_serde]
-#[derive(QueryResponses)]
-pub enum QueryMsg {
-    #[returns(FeeInfoResponse)]
-    GetFeeInfo {},
-    #[returns(ConfigResponse)]
-    GetConfig {},
-}
-
-#[cw_serde]
-pub struct FeeInfoResponse {
-    pub fee_percent: f32,
-    pub fee_bps: u16,
-    pub insurance_allocation: u8,
-    pub developer_stake: u8,
-    pub status: String,
-}
-
-#[cw_serde]
-pub struct ConfigResponse {
-    pub admin: String,
-    pub fee_bps: u16,
-    pub developer_stake: u8,
-    pub insurance_target: String,
-}
-
-#[entry_point]
-pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    msg: InstantiateMsg,
-) -> StdResult<Response> {
-    // Store configuration
-    let config = ConfigResponse {
-        admin: msg.admin,
-        fee_bps: msg.fee_bps,
-        developer_stake: msg.developer_stake,
-        insurance_target: "50000000000".to_string(), // 50,000 RLUSD
-    };
-    
-    // In a real contract, we would store this
-    
-    Ok(Response::new()
-        .add_attribute("action", "instantiate")
-        .add_attribute("fee_bps", msg.fee_bps.to_string())
-        .add_attribute("developer_stake", msg.developer_stake.to_string())
-        .add_attribute("platform", "PhoenixPME"))
-}
-
-#[entry_point]
-pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    _msg: ExecuteMsg,
-) -> StdResult<Response> {
-    // Placeholder for execute functions
-    Ok(Response::new().add_attribute("action", "execute"))
-}
-
-#[entry_point]
-pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::GetFeeInfo {} => {
-            let resp = FeeInfoResponse {
-                fee_percent: 1.1,
-                fee_bps: 110,
-                insurance_allocation: 100,
-                developer_stake: 10,
-                status: "active".to_string(),
-            };
-            cosmwasm_std::to_json_binary(&resp)
-        }
-        QueryMsg::GetConfig {} => {
-            let resp = ConfigResponse {
-                admin: "testcore1tymxlev27p5rhxd36g4j3a82c7uucjjz4xuzc6".to_string(),
-                fee_bps: 110,
-                developer_stake: 10,
-                insurance_target: "50000000000".to_string(),
-            };
-            cosmwasm_std::to_json_binary(&resp)
-        }
-    }
-}
+pub mod contract;
+pub mod msg;
+pub mod state;
+pub mod error;
+pub mod helpers;
@@ -0,0 +1,82 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use phoenix_fees::FeeSchedule;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub admin: String,
+    pub fee_bps: u16,  // 110 = 1.1%
+    pub developer_stake: u8,  // 10 = 10%
+    // Defaults to 50,000 RLUSD
+    pub insurance_target: Option<Uint128>,
+    pub accepted_denoms: Option<Vec<String>>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateConfig {
+        admin: Option<String>,
+        developer_stake: Option<u8>,
+        insurance_target: Option<Uint128>,
+    },
+    UpdateFees {
+        fees: FeeSchedule,
+    },
+    AddDenom {
+        denom: String,
+    },
+    RemoveDenom {
+        denom: String,
+    },
+    // Registers a platform contract under a role; `None` removes it
+    SetContract {
+        name: String,
+        address: Option<String>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(FeeInfoResponse)]
+    GetFeeInfo {},
+    #[returns(ConfigResponse)]
+    GetConfig {},
+    // What marketplaces apply to a sale at settlement
+    #[returns(FeeSchedule)]
+    GetFeeSchedule {},
+    #[returns(ContractResponse)]
+    GetContract { name: String },
+    #[returns(ContractsResponse)]
+    GetContracts {},
+}
+
+#[cw_serde]
+pub struct FeeInfoResponse {
+    pub fee_percent: Decimal,
+    pub fee_bps: u16,
+    pub insurance_allocation: u8,
+    pub developer_stake: u8,
+    pub status: String,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub admin: String,
+    pub fee_bps: u16,
+    pub fees: FeeSchedule,
+    pub developer_stake: u8,
+    pub insurance_target: Uint128,
+    pub accepted_denoms: Vec<String>,
+}
+
+#[cw_serde]
+pub struct ContractResponse {
+    pub name: String,
+    pub address: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct ContractsResponse {
+    pub contracts: Vec<ContractResponse>,
+}
@@ -0,0 +1,20 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use phoenix_fees::FeeSchedule;
+
+#[cw_serde]
+pub struct Config {
+    pub admin: Addr,
+    // Platform fee every marketplace charges at settlement
+    pub fees: FeeSchedule,
+    // Share of insurance pool inflows vested to the developer, 10 = 10%
+    pub developer_stake: u8,
+    // Pool size at which the insurance pool starts paying claims
+    pub insurance_target: Uint128,
+    pub accepted_denoms: Vec<String>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+// Deployed platform contracts by role, e.g. "escrow" or "insurance_pool"
+pub const CONTRACTS: Map<&str, Addr> = Map::new("contracts");
@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{from_json, Addr, Decimal, OwnedDeps, Uint128};
+    use phoenix_fees::{FeeSchedule, Rounding};
+    use phoenix_pme::contract::{execute, instantiate, query};
+    use phoenix_pme::error::ContractError;
+    use phoenix_pme::msg::{
+        ConfigResponse, ContractResponse, ContractsResponse, ExecuteMsg, FeeInfoResponse,
+        InstantiateMsg, QueryMsg,
+    };
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            developer_stake: 10,
+            insurance_target: None,
+            accepted_denoms: Some(vec!["urlusd".to_string()]),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        deps
+    }
+
+    fn config(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> ConfigResponse {
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetConfig {}).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_instantiate_persists_config() {
+        let deps = setup();
+        let config = config(&deps);
+        assert_eq!(config.admin, "admin");
+        assert_eq!(config.fee_bps, 110);
+        assert_eq!(config.fees, FeeSchedule::platform());
+        assert_eq!(config.developer_stake, 10);
+        assert_eq!(config.insurance_target, Uint128::new(50_000_000_000));
+        assert_eq!(config.accepted_denoms, vec!["urlusd".to_string()]);
+
+        let info: FeeInfoResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetFeeInfo {}).unwrap()).unwrap();
+        assert_eq!(info.fee_percent, Decimal::permille(1_100));
+        assert_eq!(info.developer_stake, 10);
+        assert_eq!(info.insurance_allocation, 100);
+    }
+
+    #[test]
+    fn test_instantiate_rejects_invalid_config() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            developer_stake: 101,
+            insurance_target: None,
+            accepted_denoms: None,
+        };
+        let err = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidDeveloperStake { stake: 101 });
+
+        let msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            developer_stake: 10,
+            insurance_target: None,
+            accepted_denoms: Some(vec!["urlusd".to_string(), "urlusd".to_string()]),
+        };
+        let err = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::DenomAlreadyAccepted { denom: "urlusd".to_string() });
+    }
+
+    #[test]
+    fn test_updates_are_admin_only() {
+        let mut deps = setup();
+        let msgs = vec![
+            ExecuteMsg::UpdateConfig { admin: None, developer_stake: Some(20), insurance_target: None },
+            ExecuteMsg::UpdateFees { fees: FeeSchedule::with_rate(200) },
+            ExecuteMsg::AddDenom { denom: "utestcore".to_string() },
+            ExecuteMsg::RemoveDenom { denom: "urlusd".to_string() },
+            ExecuteMsg::SetContract { name: "escrow".to_string(), address: Some("escrow".to_string()) },
+        ];
+        for msg in msgs {
+            let err = execute(deps.as_mut(), mock_env(), mock_info("mallory", &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+        }
+    }
+
+    #[test]
+    fn test_update_config_and_hand_over_admin() {
+        let mut deps = setup();
+        let msg = ExecuteMsg::UpdateConfig {
+            admin: Some("treasury".to_string()),
+            developer_stake: Some(5),
+            insurance_target: Some(Uint128::new(1_000_000)),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        let config = config(&deps);
+        assert_eq!(config.admin, "treasury");
+        assert_eq!(config.developer_stake, 5);
+        assert_eq!(config.insurance_target, Uint128::new(1_000_000));
+
+        // The previous admin has no rights left
+        let msg = ExecuteMsg::AddDenom { denom: "utestcore".to_string() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let msg = ExecuteMsg::UpdateConfig { admin: None, developer_stake: None, insurance_target: Some(Uint128::zero()) };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("treasury", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidTarget {});
+    }
+
+    #[test]
+    fn test_update_fees() {
+        let mut deps = setup();
+        let fees = FeeSchedule {
+            rate_bps: 250,
+            rounding: Rounding::HalfEven,
+            min_fee: Some(Uint128::new(10)),
+            max_fee: Some(Uint128::new(5_000)),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), ExecuteMsg::UpdateFees { fees: fees.clone() }).unwrap();
+
+        let schedule: FeeSchedule =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetFeeSchedule {}).unwrap()).unwrap();
+        assert_eq!(schedule, fees);
+        assert_eq!(config(&deps).fee_bps, 250);
+
+        let invalid = FeeSchedule { min_fee: Some(Uint128::new(10_000)), ..fees };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), ExecuteMsg::UpdateFees { fees: invalid }).unwrap_err();
+        assert!(matches!(err, ContractError::Fee(_)));
+    }
+
+    #[test]
+    fn test_accepted_denoms() {
+        let mut deps = setup();
+        let add = |denom: &str| ExecuteMsg::AddDenom { denom: denom.to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), add("ibc/ABC123")).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), add("urlusd")).unwrap_err();
+        assert_eq!(err, ContractError::DenomAlreadyAccepted { denom: "urlusd".to_string() });
+        let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), add("1bad")).unwrap_err();
+        assert_eq!(err, ContractError::InvalidDenom {});
+
+        let remove = ExecuteMsg::RemoveDenom { denom: "urlusd".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), remove.clone()).unwrap();
+        assert_eq!(config(&deps).accepted_denoms, vec!["ibc/ABC123".to_string()]);
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), remove).unwrap_err();
+        assert_eq!(err, ContractError::DenomNotAccepted { denom: "urlusd".to_string() });
+    }
+
+    #[test]
+    fn test_contract_addresses() {
+        let mut deps = setup();
+        let set = |name: &str, address: Option<&str>| ExecuteMsg::SetContract {
+            name: name.to_string(),
+            address: address.map(|a| a.to_string()),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), set("escrow", Some("escrow_v1"))).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), set("insurance_pool", Some("pool"))).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), set("escrow", Some("escrow_v2"))).unwrap();
+
+        let escrow: ContractResponse = from_json(query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetContract { name: "escrow".to_string() },
+        ).unwrap()).unwrap();
+        assert_eq!(escrow.address, Some(Addr::unchecked("escrow_v2")));
+
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), set("escrow", None)).unwrap();
+        let all: ContractsResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetContracts {}).unwrap()).unwrap();
+        assert_eq!(all.contracts, vec![ContractResponse {
+            name: "insurance_pool".to_string(),
+            address: Some(Addr::unchecked("pool")),
+        }]);
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), set("Escrow V3", Some("escrow"))).unwrap_err();
+        assert_eq!(err, ContractError::InvalidContractName {});
+    }
+}
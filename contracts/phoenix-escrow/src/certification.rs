@@ -0,0 +1,65 @@
+use cosmwasm_std::{StdResult, Storage};
+use phoenix_metals::{GradingService, ListingMetadata};
+
+use crate::error::ContractError;
+use crate::msg::{AuctionResponse, CertListingsResponse};
+use crate::state::{CertRecord, auctions, CERTS};
+
+// Ties a new listing to its cert; a cert can only be on sale once at a time
+pub fn register_listing(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    metadata: &ListingMetadata,
+) -> Result<(), ContractError> {
+    let certification = match &metadata.certification {
+        Some(certification) => certification,
+        None => return Ok(()),
+    };
+    let key = (certification.service.as_str(), certification.cert_number.as_str());
+    let mut record = CERTS.may_load(storage, key)?.unwrap_or_default();
+
+    if record.stolen.is_some() {
+        return Err(ContractError::ReportedStolen {});
+    }
+    if let Some(verification) = &record.verification {
+        if verification.grade != certification.grade {
+            return Err(ContractError::GradeMismatch {
+                cert_number: certification.cert_number.clone(),
+                verified: verification.grade,
+            });
+        }
+    }
+    if let Some(&last) = record.listings.last() {
+        if auctions().load(storage, last)?.status.holds_item() {
+            return Err(ContractError::CertAlreadyListed {
+                cert_number: certification.cert_number.clone(),
+                auction_id: last,
+            });
+        }
+    }
+
+    record.listings.push(auction_id);
+    CERTS.save(storage, key, &record)?;
+    Ok(())
+}
+
+pub fn listings_by_cert(
+    storage: &dyn Storage,
+    service: GradingService,
+    cert_number: String,
+) -> StdResult<CertListingsResponse> {
+    let record: CertRecord = CERTS
+        .may_load(storage, (service.as_str(), cert_number.as_str()))?
+        .unwrap_or_default();
+    let listings = record
+        .listings
+        .into_iter()
+        .map(|id| auctions().load(storage, id).map(|auction| AuctionResponse { id, auction }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(CertListingsResponse {
+        service,
+        cert_number,
+        verification: record.verification,
+        listings,
+    })
+}
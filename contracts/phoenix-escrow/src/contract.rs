@@ -1,22 +1,48 @@
 use cosmwasm_std::{
     entry_point, Binary, Deps, DepsMut, Env, MessageInfo, 
-    Response, StdResult, Uint128, Addr, to_json_binary, BankMsg, coins
+    Response, StdResult, Uint128, Addr, to_json_binary, BankMsg, coin, coins, Storage, Order,
+    CosmosMsg
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Item;
+use phoenix_fees::{Bps, FeeError, FeeSchedule};
+use phoenix_insurance::helpers::InsurancePool;
+use phoenix_metals::{
+    Certification, ContentHash, DocumentKind, GradingService, ListingMetadata, MetalsError, Weight,
+    FINENESS_DENOMINATOR,
+};
+use phoenix_pme::helpers::PlatformRegistry;
+use serde::{Deserialize, Serialize};
 
+use crate::certification;
+use crate::documents;
 use crate::error::ContractError;
+use crate::ledger::{self, Escrow};
+use crate::provenance;
+use crate::theft;
+use crate::valuation;
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, QueryMsg, ConfigResponse, 
-    AuctionResponse
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ConfigResponse, 
+    AuctionResponse, SellerBondResponse, RequiredBondResponse, BalanceResponse,
+    BidderAccountResponse, LockedBid, SolvencyResponse, CoverageRequest,
+    ListingValuationsResponse, AttesterResponse, StolenReportResponse, FlagHistoryResponse,
+    DocumentsResponse
 };
 use crate::state::{
-    Config, Auction, Bid, AuctionStatus, 
-    CONFIG, AUCTIONS, AUCTION_COUNT, COMPLETED_AUCTIONS, KYC_VERIFIED
+    Config, Auction, Bid, AuctionStatus, BondConfig, SellerBond, BondStatus,
+    CONFIG, auctions, AUCTION_COUNT, COMPLETED_AUCTIONS, KYC_VERIFIED,
+    SELLER_BONDS, SELLER_SALES, LOCKED_BIDS, LEDGER_TOTALS, ESCROW_TOTALS,
+    CertVerification, ItemRef, Document, DocumentRole, Inspection, InspectionResult,
+    ATTESTERS, CERTS, REPORTERS, DOCUMENTS, INSPECTORS
 };
 
 const CONTRACT_NAME: &str = "crates.io:phoenix-escrow";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const DEFAULT_DENOM: &str = "utestcore";
+const DEFAULT_DELIVERY_TIMEOUT_HOURS: u64 = 14 * 24;
+const MAX_BPS: u16 = 10_000;
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
@@ -28,12 +54,28 @@ pub fn instantiate(
 
     let admin = deps.api.addr_validate(&msg.admin)?;
     let fee_address = deps.api.addr_validate(&msg.fee_address)?;
+    let insurance_pool = msg.insurance_pool
+        .map(|pool| deps.api.addr_validate(&pool))
+        .transpose()?;
+    let registry = msg.registry
+        .map(|registry| deps.api.addr_validate(&registry))
+        .transpose()?;
+    Bps::new(msg.fee_bps)?;
+    if let Some(bond) = &msg.seller_bond {
+        validate_bond_config(bond)?;
+    }
     
     let config = Config {
         admin: admin.clone(),
-        fee_percentage: msg.fee_percentage,
+        fee_bps: msg.fee_bps,
         fee_address,
         require_kyc: msg.require_kyc.unwrap_or(false),
+        denom: msg.denom.unwrap_or_else(|| DEFAULT_DENOM.to_string()),
+        delivery_timeout_hours: msg.delivery_timeout_hours
+            .unwrap_or(DEFAULT_DELIVERY_TIMEOUT_HOURS),
+        seller_bond: msg.seller_bond,
+        insurance_pool,
+        registry,
     };
     
     CONFIG.save(deps.storage, &config)?;
@@ -44,6 +86,66 @@ pub fn instantiate(
         .add_attribute("admin", admin))
 }
 
+// Config as stored by versions that kept an unused whole-number fee
+// percentage, before the denom and delivery window were configurable
+#[derive(Serialize, Deserialize)]
+struct LegacyConfig {
+    admin: Addr,
+    fee_percentage: u64,
+    fee_address: Addr,
+    require_kyc: bool,
+}
+
+const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+
+// Rewrites a legacy config into the current shape. The stored percentage
+// becomes basis points (1 = 1% = 100 bps), the denom and delivery window
+// take their defaults. Configs already in the current shape are kept.
+// Stored auctions are indexed by metal and status, and item records move to
+// their canonical serial keys.
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let config = match CONFIG.may_load(deps.storage) {
+        Ok(Some(config)) => config,
+        _ => {
+            let legacy = LEGACY_CONFIG.load(deps.storage)?;
+            let fee_bps = legacy.fee_percentage
+                .checked_mul(100)
+                .and_then(|bps| u16::try_from(bps).ok())
+                .ok_or(FeeError::Overflow)?;
+            Bps::new(fee_bps)?;
+            let config = Config {
+                admin: legacy.admin,
+                fee_bps,
+                fee_address: legacy.fee_address,
+                require_kyc: legacy.require_kyc,
+                denom: DEFAULT_DENOM.to_string(),
+                delivery_timeout_hours: DEFAULT_DELIVERY_TIMEOUT_HOURS,
+                seller_bond: None,
+                insurance_pool: None,
+                registry: None,
+            };
+            CONFIG.save(deps.storage, &config)?;
+            config
+        }
+    };
+    
+    let ids = auctions()
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for id in ids {
+        let auction = auctions().load(deps.storage, id)?;
+        auctions().replace(deps.storage, id, Some(&auction), None)?;
+    }
+    provenance::canonicalize_keys(deps.storage)?;
+    
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("fee_bps", config.fee_bps.to_string()))
+}
+
 #[entry_point]
 pub fn execute(
     deps: DepsMut,
@@ -54,31 +156,50 @@ pub fn execute(
     match msg {
         ExecuteMsg::CreateAuction {
             item_id,
+            metadata,
             starting_price,
             reserve_price,
             buy_now_price,
             duration_hours,
-        } => execute_create_auction(
-            deps, env, info, 
-            item_id, starting_price, reserve_price, buy_now_price, 
+            inspector,
+        } => execute_create_auction(deps, env, info, CreateAuctionParams {
+            item_id,
+            metadata,
+            starting_price,
+            reserve_price,
+            buy_now_price,
             duration_hours,
-        ),
+            inspector,
+        }),
         ExecuteMsg::PlaceBid { auction_id } => {
-            // Check KYC if required
-            let config = CONFIG.load(deps.storage)?;
-            if config.require_kyc {
-                let is_verified = KYC_VERIFIED.may_load(deps.storage, &info.sender)?
-                    .unwrap_or(false);
-                if !is_verified {
-                    return Err(ContractError::KycRequired {});
-                }
-            }
-            execute_place_bid(deps, env, info, auction_id)
+            ensure_kyc(deps.as_ref(), &info.sender)?;
+            execute_place_bid(deps, env, info, auction_id, None)
         },
-        ExecuteMsg::BuyNow { auction_id } => execute_buy_now(deps, env, info, auction_id),
+        ExecuteMsg::PlaceBidFromDeposit { auction_id, amount } => {
+            ensure_kyc(deps.as_ref(), &info.sender)?;
+            execute_place_bid(deps, env, info, auction_id, Some(amount))
+        },
+        ExecuteMsg::Deposit {} => execute_deposit(deps, info),
+        ExecuteMsg::BuyNow { auction_id, insurance } => {
+            execute_buy_now(deps, env, info, auction_id, insurance)
+        }
+        ExecuteMsg::InsureOrder { auction_id, insurance } => {
+            execute_insure_order(deps, info, auction_id, insurance)
+        }
         ExecuteMsg::EndAuction { auction_id } => execute_end_auction(deps, env, info, auction_id),
         ExecuteMsg::CancelAuction { auction_id } => execute_cancel_auction(deps, env, info, auction_id),
         ExecuteMsg::ReleaseFunds { auction_id } => execute_release_funds(deps, env, info, auction_id),
+        ExecuteMsg::ClaimNonDelivery { auction_id } => execute_claim_non_delivery(deps, env, info, auction_id),
+        ExecuteMsg::OpenDispute { auction_id } => execute_open_dispute(deps, env, info, auction_id),
+        ExecuteMsg::ResolveDispute { auction_id, refund_buyer } => {
+            execute_resolve_dispute(deps, env, info, auction_id, refund_buyer)
+        },
+        ExecuteMsg::UpdateBondConfig { seller_bond } => execute_update_bond_config(deps, info, seller_bond),
+        ExecuteMsg::UpdateInsurancePool { insurance_pool } => {
+            execute_update_insurance_pool(deps, info, insurance_pool)
+        }
+        ExecuteMsg::UpdateRegistry { registry } => execute_update_registry(deps, info, registry),
+        ExecuteMsg::Withdraw { denom, amount } => execute_withdraw(deps, env, info, denom, amount),
         
         // KYC functions
         ExecuteMsg::VerifyUser { address } => {
@@ -89,6 +210,65 @@ pub fn execute(
             let addr = deps.api.addr_validate(&address)?;
             execute_revoke_verification(deps, info, addr)
         },
+
+        // Grading attestation
+        ExecuteMsg::AddAttester { address, services } => {
+            let addr = deps.api.addr_validate(&address)?;
+            execute_add_attester(deps, info, addr, services)
+        },
+        ExecuteMsg::RemoveAttester { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            execute_remove_attester(deps, info, addr)
+        },
+        ExecuteMsg::VerifyCert { service, cert_number, grade } => {
+            execute_verify_cert(deps, env, info, service, cert_number, grade)
+        },
+
+        // Theft reports
+        ExecuteMsg::AddReporter { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            execute_set_reporter(deps, info, addr, true)
+        },
+        ExecuteMsg::RemoveReporter { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            execute_set_reporter(deps, info, addr, false)
+        },
+        ExecuteMsg::FlagStolen { item, reason } => execute_flag_stolen(deps, env, info, item, reason),
+        ExecuteMsg::UnflagStolen { item, reason } => execute_unflag_stolen(deps, env, info, item, reason),
+        ExecuteMsg::ResolveFrozen { auction_id, cancel } => {
+            execute_resolve_frozen(deps, info, auction_id, cancel)
+        },
+
+        // Documents
+        ExecuteMsg::AttachDocument { auction_id, kind, hash } => {
+            execute_attach_document(deps, env, info, auction_id, kind, hash)
+        },
+        ExecuteMsg::RemoveDocument { auction_id, index } => {
+            execute_remove_document(deps, info, auction_id, index)
+        },
+
+        // Inspection
+        ExecuteMsg::AddInspector { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            execute_set_inspector(deps, info, addr, true)
+        },
+        ExecuteMsg::RemoveInspector { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            execute_set_inspector(deps, info, addr, false)
+        },
+        ExecuteMsg::RequireInspection { auction_id, inspector } => {
+            execute_require_inspection(deps, env, info, auction_id, inspector)
+        },
+        ExecuteMsg::RecordInspection {
+            auction_id,
+            passed,
+            measured_weight,
+            measured_fineness,
+            report,
+        } => execute_record_inspection(
+            deps, env, info, auction_id,
+            InspectionReport { passed, measured_weight, measured_fineness, report },
+        ),
     }
 }
 
@@ -96,14 +276,18 @@ pub fn execute(
 // BID FUNCTIONS
 // ============================================================
 
+// Bids are funded by the attached funds, or by the bidder's deposit when
+// `from_deposit` is set
 fn execute_place_bid(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     auction_id: u64,
+    from_deposit: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     // Load the auction
-    let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
+    let mut auction = auctions().load(deps.storage, auction_id)?;
+    let config = CONFIG.load(deps.storage)?;
     
     // Validate auction is active
     if auction.status != AuctionStatus::Active {
@@ -113,17 +297,20 @@ fn execute_place_bid(
     // Check if auction has ended
     if env.block.time.seconds() > auction.ends_at {
         auction.status = AuctionStatus::Ended;
-        AUCTIONS.save(deps.storage, auction_id, &auction)?;
+        auctions().save(deps.storage, auction_id, &auction)?;
         return Err(ContractError::AuctionEnded {});
     }
     
     // Validate funds were sent
-    if info.funds.is_empty() {
+    let bid_amount = match from_deposit {
+        Some(_) if !info.funds.is_empty() => return Err(ContractError::UnexpectedFunds {}),
+        Some(amount) => amount,
+        None => amount_sent(&info, &config.denom)?,
+    };
+    if bid_amount.is_zero() {
         return Err(ContractError::NoFunds {});
     }
     
-    let bid_amount = info.funds[0].amount;
-    
     // Check minimum bid
     if bid_amount < auction.starting_price {
         return Err(ContractError::BidTooLow {});
@@ -143,16 +330,60 @@ fn execute_place_bid(
         timestamp: env.block.time.seconds(),
     };
     
+    // Unlock and refund the outbid bidder through the ledger
+    if let Some(previous) = &auction.highest_bid {
+        LOCKED_BIDS.remove(deps.storage, (&previous.bidder, auction_id));
+        ledger::escrow_sub(deps.storage, &config.denom, Escrow::OpenBids, previous.amount)?;
+        ledger::credit(deps.storage, &previous.bidder, &config.denom, previous.amount)?;
+    }
+    
+    // Debit after the refund so the high bidder can raise their own bid
+    if from_deposit.is_some() {
+        ledger::debit(deps.storage, &info.sender, &config.denom, bid_amount)?;
+    }
+    LOCKED_BIDS.save(deps.storage, (&info.sender, auction_id), &bid_amount)?;
+    ledger::escrow_add(deps.storage, &config.denom, Escrow::OpenBids, bid_amount)?;
+    
     // Add to bids list
     auction.bids.push(bid.clone());
     auction.highest_bid = Some(bid);
-    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+    auctions().save(deps.storage, auction_id, &auction)?;
     
     Ok(Response::new()
         .add_attribute("action", "place_bid")
         .add_attribute("auction_id", auction_id.to_string())
         .add_attribute("bidder", info.sender.to_string())
-        .add_attribute("amount", bid_amount.to_string()))
+        .add_attribute("amount", bid_amount.to_string())
+        .add_attribute("from_deposit", from_deposit.is_some().to_string()))
+}
+
+// ============================================================
+// DEPOSIT FUNCTIONS
+// ============================================================
+
+// Deposits are credited to the ledger and can back bids or be withdrawn
+fn execute_deposit(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = amount_sent(&info, &config.denom)?;
+    if amount.is_zero() {
+        return Err(ContractError::NoFunds {});
+    }
+    ledger::credit(deps.storage, &info.sender, &config.denom, amount)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "deposit")
+        .add_attribute("depositor", info.sender)
+        .add_attribute("amount", amount))
+}
+
+// Releases the lock on the standing high bid once the auction leaves Active
+fn unlock_highest_bid(storage: &mut dyn Storage, auction_id: u64, auction: &Auction) {
+    if let Some(bid) = &auction.highest_bid {
+        LOCKED_BIDS.remove(storage, (&bid.bidder, auction_id));
+    }
 }
 
 // ============================================================
@@ -164,8 +395,9 @@ fn execute_buy_now(
     env: Env,
     info: MessageInfo,
     auction_id: u64,
+    insurance: Option<CoverageRequest>,
 ) -> Result<Response, ContractError> {
-    let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
+    let mut auction = auctions().load(deps.storage, auction_id)?;
     
     // Validate auction is active
     if auction.status != AuctionStatus::Active {
@@ -175,7 +407,7 @@ fn execute_buy_now(
     // Check if auction has ended
     if env.block.time.seconds() > auction.ends_at {
         auction.status = AuctionStatus::Ended;
-        AUCTIONS.save(deps.storage, auction_id, &auction)?;
+        auctions().save(deps.storage, auction_id, &auction)?;
         return Err(ContractError::AuctionEnded {});
     }
     
@@ -183,11 +415,26 @@ fn execute_buy_now(
     let buy_now_price = auction.buy_now_price
         .ok_or(ContractError::NoBuyNowPrice {})?;
     
-    // Validate funds
-    if info.funds.is_empty() || info.funds[0].amount < buy_now_price {
+    // Validate funds, including the premium when the buyer opts into insurance
+    let config = CONFIG.load(deps.storage)?;
+    let coverage = insurance
+        .map(|request| buy_coverage(deps.as_ref(), &config, auction_id, &info.sender, buy_now_price, &request))
+        .transpose()?;
+    let premium = coverage.as_ref().map(|(premium, _)| *premium).unwrap_or_default();
+    let sent = amount_sent(&info, &config.denom)?;
+    if sent < buy_now_price + premium {
         return Err(ContractError::InsufficientFunds {});
     }
     
+    // Overpayment and any standing high bid go back through the ledger
+    ledger::credit(deps.storage, &info.sender, &config.denom, sent - buy_now_price - premium)?;
+    if let Some(previous) = &auction.highest_bid {
+        LOCKED_BIDS.remove(deps.storage, (&previous.bidder, auction_id));
+        ledger::escrow_sub(deps.storage, &config.denom, Escrow::OpenBids, previous.amount)?;
+        ledger::credit(deps.storage, &previous.bidder, &config.denom, previous.amount)?;
+    }
+    ledger::escrow_add(deps.storage, &config.denom, Escrow::AwaitingRelease, buy_now_price)?;
+    
     // Create bid record
     let bid = Bid {
         bidder: info.sender.clone(),
@@ -198,19 +445,133 @@ fn execute_buy_now(
     // Complete the auction
     auction.bids.push(bid.clone());
     auction.highest_bid = Some(bid);
-    auction.status = AuctionStatus::Sold;
+    auction.status = sold_status(&auction);
+    auction.sold_at = Some(env.block.time.seconds());
     
     // Save to auctions
-    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+    auctions().save(deps.storage, auction_id, &auction)?;
     
     // Also save to completed auctions
     COMPLETED_AUCTIONS.save(deps.storage, auction_id, &auction)?;
     
-    Ok(Response::new()
+    let mut res = Response::new()
         .add_attribute("action", "buy_now")
         .add_attribute("auction_id", auction_id.to_string())
         .add_attribute("buyer", info.sender.to_string())
-        .add_attribute("amount", buy_now_price.to_string()))
+        .add_attribute("amount", buy_now_price.to_string());
+    if let Some((premium, msg)) = coverage {
+        res = res
+            .add_message(msg)
+            .add_attribute("insurance_premium", premium.to_string());
+    }
+    Ok(res)
+}
+
+// ============================================================
+// INSURANCE FUNCTIONS
+// ============================================================
+
+fn execute_insure_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+    insurance: CoverageRequest,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+    if !matches!(auction.status, AuctionStatus::Sold | AuctionStatus::AwaitingInspection) {
+        return Err(ContractError::AuctionNotActive {});
+    }
+    if winning_bid(&auction)?.bidder != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    
+    let config = CONFIG.load(deps.storage)?;
+    let price = winning_bid(&auction)?.amount;
+    let (premium, msg) = buy_coverage(deps.as_ref(), &config, auction_id, &info.sender, price, &insurance)?;
+    let sent = amount_sent(&info, &config.denom)?;
+    if sent < premium {
+        return Err(ContractError::InsufficientFunds {});
+    }
+    ledger::credit(deps.storage, &info.sender, &config.denom, sent - premium)?;
+    
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "insure_order")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("buyer", info.sender)
+        .add_attribute("insurance_premium", premium.to_string()))
+}
+
+fn execute_update_insurance_pool(
+    deps: DepsMut,
+    info: MessageInfo,
+    insurance_pool: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    
+    config.insurance_pool = insurance_pool
+        .map(|pool| deps.api.addr_validate(&pool))
+        .transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "update_insurance_pool")
+        .add_attribute("updated_by", info.sender))
+}
+
+fn execute_update_registry(
+    deps: DepsMut,
+    info: MessageInfo,
+    registry: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    
+    config.registry = registry
+        .map(|registry| deps.api.addr_validate(&registry))
+        .transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "update_registry")
+        .add_attribute("updated_by", info.sender))
+}
+
+// Quotes cover with the pool and builds the purchase; the pool refuses
+// orders it has no capacity for, so that is checked up front. Cover is
+// capped at the price paid for the lot
+fn buy_coverage(
+    deps: Deps,
+    config: &Config,
+    auction_id: u64,
+    buyer: &Addr,
+    price: Uint128,
+    request: &CoverageRequest,
+) -> Result<(Uint128, CosmosMsg), ContractError> {
+    if request.declared_value > price {
+        return Err(ContractError::DeclaredValueTooHigh {});
+    }
+    let pool = config.insurance_pool.clone()
+        .map(InsurancePool)
+        .ok_or(ContractError::InsuranceUnavailable {})?;
+    let quote = pool.quote(&deps.querier, request.declared_value, request.tier)?;
+    if !quote.available {
+        return Err(ContractError::CoverageUnavailable {});
+    }
+    
+    let msg = pool.purchase_coverage(
+        auction_id,
+        buyer,
+        request.declared_value,
+        request.tier,
+        coin(quote.premium.u128(), &config.denom),
+    )?;
+    Ok((quote.premium, msg))
 }
 
 // ============================================================
@@ -223,7 +584,7 @@ fn execute_end_auction(
     info: MessageInfo,
     auction_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
+    let mut auction = auctions().load(deps.storage, auction_id)?;
     
     // Check if auction has ended
     if env.block.time.seconds() <= auction.ends_at {
@@ -235,13 +596,38 @@ fn execute_end_auction(
         return Err(ContractError::AuctionEnded {});
     }
     
-    // Mark as ended
-    auction.status = AuctionStatus::Ended;
-    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+    let config = CONFIG.load(deps.storage)?;
+    let reserve_met = match (&auction.highest_bid, auction.reserve_price) {
+        (Some(bid), Some(reserve)) => bid.amount >= reserve,
+        (Some(_), None) => true,
+        (None, _) => false,
+    };
+    
+    // A winning bid sells the lot, otherwise the high bidder is refunded
+    // and the seller gets the bond back
+    unlock_highest_bid(deps.storage, auction_id, &auction);
+    if let Some(bid) = &auction.highest_bid {
+        ledger::escrow_sub(deps.storage, &config.denom, Escrow::OpenBids, bid.amount)?;
+    }
+    if reserve_met {
+        auction.status = sold_status(&auction);
+        auction.sold_at = Some(env.block.time.seconds());
+        COMPLETED_AUCTIONS.save(deps.storage, auction_id, &auction)?;
+        let amount = winning_bid(&auction)?.amount;
+        ledger::escrow_add(deps.storage, &config.denom, Escrow::AwaitingRelease, amount)?;
+    } else {
+        auction.status = AuctionStatus::Ended;
+        if let Some(bid) = &auction.highest_bid {
+            ledger::credit(deps.storage, &bid.bidder, &config.denom, bid.amount)?;
+        }
+        return_bond(deps.storage, &config, auction_id)?;
+    }
+    auctions().save(deps.storage, auction_id, &auction)?;
     
     Ok(Response::new()
         .add_attribute("action", "end_auction")
         .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("sold", reserve_met.to_string())
         .add_attribute("ended_by", info.sender.to_string()))
 }
 
@@ -255,7 +641,7 @@ fn execute_cancel_auction(
     info: MessageInfo,
     auction_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
+    let mut auction = auctions().load(deps.storage, auction_id)?;
     
     // Only creator can cancel
     if info.sender != auction.creator {
@@ -272,9 +658,11 @@ fn execute_cancel_auction(
         return Err(ContractError::AuctionHasBids {});
     }
     
-    // Cancel the auction
+    // Cancel the auction and hand back the bond
     auction.status = AuctionStatus::Cancelled;
-    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+    auctions().save(deps.storage, auction_id, &auction)?;
+    let config = CONFIG.load(deps.storage)?;
+    return_bond(deps.storage, &config, auction_id)?;
     
     Ok(Response::new()
         .add_attribute("action", "cancel_auction")
@@ -288,153 +676,1082 @@ fn execute_cancel_auction(
 
 fn execute_release_funds(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     auction_id: u64,
 ) -> Result<Response, ContractError> {
-    let auction = AUCTIONS.load(deps.storage, auction_id)?;
+    let mut auction = auctions().load(deps.storage, auction_id)?;
+    let config = CONFIG.load(deps.storage)?;
     
-    // Only creator can release funds
-    if info.sender != auction.creator {
-        return Err(ContractError::NotCreator {});
+    // Auction must be sold, and inspected when inspection is required
+    if auction.status == AuctionStatus::AwaitingInspection {
+        return Err(ContractError::InspectionPending {});
     }
-    
-    // Auction must be sold
     if auction.status != AuctionStatus::Sold {
         return Err(ContractError::AuctionNotActive {});
     }
     
-    // Get highest bid
-    let highest_bid = auction.highest_bid
-        .ok_or(ContractError::NoFundsToRelease {})?;
+    // Only the buyer (confirming delivery) or the admin can release funds
+    let highest_bid = winning_bid(&auction)?;
+    if info.sender != highest_bid.bidder && info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
     
     let amount = highest_bid.amount;
+    auction.status = AuctionStatus::Completed;
+    auctions().save(deps.storage, auction_id, &auction)?;
+    let fees = fee_schedule(deps.as_ref(), &config)?;
+    let msgs = settle_to_seller(deps.storage, &config, &fees, auction_id, &auction, env.block.time.seconds())?;
     
     Ok(Response::new()
+        .add_messages(msgs)
         .add_attribute("action", "release_funds")
         .add_attribute("auction_id", auction_id.to_string())
-        .add_attribute("amount", amount.to_string())
-        .add_message(BankMsg::Send {
-            to_address: auction.creator.to_string(),
-            amount: coins(amount.u128(), "utestcore"),
-        }))
+        .add_attribute("amount", amount.to_string()))
 }
 
-// KYC functions
-fn execute_verify_user(
+// ============================================================
+// DELIVERY AND DISPUTE FUNCTIONS
+// ============================================================
+
+fn execute_claim_non_delivery(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    address: Addr,
+    auction_id: u64,
 ) -> Result<Response, ContractError> {
+    let mut auction = auctions().load(deps.storage, auction_id)?;
     let config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
+    
+    // Covers a seller who never ships to the inspector
+    if !matches!(auction.status, AuctionStatus::Sold | AuctionStatus::AwaitingInspection) {
+        return Err(ContractError::AuctionNotActive {});
+    }
+    
+    // Only the buyer can claim non-delivery
+    let highest_bid = winning_bid(&auction)?;
+    if info.sender != highest_bid.bidder {
         return Err(ContractError::Unauthorized {});
     }
     
-    KYC_VERIFIED.save(deps.storage, &address, &true)?;
+    // The seller gets the full delivery window
+    let sold_at = auction.sold_at.unwrap_or(auction.ends_at);
+    if env.block.time.seconds() <= sold_at + config.delivery_timeout_hours * 3600 {
+        return Err(ContractError::DeliveryWindowOpen {});
+    }
+    
+    // The lot may be sitting with a slow inspector rather than an absent
+    // seller, so the arbitrator decides who is at fault
+    if auction.status == AuctionStatus::AwaitingInspection {
+        auction.status = AuctionStatus::Disputed;
+        auctions().save(deps.storage, auction_id, &auction)?;
+        return Ok(Response::new()
+            .add_attribute("action", "claim_non_delivery")
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("buyer", info.sender)
+            .add_attribute("disputed", "true"));
+    }
+    
+    auction.status = AuctionStatus::Refunded;
+    auctions().save(deps.storage, auction_id, &auction)?;
+    let msgs = refund_to_buyer(deps.storage, &config, auction_id, &auction)?;
     
     Ok(Response::new()
-        .add_attribute("action", "verify_user")
-        .add_attribute("address", address)
-        .add_attribute("verified_by", info.sender))
+        .add_messages(msgs)
+        .add_attribute("action", "claim_non_delivery")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("buyer", info.sender)
+        .add_attribute("refund", highest_bid.amount.to_string()))
 }
 
-fn execute_revoke_verification(
+fn execute_open_dispute(
     deps: DepsMut,
+    _env: Env,
     info: MessageInfo,
-    address: Addr,
+    auction_id: u64,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
+    let mut auction = auctions().load(deps.storage, auction_id)?;
+    
+    if auction.status != AuctionStatus::Sold {
+        return Err(ContractError::AuctionNotActive {});
+    }
+    
+    // Only the buyer can dispute a sale
+    let highest_bid = winning_bid(&auction)?;
+    if info.sender != highest_bid.bidder {
         return Err(ContractError::Unauthorized {});
     }
     
-    KYC_VERIFIED.remove(deps.storage, &address);
+    auction.status = AuctionStatus::Disputed;
+    auctions().save(deps.storage, auction_id, &auction)?;
     
     Ok(Response::new()
-        .add_attribute("action", "revoke_verification")
-        .add_attribute("address", address)
-        .add_attribute("revoked_by", info.sender))
+        .add_attribute("action", "open_dispute")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("buyer", info.sender))
 }
 
-// Existing auction functions (simplified)
-fn execute_create_auction(
+fn execute_resolve_dispute(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    item_id: String,
-    starting_price: Uint128,
-    reserve_price: Option<Uint128>,
-    buy_now_price: Option<Uint128>,
-    duration_hours: u64,
+    auction_id: u64,
+    refund_buyer: bool,
 ) -> Result<Response, ContractError> {
-    // Check KYC if required
+    let mut auction = auctions().load(deps.storage, auction_id)?;
     let config = CONFIG.load(deps.storage)?;
-    if config.require_kyc {
-        let is_verified = KYC_VERIFIED.may_load(deps.storage, &info.sender)?
-            .unwrap_or(false);
-        if !is_verified {
-            return Err(ContractError::KycRequired {});
-        }
+    
+    // Admin acts as arbitrator
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
     }
     
-    let ends_at = env.block.time.seconds() + duration_hours * 3600;
+    if auction.status != AuctionStatus::Disputed {
+        return Err(ContractError::NotDisputed {});
+    }
     
-    let auction = Auction {
-        creator: info.sender.clone(),
-        item_id,
-        starting_price,
-        reserve_price,
-        buy_now_price,
-        ends_at,
-        bids: vec![],
-        highest_bid: None,
-        status: AuctionStatus::Active,
-        created_at: env.block.time.seconds(),
+    let msgs = if refund_buyer {
+        auction.status = AuctionStatus::Refunded;
+        auctions().save(deps.storage, auction_id, &auction)?;
+        refund_to_buyer(deps.storage, &config, auction_id, &auction)?
+    } else {
+        auction.status = AuctionStatus::Completed;
+        auctions().save(deps.storage, auction_id, &auction)?;
+        let fees = fee_schedule(deps.as_ref(), &config)?;
+        settle_to_seller(deps.storage, &config, &fees, auction_id, &auction, env.block.time.seconds())?
     };
     
-    let auction_id = AUCTION_COUNT.load(deps.storage)?;
-    AUCTIONS.save(deps.storage, auction_id, &auction)?;
-    AUCTION_COUNT.save(deps.storage, &(auction_id + 1))?;
-    
     Ok(Response::new()
-        .add_attribute("action", "create_auction")
+        .add_messages(msgs)
+        .add_attribute("action", "resolve_dispute")
         .add_attribute("auction_id", auction_id.to_string())
-        .add_attribute("creator", info.sender))
+        .add_attribute("refund_buyer", refund_buyer.to_string()))
 }
 
-// ... (other auction functions would go here, simplified for now)
+// Credits the winning bid less the platform fee to the seller, returns the
+// seller's bond and records the change of ownership. The fee is deposited
+// with the insurance pool when one is configured, else credited to fee_address
+fn settle_to_seller(
+    storage: &mut dyn Storage,
+    config: &Config,
+    fees: &FeeSchedule,
+    auction_id: u64,
+    auction: &Auction,
+    now: u64,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let highest_bid = winning_bid(auction)?;
+    let sales = SELLER_SALES.may_load(storage, &auction.creator)?.unwrap_or(0);
+    SELLER_SALES.save(storage, &auction.creator, &(sales + 1))?;
+    
+    ledger::escrow_sub(storage, &config.denom, Escrow::AwaitingRelease, highest_bid.amount)?;
+    let amounts = fees.compute(highest_bid.amount)?;
+    ledger::credit(storage, &auction.creator, &config.denom, amounts.net)?;
+    let msgs = pay_platform(storage, config, auction_id, amounts.fee)?;
+    return_bond(storage, config, auction_id)?;
+    provenance::record_transfer(storage, auction_id, auction, &highest_bid, &config.denom, now)?;
+    Ok(msgs)
+}
 
-#[entry_point]
-pub fn query(
-    deps: Deps,
-    _env: Env,
-    msg: QueryMsg,
-) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Config {} => {
-            let config = CONFIG.load(deps.storage)?;
-            let resp = ConfigResponse {
-                admin: config.admin,
-                fee_percentage: config.fee_percentage,
-                fee_address: config.fee_address,
-                require_kyc: config.require_kyc,
-            };
-            to_json_binary(&resp)
+// Sends a platform share to the insurance pool, attributed to the auction,
+// or credits it to fee_address when no pool is configured
+fn pay_platform(
+    storage: &mut dyn Storage,
+    config: &Config,
+    auction_id: u64,
+    amount: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    if amount.is_zero() {
+        return Ok(vec![]);
+    }
+    match &config.insurance_pool {
+        Some(pool) => {
+            let pool = InsurancePool(pool.clone());
+            Ok(vec![pool.deposit_fee(auction_id, coin(amount.u128(), &config.denom))?])
         }
-        QueryMsg::Auction { id } => {
-            let auction = AUCTIONS.load(deps.storage, id)?;
-            let resp = AuctionResponse {
-                id,
-                auction,
-            };
-            to_json_binary(&resp)
+        None => {
+            ledger::credit(storage, &config.fee_address, &config.denom, amount)?;
+            Ok(vec![])
         }
-        QueryMsg::IsVerified { address } => {
-            let addr = deps.api.addr_validate(&address)?;
-            let is_verified = KYC_VERIFIED.may_load(deps.storage, &addr)?
-                .unwrap_or(false);
-            to_json_binary(&is_verified)
+    }
+}
+
+// The platform fee as set in the registry at the time of settlement
+fn fee_schedule(deps: Deps, config: &Config) -> StdResult<FeeSchedule> {
+    match &config.registry {
+        Some(registry) => PlatformRegistry(registry.clone()).fee_schedule(&deps.querier),
+        None => Ok(FeeSchedule::with_rate(config.fee_bps)),
+    }
+}
+
+// Credits the winning bid back to the buyer and slashes the seller's bond
+fn refund_to_buyer(
+    storage: &mut dyn Storage,
+    config: &Config,
+    auction_id: u64,
+    auction: &Auction,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let highest_bid = winning_bid(auction)?;
+    ledger::escrow_sub(storage, &config.denom, Escrow::AwaitingRelease, highest_bid.amount)?;
+    ledger::credit(storage, &highest_bid.bidder, &config.denom, highest_bid.amount)?;
+    Ok(slash_bond(storage, config, auction_id, &highest_bid.bidder)?)
+}
+
+fn winning_bid(auction: &Auction) -> Result<Bid, ContractError> {
+    auction.highest_bid.clone().ok_or(ContractError::NoFundsToRelease {})
+}
+
+// ============================================================
+// BOND FUNCTIONS
+// ============================================================
+
+fn execute_update_bond_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    seller_bond: Option<BondConfig>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let Some(bond) = &seller_bond {
+        validate_bond_config(bond)?;
+    }
+    
+    // Only applies to auctions created from now on
+    config.seller_bond = seller_bond;
+    CONFIG.save(deps.storage, &config)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "update_bond_config")
+        .add_attribute("updated_by", info.sender))
+}
+
+fn validate_bond_config(bond: &BondConfig) -> Result<(), ContractError> {
+    if bond.slash_bps > MAX_BPS || bond.buyer_share_bps > MAX_BPS {
+        return Err(ContractError::InvalidBondConfig {});
+    }
+    Ok(())
+}
+
+// Bond a seller has to post, based on KYC status and trade history
+fn required_bond(deps: Deps, config: &Config, seller: &Addr) -> StdResult<Uint128> {
+    let bond = match &config.seller_bond {
+        Some(bond) => bond,
+        None => return Ok(Uint128::zero()),
+    };
+    
+    if let Some(waive_after) = bond.waive_after_sales {
+        let sales = SELLER_SALES.may_load(deps.storage, seller)?.unwrap_or(0);
+        if sales >= waive_after {
+            return Ok(Uint128::zero());
+        }
+    }
+    
+    let verified = KYC_VERIFIED.may_load(deps.storage, seller)?.unwrap_or(false);
+    match bond.verified_amount {
+        Some(amount) if verified => Ok(amount),
+        _ => Ok(bond.amount),
+    }
+}
+
+fn return_bond(
+    storage: &mut dyn Storage,
+    config: &Config,
+    auction_id: u64,
+) -> StdResult<()> {
+    let mut bond = match SELLER_BONDS.may_load(storage, auction_id)? {
+        Some(bond) if bond.status == BondStatus::Held => bond,
+        _ => return Ok(()),
+    };
+    bond.status = BondStatus::Returned;
+    SELLER_BONDS.save(storage, auction_id, &bond)?;
+    
+    ledger::escrow_sub(storage, &config.denom, Escrow::BondsHeld, bond.amount)?;
+    ledger::credit(storage, &bond.seller, &config.denom, bond.amount)
+}
+
+// Splits the slashed part of the bond between the buyer and the platform,
+// paid as a sale fee would be; whatever is not slashed goes back to the seller
+fn slash_bond(
+    storage: &mut dyn Storage,
+    config: &Config,
+    auction_id: u64,
+    buyer: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut bond = match SELLER_BONDS.may_load(storage, auction_id)? {
+        Some(bond) if bond.status == BondStatus::Held => bond,
+        _ => return Ok(vec![]),
+    };
+    
+    let slashed = bond.amount.multiply_ratio(bond.slash_bps, MAX_BPS);
+    let to_buyer = slashed.multiply_ratio(bond.buyer_share_bps, MAX_BPS);
+    let to_pool = slashed - to_buyer;
+    let remainder = bond.amount - slashed;
+    
+    bond.status = BondStatus::Slashed { slashed };
+    SELLER_BONDS.save(storage, auction_id, &bond)?;
+    
+    ledger::escrow_sub(storage, &config.denom, Escrow::BondsHeld, bond.amount)?;
+    ledger::credit(storage, buyer, &config.denom, to_buyer)?;
+    ledger::credit(storage, &bond.seller, &config.denom, remainder)?;
+    pay_platform(storage, config, auction_id, to_pool)
+}
+
+// Coins in any other denom would sit outside the ledger, so they are refused
+fn amount_sent(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    info.funds.iter().try_fold(Uint128::zero(), |total, coin| {
+        if coin.denom != denom {
+            return Err(ContractError::InvalidDenom {});
+        }
+        Ok(total + coin.amount)
+    })
+}
+
+// ============================================================
+// WITHDRAW FUNCTIONS
+// ============================================================
+
+fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroWithdraw {});
+    }
+    
+    // Never pay out while the bank balance does not cover what is owed
+    let config = CONFIG.load(deps.storage)?;
+    let solvency = ledger::solvency(deps.as_ref(), &env.contract.address, &config.fee_address, &denom)?;
+    if !solvency.solvent {
+        return Err(ContractError::Insolvent { denom });
+    }
+    
+    ledger::debit(deps.storage, &info.sender, &denom, amount)?;
+    
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), &denom),
+        })
+        .add_attribute("action", "withdraw")
+        .add_attribute("recipient", info.sender)
+        .add_attribute("denom", denom)
+        .add_attribute("amount", amount))
+}
+
+// KYC functions
+fn ensure_kyc(deps: Deps, address: &Addr) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.require_kyc {
+        let is_verified = KYC_VERIFIED.may_load(deps.storage, address)?
+            .unwrap_or(false);
+        if !is_verified {
+            return Err(ContractError::KycRequired {});
+        }
+    }
+    Ok(())
+}
+
+fn execute_verify_user(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    
+    KYC_VERIFIED.save(deps.storage, &address, &true)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "verify_user")
+        .add_attribute("address", address)
+        .add_attribute("verified_by", info.sender))
+}
+
+fn execute_revoke_verification(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    
+    KYC_VERIFIED.remove(deps.storage, &address);
+    
+    Ok(Response::new()
+        .add_attribute("action", "revoke_verification")
+        .add_attribute("address", address)
+        .add_attribute("revoked_by", info.sender))
+}
+
+// ============================================================
+// GRADING ATTESTATION
+// ============================================================
+
+fn execute_add_attester(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    services: Vec<GradingService>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let services = services.into_iter().fold(vec![], |mut unique, service| {
+        if !unique.contains(&service) {
+            unique.push(service);
+        }
+        unique
+    });
+    if services.is_empty() {
+        return Err(ContractError::NoGradingServices {});
+    }
+
+    ATTESTERS.save(deps.storage, &address, &services)?;
+
+    let services = services.iter().map(|service| service.as_str()).collect::<Vec<_>>();
+    Ok(Response::new()
+        .add_attribute("action", "add_attester")
+        .add_attribute("address", address)
+        .add_attribute("services", services.join(","))
+        .add_attribute("added_by", info.sender))
+}
+
+fn execute_remove_attester(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    ATTESTERS.remove(deps.storage, &address);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_attester")
+        .add_attribute("address", address)
+        .add_attribute("removed_by", info.sender))
+}
+
+// Attesters only vouch for the grading services they were added for
+fn execute_verify_cert(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    service: GradingService,
+    cert_number: String,
+    grade: u8,
+) -> Result<Response, ContractError> {
+    let services = ATTESTERS.may_load(deps.storage, &info.sender)?.unwrap_or_default();
+    if !services.contains(&service) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Certification { service, cert_number: cert_number.clone(), grade, designation: None }
+        .validate()?;
+
+    let key = (service.as_str(), cert_number.as_str());
+    let mut record = CERTS.may_load(deps.storage, key)?.unwrap_or_default();
+    record.verification = Some(CertVerification {
+        attester: info.sender.clone(),
+        grade,
+        verified_at: env.block.time.seconds(),
+    });
+    CERTS.save(deps.storage, key, &record)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "verify_cert")
+        .add_attribute("service", service.as_str())
+        .add_attribute("cert_number", cert_number)
+        .add_attribute("grade", grade.to_string())
+        .add_attribute("verified_by", info.sender))
+}
+
+// ============================================================
+// THEFT REPORTS
+// ============================================================
+
+fn execute_set_reporter(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if enabled {
+        REPORTERS.save(deps.storage, &address, &true)?;
+    } else {
+        REPORTERS.remove(deps.storage, &address);
+    }
+
+    let action = if enabled { "add_reporter" } else { "remove_reporter" };
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_attribute("address", address)
+        .add_attribute("updated_by", info.sender))
+}
+
+fn ensure_reporter(deps: Deps, address: &Addr) -> Result<(), ContractError> {
+    if !REPORTERS.may_load(deps.storage, address)?.unwrap_or(false) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+fn execute_flag_stolen(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    item: ItemRef,
+    reason: String,
+) -> Result<Response, ContractError> {
+    ensure_reporter(deps.as_ref(), &info.sender)?;
+    let event = theft::flag(deps.storage, item, &info.sender, reason, env.block.time.seconds())?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "flag_stolen")
+        .add_attribute("event_id", event.id.to_string())
+        .add_attribute("item", event.item.to_string())
+        .add_attribute("reason", event.reason)
+        .add_attribute("reporter", info.sender);
+    if let Some(auction_id) = event.frozen_auction {
+        response = response.add_attribute("frozen_auction", auction_id.to_string());
+    }
+    Ok(response)
+}
+
+fn execute_unflag_stolen(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    item: ItemRef,
+    reason: String,
+) -> Result<Response, ContractError> {
+    ensure_reporter(deps.as_ref(), &info.sender)?;
+    let event = theft::unflag(deps.storage, item, &info.sender, reason, env.block.time.seconds())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unflag_stolen")
+        .add_attribute("event_id", event.id.to_string())
+        .add_attribute("item", event.item.to_string())
+        .add_attribute("reason", event.reason)
+        .add_attribute("reporter", info.sender))
+}
+
+// Admin acts as arbitrator. Cancelling refunds whoever's money is in escrow;
+// resuming puts the listing back where it was when it froze
+fn execute_resolve_frozen(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+    cancel: bool,
+) -> Result<Response, ContractError> {
+    let mut auction = auctions().load(deps.storage, auction_id)?;
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let frozen_from = match (&auction.status, auction.frozen_from.take()) {
+        (AuctionStatus::Frozen, Some(status)) => status,
+        _ => return Err(ContractError::NotFrozen {}),
+    };
+
+    let mut msgs = vec![];
+    if !cancel {
+        auction.status = frozen_from;
+    } else if frozen_from == AuctionStatus::Active {
+        // Still on sale: the high bid is open and the bond goes back with the listing
+        unlock_highest_bid(deps.storage, auction_id, &auction);
+        if let Some(bid) = &auction.highest_bid {
+            ledger::escrow_sub(deps.storage, &config.denom, Escrow::OpenBids, bid.amount)?;
+            ledger::credit(deps.storage, &bid.bidder, &config.denom, bid.amount)?;
+        }
+        return_bond(deps.storage, &config, auction_id)?;
+        auction.status = AuctionStatus::Cancelled;
+    } else {
+        msgs = refund_to_buyer(deps.storage, &config, auction_id, &auction)?;
+        auction.status = AuctionStatus::Refunded;
+    }
+    auctions().save(deps.storage, auction_id, &auction)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "resolve_frozen")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("cancel", cancel.to_string())
+        .add_attribute("status", format!("{:?}", auction.status)))
+}
+
+// ============================================================
+// INSPECTION FUNCTIONS
+// ============================================================
+
+// Measurements an inspector reports for a lot
+struct InspectionReport {
+    passed: bool,
+    measured_weight: Weight,
+    measured_fineness: u16,
+    report: ContentHash,
+}
+
+// Lots that must be inspected go to the inspector before they count as delivered
+fn sold_status(auction: &Auction) -> AuctionStatus {
+    if auction.inspection.is_some() {
+        AuctionStatus::AwaitingInspection
+    } else {
+        AuctionStatus::Sold
+    }
+}
+
+fn approved_inspector(deps: Deps, inspector: &str) -> Result<Addr, ContractError> {
+    let inspector = deps.api.addr_validate(inspector)?;
+    if !INSPECTORS.may_load(deps.storage, &inspector)?.unwrap_or(false) {
+        return Err(ContractError::UnknownInspector {});
+    }
+    Ok(inspector)
+}
+
+fn execute_set_inspector(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if enabled {
+        INSPECTORS.save(deps.storage, &address, &true)?;
+    } else {
+        INSPECTORS.remove(deps.storage, &address);
+    }
+
+    let action = if enabled { "add_inspector" } else { "remove_inspector" };
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_attribute("address", address)
+        .add_attribute("updated_by", info.sender))
+}
+
+// The seller gets a fresh delivery window to ship to the inspector
+fn execute_require_inspection(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    inspector: String,
+) -> Result<Response, ContractError> {
+    let mut auction = auctions().load(deps.storage, auction_id)?;
+    if auction.status != AuctionStatus::Sold {
+        return Err(ContractError::AuctionNotActive {});
+    }
+    if winning_bid(&auction)?.bidder != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if auction.inspection.is_some() {
+        return Err(ContractError::InspectionAlreadyRequired {});
+    }
+    let inspector = approved_inspector(deps.as_ref(), &inspector)?;
+
+    auction.inspection = Some(Inspection {
+        inspector: inspector.clone(),
+        required_by: info.sender.clone(),
+        result: None,
+    });
+    auction.status = AuctionStatus::AwaitingInspection;
+    auction.sold_at = Some(env.block.time.seconds());
+    auctions().save(deps.storage, auction_id, &auction)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "require_inspection")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("inspector", inspector)
+        .add_attribute("buyer", info.sender))
+}
+
+// A pass hands the lot on to the buyer with a fresh delivery window; a failure
+// refunds the buyer and slashes the seller's bond
+fn execute_record_inspection(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    report: InspectionReport,
+) -> Result<Response, ContractError> {
+    let mut auction = auctions().load(deps.storage, auction_id)?;
+    let config = CONFIG.load(deps.storage)?;
+    if auction.status != AuctionStatus::AwaitingInspection {
+        return Err(ContractError::AuctionNotActive {});
+    }
+    let mut inspection = match auction.inspection.take() {
+        Some(inspection) if inspection.inspector == info.sender => inspection,
+        _ => return Err(ContractError::Unauthorized {}),
+    };
+    approved_inspector(deps.as_ref(), info.sender.as_str())?;
+
+    if report.measured_weight.amount.is_zero() {
+        return Err(MetalsError::ZeroWeight.into());
+    }
+    if report.measured_fineness == 0 || report.measured_fineness > FINENESS_DENOMINATOR {
+        return Err(MetalsError::InvalidFineness { fineness: report.measured_fineness }.into());
+    }
+    report.report.validate()?;
+
+    // The report joins the auction's documents unless the inspector attached it already
+    let attached = DOCUMENTS.may_load(deps.storage, auction_id)?
+        .unwrap_or_default()
+        .iter()
+        .any(|document| document.hash == report.report);
+    if !attached {
+        documents::attach(deps.storage, auction_id, Document {
+            kind: DocumentKind::AssayCertificate,
+            hash: report.report.clone(),
+            uploader: info.sender.clone(),
+            role: DocumentRole::Inspector,
+            stage: AuctionStatus::AwaitingInspection,
+            attached_at: env.block.time.seconds(),
+        })?;
+    }
+
+    inspection.result = Some(InspectionResult {
+        passed: report.passed,
+        measured_weight: report.measured_weight,
+        measured_fineness: report.measured_fineness,
+        report: report.report.clone(),
+        inspected_at: env.block.time.seconds(),
+    });
+    auction.inspection = Some(inspection);
+    let msgs = if report.passed {
+        auction.status = AuctionStatus::Sold;
+        auction.sold_at = Some(env.block.time.seconds());
+        auctions().save(deps.storage, auction_id, &auction)?;
+        vec![]
+    } else {
+        auction.status = AuctionStatus::Refunded;
+        auctions().save(deps.storage, auction_id, &auction)?;
+        refund_to_buyer(deps.storage, &config, auction_id, &auction)?
+    };
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "record_inspection")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("passed", report.passed.to_string())
+        .add_attribute("measured_fineness", report.measured_fineness.to_string())
+        .add_attribute("report", report.report.to_string())
+        .add_attribute("inspector", info.sender))
+}
+
+// ============================================================
+// DOCUMENT FUNCTIONS
+// ============================================================
+
+fn execute_attach_document(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    kind: DocumentKind,
+    hash: ContentHash,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+    let config = CONFIG.load(deps.storage)?;
+    let role = documents::attaching_role(&config, &auction, &info.sender, kind)?;
+
+    let document = Document {
+        kind,
+        hash: hash.clone(),
+        uploader: info.sender.clone(),
+        role,
+        stage: auction.status,
+        attached_at: env.block.time.seconds(),
+    };
+    let index = documents::attach(deps.storage, auction_id, document)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "attach_document")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("index", index.to_string())
+        .add_attribute("kind", kind.to_string())
+        .add_attribute("hash", hash.to_string())
+        .add_attribute("role", format!("{:?}", role))
+        .add_attribute("uploader", info.sender))
+}
+
+fn execute_remove_document(
+    deps: DepsMut,
+    info: MessageInfo,
+    auction_id: u64,
+    index: u32,
+) -> Result<Response, ContractError> {
+    let auction = auctions().load(deps.storage, auction_id)?;
+    let document = documents::remove(deps.storage, auction_id, &auction, &info.sender, index)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_document")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("index", index.to_string())
+        .add_attribute("hash", document.hash.to_string())
+        .add_attribute("removed_by", info.sender))
+}
+
+// Existing auction functions (simplified)
+// Listing terms as sent in ExecuteMsg::CreateAuction
+struct CreateAuctionParams {
+    item_id: String,
+    metadata: ListingMetadata,
+    starting_price: Uint128,
+    reserve_price: Option<Uint128>,
+    buy_now_price: Option<Uint128>,
+    duration_hours: u64,
+    inspector: Option<String>,
+}
+
+fn execute_create_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    params: CreateAuctionParams,
+) -> Result<Response, ContractError> {
+    let CreateAuctionParams {
+        item_id,
+        metadata,
+        starting_price,
+        reserve_price,
+        buy_now_price,
+        duration_hours,
+        inspector,
+    } = params;
+    ensure_kyc(deps.as_ref(), &info.sender)?;
+    metadata.validate()?;
+    let config = CONFIG.load(deps.storage)?;
+    let inspection = inspector
+        .map(|inspector| -> Result<_, ContractError> {
+            Ok(Inspection {
+                inspector: approved_inspector(deps.as_ref(), &inspector)?,
+                required_by: info.sender.clone(),
+                result: None,
+            })
+        })
+        .transpose()?;
+    
+    // Sellers post their performance bond with the listing
+    let bond_amount = required_bond(deps.as_ref(), &config, &info.sender)?;
+    if bond_amount.is_zero() {
+        if !info.funds.is_empty() {
+            return Err(ContractError::BondNotRequired {});
+        }
+    } else if amount_sent(&info, &config.denom)? != bond_amount {
+        return Err(ContractError::BondRequired { required: bond_amount });
+    }
+    
+    let ends_at = env.block.time.seconds() + duration_hours * 3600;
+    
+    let auction = Auction {
+        creator: info.sender.clone(),
+        item_id,
+        metadata: Some(metadata.clone()),
+        starting_price,
+        reserve_price,
+        buy_now_price,
+        ends_at,
+        bids: vec![],
+        highest_bid: None,
+        status: AuctionStatus::Active,
+        created_at: env.block.time.seconds(),
+        sold_at: None,
+        frozen_from: None,
+        inspection,
+    };
+    
+    let auction_id = AUCTION_COUNT.load(deps.storage)?;
+    certification::register_listing(deps.storage, auction_id, &metadata)?;
+    provenance::register_listing(deps.storage, auction_id, &metadata)?;
+    auctions().save(deps.storage, auction_id, &auction)?;
+    AUCTION_COUNT.save(deps.storage, &(auction_id + 1))?;
+    
+    if let Some(bond_config) = &config.seller_bond {
+        if !bond_amount.is_zero() {
+            let bond = SellerBond {
+                seller: info.sender.clone(),
+                amount: bond_amount,
+                slash_bps: bond_config.slash_bps,
+                buyer_share_bps: bond_config.buyer_share_bps,
+                status: BondStatus::Held,
+            };
+            SELLER_BONDS.save(deps.storage, auction_id, &bond)?;
+            ledger::escrow_add(deps.storage, &config.denom, Escrow::BondsHeld, bond_amount)?;
+        }
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", "create_auction")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("creator", info.sender)
+        .add_attribute("bond", bond_amount))
+}
+
+// ... (other auction functions would go here, simplified for now)
+
+#[entry_point]
+pub fn query(
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let resp = ConfigResponse {
+                admin: config.admin,
+                fee_bps: config.fee_bps,
+                fee_address: config.fee_address,
+                require_kyc: config.require_kyc,
+                denom: config.denom,
+                delivery_timeout_hours: config.delivery_timeout_hours,
+                seller_bond: config.seller_bond,
+                insurance_pool: config.insurance_pool,
+                registry: config.registry,
+            };
+            to_json_binary(&resp)
+        }
+        QueryMsg::Auction { id } => {
+            let auction = auctions().load(deps.storage, id)?;
+            let resp = AuctionResponse {
+                id,
+                auction,
+            };
+            to_json_binary(&resp)
+        }
+        QueryMsg::IsVerified { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            let is_verified = KYC_VERIFIED.may_load(deps.storage, &addr)?
+                .unwrap_or(false);
+            to_json_binary(&is_verified)
+        }
+        QueryMsg::SellerBond { auction_id } => {
+            let bond = SELLER_BONDS.may_load(deps.storage, auction_id)?;
+            to_json_binary(&SellerBondResponse { auction_id, bond })
+        }
+        QueryMsg::Balance { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            let balances = ledger::balances(deps.storage, &address)?;
+            to_json_binary(&BalanceResponse { address, balances })
+        }
+        QueryMsg::BidderAccount { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            let config = CONFIG.load(deps.storage)?;
+            let available = ledger::balance_of(deps.storage, &address, &config.denom)?;
+            let locked_bids = LOCKED_BIDS
+                .prefix(&address)
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(auction_id, amount)| LockedBid { auction_id, amount }))
+                .collect::<StdResult<Vec<_>>>()?;
+            let locked = locked_bids.iter().fold(Uint128::zero(), |total, bid| total + bid.amount);
+            to_json_binary(&BidderAccountResponse {
+                address,
+                denom: config.denom,
+                available,
+                locked,
+                locked_bids,
+            })
+        }
+        QueryMsg::Solvency {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let mut denoms = vec![config.denom.clone()];
+            for denom in LEDGER_TOTALS.keys(deps.storage, None, None, Order::Ascending)
+                .chain(ESCROW_TOTALS.keys(deps.storage, None, None, Order::Ascending))
+            {
+                let denom = denom?;
+                if !denoms.contains(&denom) {
+                    denoms.push(denom);
+                }
+            }
+            let denoms = denoms
+                .iter()
+                .map(|denom| ledger::solvency(deps, &env.contract.address, &config.fee_address, denom))
+                .collect::<StdResult<Vec<_>>>()?;
+            let solvent = denoms.iter().all(|denom| denom.solvent);
+            to_json_binary(&SolvencyResponse { denoms, solvent })
+        }
+        QueryMsg::RequiredBond { seller } => {
+            let seller = deps.api.addr_validate(&seller)?;
+            let config = CONFIG.load(deps.storage)?;
+            let amount = required_bond(deps, &config, &seller)?;
+            to_json_binary(&RequiredBondResponse {
+                seller,
+                amount,
+                denom: config.denom,
+            })
+        }
+        QueryMsg::ListingValuations { start_after, limit } => {
+            let config = CONFIG.load(deps.storage)?;
+            let now = env.block.time.seconds();
+            let listings = valuation::open_valuations(deps.storage, now, start_after, limit)?;
+            to_json_binary(&ListingValuationsResponse {
+                denom: config.denom,
+                listings,
+            })
+        }
+        QueryMsg::CompareListings { metal, limit } => {
+            let config = CONFIG.load(deps.storage)?;
+            let now = env.block.time.seconds();
+            let listings = valuation::compare(deps.storage, now, metal, limit)?;
+            to_json_binary(&ListingValuationsResponse {
+                denom: config.denom,
+                listings,
+            })
+        }
+        QueryMsg::Attester { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            let services = ATTESTERS.may_load(deps.storage, &address)?.unwrap_or_default();
+            to_json_binary(&AttesterResponse { address, services })
+        }
+        QueryMsg::ListingsByCert { service, cert_number } => {
+            to_json_binary(&certification::listings_by_cert(deps.storage, service, cert_number)?)
+        }
+        QueryMsg::ProvenanceHistory { refiner, serial } => {
+            to_json_binary(&provenance::history(deps.storage, refiner, serial)?)
+        }
+        QueryMsg::IsReporter { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            let is_reporter = REPORTERS.may_load(deps.storage, &addr)?
+                .unwrap_or(false);
+            to_json_binary(&is_reporter)
+        }
+        QueryMsg::StolenReport { item } => {
+            let item = item.canonical();
+            let report = theft::report(deps.storage, &item)?;
+            to_json_binary(&StolenReportResponse { item, report })
+        }
+        QueryMsg::FlagHistory { start_after, limit } => {
+            let events = theft::history(deps.storage, start_after, limit)?;
+            to_json_binary(&FlagHistoryResponse { events })
+        }
+        QueryMsg::IsInspector { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            let is_inspector = INSPECTORS.may_load(deps.storage, &addr)?
+                .unwrap_or(false);
+            to_json_binary(&is_inspector)
+        }
+        QueryMsg::Documents { auction_id } => {
+            let documents = DOCUMENTS.may_load(deps.storage, auction_id)?.unwrap_or_default();
+            to_json_binary(&DocumentsResponse { auction_id, documents })
         }
         // ... (other queries)
         _ => unimplemented!(),
@@ -0,0 +1,145 @@
+use cosmwasm_std::{Addr, Storage};
+use phoenix_metals::DocumentKind;
+
+use crate::error::ContractError;
+use crate::state::{Auction, AuctionStatus, Config, Document, DocumentRole, DOCUMENTS};
+
+const MAX_LISTING_DOCUMENTS: usize = 20;
+const MAX_ROLE_DOCUMENTS: usize = 10;
+
+// Listing documents and each role's later documents count against separate
+// allowances, so a full listing still leaves room for the shipping label and
+// for the buyer, inspector and arbitrator
+fn is_listing(document: &Document) -> bool {
+    document.stage == AuctionStatus::Active
+}
+
+fn allowance(document: &Document) -> usize {
+    if is_listing(document) {
+        MAX_LISTING_DOCUMENTS
+    } else {
+        MAX_ROLE_DOCUMENTS
+    }
+}
+
+fn shares_allowance(a: &Document, b: &Document) -> bool {
+    a.role == b.role && is_listing(a) == is_listing(b)
+}
+
+// Capacities the sender holds on this auction, in order of precedence
+fn roles(config: &Config, auction: &Auction, sender: &Addr) -> Vec<DocumentRole> {
+    let mut roles = vec![];
+    if *sender == auction.creator {
+        roles.push(DocumentRole::Seller);
+    }
+    let is_buyer = auction.status != AuctionStatus::Active
+        && auction
+            .highest_bid
+            .as_ref()
+            .is_some_and(|bid| bid.bidder == *sender);
+    if is_buyer {
+        roles.push(DocumentRole::Buyer);
+    }
+    let is_inspector = auction
+        .inspection
+        .as_ref()
+        .is_some_and(|inspection| inspection.inspector == *sender);
+    if is_inspector {
+        roles.push(DocumentRole::Inspector);
+    }
+    if *sender == config.admin {
+        roles.push(DocumentRole::Arbitrator);
+    }
+    roles
+}
+
+// Lifecycle stages at which each role may attach each kind of document:
+// - seller: listing documents before the first bid, the shipping label once sold
+// - buyer: photos and assays of what arrived, while the sale is open or disputed
+// - inspector: photos and assays while the lot is with them
+// - arbitrator: anything while the auction is disputed or frozen
+fn allowed(role: DocumentRole, status: &AuctionStatus, kind: DocumentKind) -> bool {
+    match role {
+        DocumentRole::Seller => match status {
+            AuctionStatus::Active => kind != DocumentKind::ShippingLabel,
+            AuctionStatus::Sold => kind == DocumentKind::ShippingLabel,
+            _ => false,
+        },
+        DocumentRole::Buyer => {
+            matches!(status, AuctionStatus::Sold | AuctionStatus::Disputed)
+                && matches!(kind, DocumentKind::Photo | DocumentKind::AssayCertificate)
+        }
+        DocumentRole::Inspector => {
+            *status == AuctionStatus::AwaitingInspection
+                && matches!(kind, DocumentKind::Photo | DocumentKind::AssayCertificate)
+        }
+        DocumentRole::Arbitrator => {
+            matches!(status, AuctionStatus::Disputed | AuctionStatus::Frozen)
+        }
+    }
+}
+
+// Role the sender attaches this kind of document in at the auction's current stage
+pub fn attaching_role(
+    config: &Config,
+    auction: &Auction,
+    sender: &Addr,
+    kind: DocumentKind,
+) -> Result<DocumentRole, ContractError> {
+    let roles = roles(config, auction, sender);
+    if roles.is_empty() {
+        return Err(ContractError::Unauthorized {});
+    }
+    match roles.into_iter().find(|role| allowed(*role, &auction.status, kind)) {
+        Some(DocumentRole::Seller) if !auction.bids.is_empty() && auction.status == AuctionStatus::Active => {
+            Err(ContractError::DocumentsLocked {})
+        }
+        Some(role) => Ok(role),
+        None => Err(ContractError::DocumentNotAllowed {}),
+    }
+}
+
+pub fn attach(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    document: Document,
+) -> Result<usize, ContractError> {
+    document.hash.validate()?;
+    let mut documents = DOCUMENTS.may_load(storage, auction_id)?.unwrap_or_default();
+    let max = allowance(&document);
+    if documents.iter().filter(|attached| shares_allowance(attached, &document)).count() >= max {
+        return Err(ContractError::TooManyDocuments { max });
+    }
+    if documents.iter().any(|attached| attached.hash == document.hash) {
+        return Err(ContractError::DuplicateDocument {});
+    }
+
+    documents.push(document);
+    DOCUMENTS.save(storage, auction_id, &documents)?;
+    Ok(documents.len() - 1)
+}
+
+// Once bidding starts the listing documents are what bidders relied on and stay fixed
+pub fn remove(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    auction: &Auction,
+    sender: &Addr,
+    index: u32,
+) -> Result<Document, ContractError> {
+    if *sender != auction.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+    if auction.status != AuctionStatus::Active || !auction.bids.is_empty() {
+        return Err(ContractError::DocumentsLocked {});
+    }
+
+    let mut documents = DOCUMENTS.may_load(storage, auction_id)?.unwrap_or_default();
+    let index = index as usize;
+    if index >= documents.len() {
+        return Err(ContractError::DocumentNotFound {});
+    }
+    let document = documents.remove(index);
+    DOCUMENTS.save(storage, auction_id, &documents)?;
+    Ok(document)
+}
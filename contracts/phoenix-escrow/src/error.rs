@@ -1,4 +1,6 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
+use phoenix_fees::FeeError;
+use phoenix_metals::MetalsError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -12,6 +14,12 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Fee(#[from] FeeError),
+
+    #[error("{0}")]
+    Metadata(#[from] MetalsError),
+
     #[error("Unauthorized")]
     Unauthorized {},
     
@@ -42,6 +50,101 @@ pub enum ContractError {
     #[error("Auction already has bids")]
     AuctionHasBids {},
     
+    // Bond and delivery errors
+    #[error("Seller bond of {required} required")]
+    BondRequired { required: Uint128 },
+
+    #[error("No seller bond required, funds must not be attached")]
+    BondNotRequired {},
+
+    #[error("Invalid bond config")]
+    InvalidBondConfig {},
+
+    #[error("Delivery window still open")]
+    DeliveryWindowOpen {},
+
+    #[error("Auction not disputed")]
+    NotDisputed {},
+
+    #[error("Invalid denom")]
+    InvalidDenom {},
+
+    #[error("Funds must not be attached to a deposit-backed bid")]
+    UnexpectedFunds {},
+
+    // Insurance errors
+    #[error("No insurance pool configured")]
+    InsuranceUnavailable {},
+
+    #[error("Insurance pool cannot cover this order")]
+    CoverageUnavailable {},
+
+    #[error("Declared value exceeds the price paid for the lot")]
+    DeclaredValueTooHigh {},
+
+    // Ledger errors
+    #[error("Withdraw amount must be greater than zero")]
+    ZeroWithdraw {},
+
+    #[error("Contract balance of {denom} does not cover the ledger")]
+    Insolvent { denom: String },
+
+    // Certification errors
+    #[error("Cert {cert_number} is already listed in auction {auction_id}")]
+    CertAlreadyListed { cert_number: String, auction_id: u64 },
+
+    #[error("Cert {cert_number} was verified at grade {verified}")]
+    GradeMismatch { cert_number: String, verified: u8 },
+
+    #[error("Attester must cover at least one grading service")]
+    NoGradingServices {},
+
+    // Provenance errors
+    #[error("Serial {serial} is already listed in auction {auction_id}")]
+    SerialAlreadyListed { serial: String, auction_id: u64 },
+
+    // Document errors
+    #[error("Document cannot be attached at this stage")]
+    DocumentNotAllowed {},
+
+    #[error("Listing documents are locked once the auction has bids")]
+    DocumentsLocked {},
+
+    #[error("Document already attached")]
+    DuplicateDocument {},
+
+    #[error("Document allowance of {max} reached")]
+    TooManyDocuments { max: usize },
+
+    #[error("Document not found")]
+    DocumentNotFound {},
+
+    // Inspection errors
+    #[error("Not an approved inspector")]
+    UnknownInspector {},
+
+    #[error("Inspection already required")]
+    InspectionAlreadyRequired {},
+
+    #[error("Lot has not passed inspection yet")]
+    InspectionPending {},
+
+    // Theft report errors
+    #[error("Item is reported stolen")]
+    ReportedStolen {},
+
+    #[error("Item is already flagged")]
+    AlreadyFlagged {},
+
+    #[error("Item is not flagged")]
+    NotFlagged {},
+
+    #[error("Reason must be 1-{max} characters")]
+    InvalidReason { max: usize },
+
+    #[error("Auction not frozen")]
+    NotFrozen {},
+
     // KYC errors
     #[error("KYC verification required")]
     KycRequired {},
@@ -0,0 +1,137 @@
+use cosmwasm_std::{Addr, Coin, Deps, Order, StdError, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
+use crate::msg::DenomSolvency;
+use crate::state::{EscrowTotals, BALANCES, ESCROW_TOTALS, LEDGER_TOTALS};
+
+// Buckets of escrowed funds that are not yet owed to anyone in particular
+#[derive(Clone, Copy)]
+pub enum Escrow {
+    OpenBids,
+    AwaitingRelease,
+    BondsHeld,
+}
+
+// Credit withdrawable funds to an address
+pub fn credit(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    BALANCES.update(storage, (owner, denom), |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_add(amount)?)
+    })?;
+    LEDGER_TOTALS.update(storage, denom, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default().checked_add(amount)?)
+    })?;
+    Ok(())
+}
+
+// Take funds out of an address's ledger balance
+pub fn debit(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    denom: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let balance = BALANCES.may_load(storage, (owner, denom))?.unwrap_or_default();
+    if balance < amount {
+        return Err(ContractError::InsufficientFunds {});
+    }
+
+    let remaining = balance - amount;
+    if remaining.is_zero() {
+        BALANCES.remove(storage, (owner, denom));
+    } else {
+        BALANCES.save(storage, (owner, denom), &remaining)?;
+    }
+
+    let total = LEDGER_TOTALS.may_load(storage, denom)?.unwrap_or_default();
+    let total = total.checked_sub(amount).map_err(StdError::from)?;
+    LEDGER_TOTALS.save(storage, denom, &total)?;
+    Ok(())
+}
+
+pub fn balance_of(storage: &dyn Storage, owner: &Addr, denom: &str) -> StdResult<Uint128> {
+    Ok(BALANCES.may_load(storage, (owner, denom))?.unwrap_or_default())
+}
+
+// All non-zero ledger balances of an address
+pub fn balances(storage: &dyn Storage, owner: &Addr) -> StdResult<Vec<Coin>> {
+    BALANCES
+        .prefix(owner)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect()
+}
+
+pub fn ledger_total(storage: &dyn Storage, denom: &str) -> StdResult<Uint128> {
+    Ok(LEDGER_TOTALS.may_load(storage, denom)?.unwrap_or_default())
+}
+
+pub fn escrow_add(
+    storage: &mut dyn Storage,
+    denom: &str,
+    bucket: Escrow,
+    amount: Uint128,
+) -> StdResult<()> {
+    let mut totals = ESCROW_TOTALS.may_load(storage, denom)?.unwrap_or_default();
+    let total = escrow_bucket(&mut totals, bucket);
+    *total = total.checked_add(amount)?;
+    ESCROW_TOTALS.save(storage, denom, &totals)
+}
+
+pub fn escrow_sub(
+    storage: &mut dyn Storage,
+    denom: &str,
+    bucket: Escrow,
+    amount: Uint128,
+) -> StdResult<()> {
+    let mut totals = ESCROW_TOTALS.may_load(storage, denom)?.unwrap_or_default();
+    let total = escrow_bucket(&mut totals, bucket);
+    *total = total.checked_sub(amount)?;
+    ESCROW_TOTALS.save(storage, denom, &totals)
+}
+
+fn escrow_bucket(totals: &mut EscrowTotals, bucket: Escrow) -> &mut Uint128 {
+    match bucket {
+        Escrow::OpenBids => &mut totals.open_bids,
+        Escrow::AwaitingRelease => &mut totals.awaiting_release,
+        Escrow::BondsHeld => &mut totals.bonds_held,
+    }
+}
+
+// Compares everything the contract owes in a denom with what it holds
+pub fn solvency(
+    deps: Deps,
+    contract: &Addr,
+    fee_address: &Addr,
+    denom: &str,
+) -> StdResult<DenomSolvency> {
+    let escrow = ESCROW_TOTALS.may_load(deps.storage, denom)?.unwrap_or_default();
+    let ledger = ledger_total(deps.storage, denom)?;
+    let unpaid_fees = balance_of(deps.storage, fee_address, denom)?;
+    let liabilities = escrow.open_bids
+        .checked_add(escrow.awaiting_release)?
+        .checked_add(escrow.bonds_held)?
+        .checked_add(ledger)?;
+    let bank_balance = deps.querier.query_balance(contract, denom)?.amount;
+    let shortfall = liabilities.saturating_sub(bank_balance);
+
+    Ok(DenomSolvency {
+        denom: denom.to_string(),
+        open_bids: escrow.open_bids,
+        awaiting_release: escrow.awaiting_release,
+        bonds_held: escrow.bonds_held,
+        withdrawable: ledger - unpaid_fees,
+        unpaid_fees,
+        liabilities,
+        bank_balance,
+        shortfall,
+        solvent: shortfall.is_zero(),
+    })
+}
@@ -1,4 +1,10 @@
 pub mod contract;
+pub mod documents;
 pub mod msg;
+pub mod provenance;
+pub mod theft;
 pub mod state;
 pub mod error;
+pub mod certification;
+pub mod ledger;
+pub mod valuation;
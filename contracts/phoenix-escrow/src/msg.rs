@@ -1,13 +1,39 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::state::{
+    BondConfig, CertVerification, Document, FlagEvent, ItemRef, OwnershipTransfer, SellerBond,
+    TheftReport,
+};
+pub use phoenix_insurance::state::RiskTier;
+pub use phoenix_metals::{
+    Certification, ContentHash, DocumentKind, GradingService, ListingMetadata, Metal, ProductForm,
+    Weight, WeightUnit,
+};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub admin: String,
-    pub fee_percentage: u64,
+    pub fee_bps: u16,  // 110 = 1.1%
     pub fee_address: String,
     pub require_kyc: Option<bool>,  // Simple KYC flag
+    pub denom: Option<String>,  // Defaults to utestcore
+    pub delivery_timeout_hours: Option<u64>,
+    pub seller_bond: Option<BondConfig>,
+    pub insurance_pool: Option<String>,
+    pub registry: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+// Shipping cover a buyer opts into, declared at no more than the price paid;
+// the premium is quoted by the insurance pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CoverageRequest {
+    pub declared_value: Uint128,
+    pub tier: RiskTier,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -15,16 +41,33 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     CreateAuction {
         item_id: String,
+        metadata: ListingMetadata,
         starting_price: Uint128,
         reserve_price: Option<Uint128>,
         buy_now_price: Option<Uint128>,
         duration_hours: u64,
+        // Approved inspector the lot must pass before funds release
+        #[serde(default)]
+        inspector: Option<String>,
     },
     PlaceBid {
         auction_id: u64,
     },
+    // Bid against the sender's deposit instead of attached funds
+    PlaceBidFromDeposit {
+        auction_id: u64,
+        amount: Uint128,
+    },
+    Deposit {},
+    // Attach the premium on top of the price when opting into insurance
     BuyNow {
         auction_id: u64,
+        insurance: Option<CoverageRequest>,
+    },
+    // Winning bidder insures a sold lot before it is released
+    InsureOrder {
+        auction_id: u64,
+        insurance: CoverageRequest,
     },
     EndAuction {
         auction_id: u64,
@@ -35,6 +78,29 @@ pub enum ExecuteMsg {
     ReleaseFunds {
         auction_id: u64,
     },
+    ClaimNonDelivery {
+        auction_id: u64,
+    },
+    OpenDispute {
+        auction_id: u64,
+    },
+    ResolveDispute {
+        auction_id: u64,
+        refund_buyer: bool,
+    },
+    UpdateBondConfig {
+        seller_bond: Option<BondConfig>,
+    },
+    UpdateInsurancePool {
+        insurance_pool: Option<String>,
+    },
+    UpdateRegistry {
+        registry: Option<String>,
+    },
+    Withdraw {
+        denom: String,
+        amount: Uint128,
+    },
     
     // Simple KYC functions
     VerifyUser {
@@ -43,6 +109,77 @@ pub enum ExecuteMsg {
     RevokeVerification {
         address: String,
     },
+
+    // Grading attestation
+    AddAttester {
+        address: String,
+        services: Vec<GradingService>,
+    },
+    RemoveAttester {
+        address: String,
+    },
+    // Attester confirms the cert exists at the given grade
+    VerifyCert {
+        service: GradingService,
+        cert_number: String,
+        grade: u8,
+    },
+
+    // Theft reports
+    AddReporter {
+        address: String,
+    },
+    RemoveReporter {
+        address: String,
+    },
+    // Blocks new listings of the item and freezes the one it is committed to
+    FlagStolen {
+        item: ItemRef,
+        reason: String,
+    },
+    UnflagStolen {
+        item: ItemRef,
+        reason: String,
+    },
+    // Arbitrator ruling on a frozen listing: cancel it and refund the buyer, or resume it
+    ResolveFrozen {
+        auction_id: u64,
+        cancel: bool,
+    },
+
+    // Documents
+    // Who may attach what depends on the sender's role and the auction status
+    AttachDocument {
+        auction_id: u64,
+        kind: DocumentKind,
+        hash: ContentHash,
+    },
+    // Sellers can withdraw listing documents until the first bid
+    RemoveDocument {
+        auction_id: u64,
+        index: u32,
+    },
+
+    // Inspection
+    AddInspector {
+        address: String,
+    },
+    RemoveInspector {
+        address: String,
+    },
+    // Buyer has a sold lot routed through an approved inspector before release
+    RequireInspection {
+        auction_id: u64,
+        inspector: String,
+    },
+    // A failed inspection refunds the buyer
+    RecordInspection {
+        auction_id: u64,
+        passed: bool,
+        measured_weight: Weight,
+        measured_fineness: u16,
+        report: ContentHash,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -60,14 +197,54 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
     IsVerified { address: String },
+    SellerBond { auction_id: u64 },
+    RequiredBond { seller: String },
+    Balance { address: String },
+    BidderAccount { address: String },
+    Solvency {},
+    // Pure metal content and price per pure troy ounce of open listings, in id order
+    ListingValuations {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Open listings of one metal ranked by price per pure troy ounce, cheapest first
+    CompareListings {
+        metal: Metal,
+        limit: Option<u32>,
+    },
+    Attester { address: String },
+    // Every listing of a graded item, oldest first
+    ListingsByCert {
+        service: GradingService,
+        cert_number: String,
+    },
+    // Chain of custody of a serialized item
+    ProvenanceHistory {
+        refiner: String,
+        serial: String,
+    },
+    IsReporter { address: String },
+    StolenReport { item: ItemRef },
+    // Every flag and unflag, oldest first
+    FlagHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    Documents { auction_id: u64 },
+    IsInspector { address: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
     pub admin: Addr,
-    pub fee_percentage: u64,
+    pub fee_bps: u16,
     pub fee_address: Addr,
     pub require_kyc: bool,
+    pub denom: String,
+    pub delivery_timeout_hours: u64,
+    pub seller_bond: Option<BondConfig>,
+    pub insurance_pool: Option<Addr>,
+    pub registry: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -85,3 +262,120 @@ pub struct ListAuctionsResponse {
 pub struct ListCompletedAuctionsResponse {
     pub auctions: Vec<AuctionResponse>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SellerBondResponse {
+    pub auction_id: u64,
+    pub bond: Option<SellerBond>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RequiredBondResponse {
+    pub seller: Addr,
+    pub amount: Uint128,
+    pub denom: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BalanceResponse {
+    pub address: Addr,
+    pub balances: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockedBid {
+    pub auction_id: u64,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BidderAccountResponse {
+    pub address: Addr,
+    pub denom: String,
+    pub available: Uint128,
+    pub locked: Uint128,
+    pub locked_bids: Vec<LockedBid>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DenomSolvency {
+    pub denom: String,
+    pub open_bids: Uint128,
+    pub awaiting_release: Uint128,
+    pub bonds_held: Uint128,
+    // Ledger balances other than the fee address
+    pub withdrawable: Uint128,
+    // Ledger balance of the fee address
+    pub unpaid_fees: Uint128,
+    pub liabilities: Uint128,
+    pub bank_balance: Uint128,
+    pub shortfall: Uint128,
+    pub solvent: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SolvencyResponse {
+    pub denoms: Vec<DenomSolvency>,
+    pub solvent: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingValuation {
+    pub auction_id: u64,
+    pub metal: Metal,
+    pub form: ProductForm,
+    pub pure_troy_ounces: Decimal,
+    // Standing high bid, or the starting price before any bid
+    pub price: Uint128,
+    pub price_per_troy_ounce: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingValuationsResponse {
+    // Denom `price` and `price_per_troy_ounce` are quoted in
+    pub denom: String,
+    pub listings: Vec<ListingValuation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AttesterResponse {
+    pub address: Addr,
+    // Empty when the address is not an attester
+    pub services: Vec<GradingService>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CertListingsResponse {
+    pub service: GradingService,
+    pub cert_number: String,
+    pub verification: Option<CertVerification>,
+    pub listings: Vec<AuctionResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProvenanceResponse {
+    pub refiner: String,
+    pub serial: String,
+    // Buyer of the latest completed sale
+    pub current_owner: Option<Addr>,
+    pub listings: Vec<u64>,
+    pub transfers: Vec<OwnershipTransfer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StolenReportResponse {
+    pub item: ItemRef,
+    // None when the item is not flagged
+    pub report: Option<TheftReport>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FlagHistoryResponse {
+    pub events: Vec<FlagEvent>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DocumentsResponse {
+    pub auction_id: u64,
+    pub documents: Vec<Document>,
+}
@@ -0,0 +1,105 @@
+use cosmwasm_std::{Order, StdResult, Storage};
+use phoenix_metals::{canonical_serial_key, ListingMetadata};
+
+use crate::error::ContractError;
+use crate::msg::ProvenanceResponse;
+use crate::state::{Auction, Bid, OwnershipTransfer, auctions, ITEMS};
+
+// Ties a new listing to its serialized item; a bar can only be on sale once at a time
+pub fn register_listing(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    metadata: &ListingMetadata,
+) -> Result<(), ContractError> {
+    let (refiner, serial) = match metadata.serial_key() {
+        Some(key) => key,
+        None => return Ok(()),
+    };
+    let key = (refiner.as_str(), serial.as_str());
+    let mut record = ITEMS.may_load(storage, key)?.unwrap_or_default();
+
+    if record.stolen.is_some() {
+        return Err(ContractError::ReportedStolen {});
+    }
+    if let Some(&last) = record.listings.last() {
+        if auctions().load(storage, last)?.status.holds_item() {
+            return Err(ContractError::SerialAlreadyListed {
+                serial,
+                auction_id: last,
+            });
+        }
+    }
+
+    record.listings.push(auction_id);
+    ITEMS.save(storage, key, &record)?;
+    Ok(())
+}
+
+// Appends the completed sale to the item's chain of custody
+pub fn record_transfer(
+    storage: &mut dyn Storage,
+    auction_id: u64,
+    auction: &Auction,
+    winning_bid: &Bid,
+    denom: &str,
+    now: u64,
+) -> StdResult<()> {
+    let (refiner, serial) = match auction.metadata.as_ref().and_then(|metadata| metadata.serial_key()) {
+        Some(key) => key,
+        None => return Ok(()),
+    };
+    let key = (refiner.as_str(), serial.as_str());
+    let mut record = ITEMS.may_load(storage, key)?.unwrap_or_default();
+    record.transfers.push(OwnershipTransfer {
+        auction_id,
+        from: auction.creator.clone(),
+        to: winning_bid.bidder.clone(),
+        price: winning_bid.amount,
+        denom: denom.to_string(),
+        transferred_at: now,
+    });
+    ITEMS.save(storage, key, &record)
+}
+
+pub fn history(
+    storage: &dyn Storage,
+    refiner: String,
+    serial: String,
+) -> StdResult<ProvenanceResponse> {
+    let (refiner, serial) = canonical_serial_key(&refiner, &serial);
+    let record = ITEMS
+        .may_load(storage, (refiner.as_str(), serial.as_str()))?
+        .unwrap_or_default();
+    Ok(ProvenanceResponse {
+        refiner,
+        serial,
+        current_owner: record.transfers.last().map(|transfer| transfer.to.clone()),
+        listings: record.listings,
+        transfers: record.transfers,
+    })
+}
+
+// Moves records stored under a raw (refiner, serial) onto the canonical key,
+// merging them when the same item was recorded under both
+pub fn canonicalize_keys(storage: &mut dyn Storage) -> StdResult<()> {
+    let raw = ITEMS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for ((refiner, serial), record) in raw {
+        let (canonical_refiner, canonical_serial) = canonical_serial_key(&refiner, &serial);
+        if (canonical_refiner.as_str(), canonical_serial.as_str()) == (refiner.as_str(), serial.as_str()) {
+            continue;
+        }
+        ITEMS.remove(storage, (refiner.as_str(), serial.as_str()));
+        let key = (canonical_refiner.as_str(), canonical_serial.as_str());
+        let mut merged = ITEMS.may_load(storage, key)?.unwrap_or_default();
+        merged.listings.extend(record.listings);
+        merged.listings.sort_unstable();
+        merged.listings.dedup();
+        merged.transfers.extend(record.transfers);
+        merged.transfers.sort_by_key(|transfer| transfer.transferred_at);
+        merged.stolen = merged.stolen.or(record.stolen);
+        ITEMS.save(storage, key, &merged)?;
+    }
+    Ok(())
+}
@@ -1,21 +1,56 @@
+use std::fmt;
+
 use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use phoenix_metals::{
+    canonical_serial_key, ContentHash, DocumentKind, GradingService, ListingMetadata, Weight,
+};
 use serde::{Deserialize, Serialize};
 use schemars::JsonSchema;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub admin: Addr,
-    pub fee_percentage: u64,
+    // Platform fee in basis points (110 = 1.1%)
+    pub fee_bps: u16,
     pub fee_address: Addr,
     // Simple KYC toggle
     pub require_kyc: bool,
+    // Denom all bids, payouts and bonds are settled in
+    pub denom: String,
+    // Hours the seller has to deliver a sold lot before the buyer can claim non-delivery
+    pub delivery_timeout_hours: u64,
+    // Seller performance bond policy; no bond is required when unset
+    pub seller_bond: Option<BondConfig>,
+    // Insurance pool selling shipping cover and receiving sale fees; when unset
+    // buyers cannot opt in and fees are credited to fee_address
+    pub insurance_pool: Option<Addr>,
+    // Platform registry the fee is read from at settlement; `fee_bps` applies when unset
+    pub registry: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondConfig {
+    // Bond posted by unverified sellers without trade history
+    pub amount: Uint128,
+    // Reduced bond for KYC-verified sellers (defaults to `amount`)
+    pub verified_amount: Option<Uint128>,
+    // Completed sales after which a seller no longer posts a bond
+    pub waive_after_sales: Option<u64>,
+    // Share of the bond slashed on non-delivery or a lost dispute, in basis points
+    pub slash_bps: u16,
+    // Share of the slashed amount paid to the buyer, in basis points; the rest is paid
+    // to the platform like a sale fee
+    pub buyer_share_bps: u16,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Auction {
     pub creator: Addr,
     pub item_id: String,
+    // Absent on listings created before metadata was required
+    #[serde(default)]
+    pub metadata: Option<ListingMetadata>,
     pub starting_price: Uint128,
     pub reserve_price: Option<Uint128>,
     pub buy_now_price: Option<Uint128>,
@@ -24,6 +59,32 @@ pub struct Auction {
     pub highest_bid: Option<Bid>,
     pub status: AuctionStatus,
     pub created_at: u64,
+    // Set when the lot is sold, starts the delivery window
+    pub sold_at: Option<u64>,
+    // Status to return to if the arbitrator lifts a freeze
+    #[serde(default)]
+    pub frozen_from: Option<AuctionStatus>,
+    // Set when the listing or the buyer requires the lot to pass an inspector before release
+    #[serde(default)]
+    pub inspection: Option<Inspection>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Inspection {
+    pub inspector: Addr,
+    // Seller for listings that require inspection, otherwise the buyer
+    pub required_by: Addr,
+    pub result: Option<InspectionResult>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InspectionResult {
+    pub passed: bool,
+    pub measured_weight: Weight,
+    pub measured_fineness: u16,
+    // Inspector's report, also listed among the auction's documents
+    pub report: ContentHash,
+    pub inspected_at: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -39,13 +100,251 @@ pub enum AuctionStatus {
     Ended,
     Sold,
     Cancelled,
+    Disputed,
+    Completed,
+    Refunded,
+    // Item reported stolen, waiting on the arbitrator
+    Frozen,
+    // Sold, shipped to the inspector; a pass returns it to Sold, a failure refunds the buyer
+    AwaitingInspection,
+}
+
+impl AuctionStatus {
+    // The item is still committed to this listing: on sale, awaiting delivery or
+    // inspection, in dispute or frozen
+    pub fn holds_item(&self) -> bool {
+        matches!(
+            self,
+            AuctionStatus::Active
+                | AuctionStatus::Sold
+                | AuctionStatus::AwaitingInspection
+                | AuctionStatus::Disputed
+                | AuctionStatus::Frozen
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuctionStatus::Active => "active",
+            AuctionStatus::Ended => "ended",
+            AuctionStatus::Sold => "sold",
+            AuctionStatus::Cancelled => "cancelled",
+            AuctionStatus::Disputed => "disputed",
+            AuctionStatus::Completed => "completed",
+            AuctionStatus::Refunded => "refunded",
+            AuctionStatus::Frozen => "frozen",
+            AuctionStatus::AwaitingInspection => "awaiting_inspection",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SellerBond {
+    pub seller: Addr,
+    pub amount: Uint128,
+    // Slashing terms are fixed when the bond is posted
+    pub slash_bps: u16,
+    pub buyer_share_bps: u16,
+    pub status: BondStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum BondStatus {
+    Held,
+    Returned,
+    Slashed { slashed: Uint128 },
+}
+
+// Auctions by listed metal and status, so open listings of one metal are read
+// without going through the whole history. Listings without metadata have no metal
+pub struct AuctionIndexes<'a> {
+    pub metal_status: MultiIndex<'a, (String, String), Auction, u64>,
+}
+
+impl IndexList<Auction> for AuctionIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Auction>> + '_> {
+        let v: Vec<&dyn Index<Auction>> = vec![&self.metal_status];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn auctions<'a>() -> IndexedMap<'a, u64, Auction, AuctionIndexes<'a>> {
+    let indexes = AuctionIndexes {
+        metal_status: MultiIndex::new(
+            |_, auction| {
+                let metal = auction.metadata.as_ref().map(|metadata| metadata.metal.to_string());
+                (metal.unwrap_or_default(), auction.status.as_str().to_string())
+            },
+            "auctions",
+            "auctions__metal_status",
+        ),
+    };
+    IndexedMap::new("auctions", indexes)
 }
 
 // Storage
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
 pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
 pub const COMPLETED_AUCTIONS: Map<u64, Auction> = Map::new("completed_auctions");
 
+// Seller bonds keyed by auction id
+pub const SELLER_BONDS: Map<u64, SellerBond> = Map::new("seller_bonds");
+// Completed sales per seller, used to waive bonds for established sellers
+pub const SELLER_SALES: Map<&Addr, u64> = Map::new("seller_sales");
+
+// Pull-payment ledger: (owner, denom) -> withdrawable amount
+pub const BALANCES: Map<(&Addr, &str), Uint128> = Map::new("balances");
+// Sum of BALANCES per denom, checked against the bank balance on withdrawal
+pub const LEDGER_TOTALS: Map<&str, Uint128> = Map::new("ledger_totals");
+
+// Funds held outside the ledger, per denom
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+pub struct EscrowTotals {
+    // Standing high bids in active auctions
+    pub open_bids: Uint128,
+    // Winning bids of sold lots waiting on delivery or a dispute
+    pub awaiting_release: Uint128,
+    // Seller bonds not yet returned or slashed
+    pub bonds_held: Uint128,
+}
+
+pub const ESCROW_TOTALS: Map<&str, EscrowTotals> = Map::new("escrow_totals");
+
+// Funds committed to the standing high bid: (bidder, auction id) -> amount
+pub const LOCKED_BIDS: Map<(&Addr, u64), Uint128> = Map::new("locked_bids");
+
 // Simple KYC: just a map of verified addresses
 pub const KYC_VERIFIED: Map<&Addr, bool> = Map::new("kyc_verified");
+
+// Grading-service attestation that a cert exists with the stated grade
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CertVerification {
+    pub attester: Addr,
+    pub grade: u8,
+    pub verified_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+pub struct CertRecord {
+    // Every listing of the cert, oldest first
+    pub listings: Vec<u64>,
+    pub verification: Option<CertVerification>,
+    #[serde(default)]
+    pub stolen: Option<TheftReport>,
+}
+
+// Graded items: (grading service, cert number) -> record
+pub const CERTS: Map<(&str, &str), CertRecord> = Map::new("certs");
+// Addresses allowed to verify certs, with the grading services they attest for
+pub const ATTESTERS: Map<&Addr, Vec<GradingService>> = Map::new("attesters");
+
+// A completed sale of a serialized item
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnershipTransfer {
+    pub auction_id: u64,
+    pub from: Addr,
+    pub to: Addr,
+    pub price: Uint128,
+    pub denom: String,
+    pub transferred_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+pub struct ItemRecord {
+    // Every listing of the item, oldest first
+    pub listings: Vec<u64>,
+    // Chain of custody, oldest first
+    pub transfers: Vec<OwnershipTransfer>,
+    #[serde(default)]
+    pub stolen: Option<TheftReport>,
+}
+
+// Serialized items: (refiner, serial) -> record
+pub const ITEMS: Map<(&str, &str), ItemRecord> = Map::new("items");
+
+// An item identified by its refiner serial or its grading cert
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemRef {
+    Serial { refiner: String, serial: String },
+    Cert { service: GradingService, cert_number: String },
+}
+
+impl ItemRef {
+    // The form items are recorded under, however the serial or cert number was typed
+    pub fn canonical(self) -> ItemRef {
+        match self {
+            ItemRef::Serial { refiner, serial } => {
+                let (refiner, serial) = canonical_serial_key(&refiner, &serial);
+                ItemRef::Serial { refiner, serial }
+            }
+            ItemRef::Cert { service, cert_number } => ItemRef::Cert {
+                service,
+                cert_number: cert_number.trim().to_ascii_uppercase(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ItemRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ItemRef::Serial { refiner, serial } => write!(f, "serial:{}/{}", refiner, serial),
+            ItemRef::Cert { service, cert_number } => {
+                write!(f, "cert:{}/{}", service.as_str(), cert_number)
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TheftReport {
+    pub reporter: Addr,
+    pub reason: String,
+    pub reported_at: u64,
+}
+
+// Audit trail entry for every flag and unflag
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FlagEvent {
+    pub id: u64,
+    pub item: ItemRef,
+    pub flagged: bool,
+    pub reporter: Addr,
+    pub reason: String,
+    pub timestamp: u64,
+    // Listing frozen by the flag, if the item was in escrow
+    pub frozen_auction: Option<u64>,
+}
+
+// Capacity a document was attached in
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentRole {
+    Seller,
+    Buyer,
+    Inspector,
+    Arbitrator,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Document {
+    pub kind: DocumentKind,
+    pub hash: ContentHash,
+    pub uploader: Addr,
+    pub role: DocumentRole,
+    // Auction status when the document was attached
+    pub stage: AuctionStatus,
+    pub attached_at: u64,
+}
+
+// Documents per auction, in the order they were attached
+pub const DOCUMENTS: Map<u64, Vec<Document>> = Map::new("documents");
+
+// Approved inspectors and assayers
+pub const INSPECTORS: Map<&Addr, bool> = Map::new("inspectors");
+
+// Law enforcement liaisons and compliance staff allowed to flag stolen items
+pub const REPORTERS: Map<&Addr, bool> = Map::new("reporters");
+pub const FLAG_EVENTS: Map<u64, FlagEvent> = Map::new("flag_events");
+pub const FLAG_EVENT_COUNT: Item<u64> = Item::new("flag_event_count");
@@ -0,0 +1,152 @@
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::state::{
+    AuctionStatus, FlagEvent, ItemRef, TheftReport, auctions, CERTS, FLAG_EVENTS,
+    FLAG_EVENT_COUNT, ITEMS,
+};
+
+const MAX_REASON_LENGTH: usize = 256;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+// `item` is expected in its canonical form
+pub fn report(storage: &dyn Storage, item: &ItemRef) -> StdResult<Option<TheftReport>> {
+    let report = match item {
+        ItemRef::Serial { refiner, serial } => ITEMS
+            .may_load(storage, (refiner.as_str(), serial.as_str()))?
+            .and_then(|record| record.stolen),
+        ItemRef::Cert { service, cert_number } => CERTS
+            .may_load(storage, (service.as_str(), cert_number.as_str()))?
+            .and_then(|record| record.stolen),
+    };
+    Ok(report)
+}
+
+// Flags the item and freezes the listing it is committed to, if any
+pub fn flag(
+    storage: &mut dyn Storage,
+    item: ItemRef,
+    reporter: &Addr,
+    reason: String,
+    now: u64,
+) -> Result<FlagEvent, ContractError> {
+    validate_reason(&reason)?;
+    let item = item.canonical();
+    if report(storage, &item)?.is_some() {
+        return Err(ContractError::AlreadyFlagged {});
+    }
+
+    let stolen = TheftReport {
+        reporter: reporter.clone(),
+        reason: reason.clone(),
+        reported_at: now,
+    };
+    let frozen_auction = match update_report(storage, &item, Some(stolen))? {
+        Some(auction_id) => freeze(storage, auction_id)?,
+        None => None,
+    };
+    log_event(storage, item, true, reporter, reason, now, frozen_auction)
+}
+
+// Clearing a flag allows new listings; a frozen listing stays frozen until the arbitrator rules
+pub fn unflag(
+    storage: &mut dyn Storage,
+    item: ItemRef,
+    reporter: &Addr,
+    reason: String,
+    now: u64,
+) -> Result<FlagEvent, ContractError> {
+    validate_reason(&reason)?;
+    let item = item.canonical();
+    if report(storage, &item)?.is_none() {
+        return Err(ContractError::NotFlagged {});
+    }
+
+    update_report(storage, &item, None)?;
+    log_event(storage, item, false, reporter, reason, now, None)
+}
+
+pub fn history(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FlagEvent>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    FLAG_EVENTS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, event)| event))
+        .collect()
+}
+
+fn validate_reason(reason: &str) -> Result<(), ContractError> {
+    if reason.trim().is_empty() || reason.len() > MAX_REASON_LENGTH {
+        return Err(ContractError::InvalidReason {
+            max: MAX_REASON_LENGTH,
+        });
+    }
+    Ok(())
+}
+
+// Sets or clears the report on the item's record, returning the item's latest listing
+fn update_report(
+    storage: &mut dyn Storage,
+    item: &ItemRef,
+    stolen: Option<TheftReport>,
+) -> StdResult<Option<u64>> {
+    match item {
+        ItemRef::Serial { refiner, serial } => {
+            let key = (refiner.as_str(), serial.as_str());
+            let mut record = ITEMS.may_load(storage, key)?.unwrap_or_default();
+            record.stolen = stolen;
+            ITEMS.save(storage, key, &record)?;
+            Ok(record.listings.last().copied())
+        }
+        ItemRef::Cert { service, cert_number } => {
+            let key = (service.as_str(), cert_number.as_str());
+            let mut record = CERTS.may_load(storage, key)?.unwrap_or_default();
+            record.stolen = stolen;
+            CERTS.save(storage, key, &record)?;
+            Ok(record.listings.last().copied())
+        }
+    }
+}
+
+// Only listings still holding the item are frozen; finished sales are left as they are
+fn freeze(storage: &mut dyn Storage, auction_id: u64) -> StdResult<Option<u64>> {
+    let mut auction = auctions().load(storage, auction_id)?;
+    if !auction.status.holds_item() || auction.status == AuctionStatus::Frozen {
+        return Ok(None);
+    }
+    auction.frozen_from = Some(auction.status);
+    auction.status = AuctionStatus::Frozen;
+    auctions().save(storage, auction_id, &auction)?;
+    Ok(Some(auction_id))
+}
+
+fn log_event(
+    storage: &mut dyn Storage,
+    item: ItemRef,
+    flagged: bool,
+    reporter: &Addr,
+    reason: String,
+    timestamp: u64,
+    frozen_auction: Option<u64>,
+) -> Result<FlagEvent, ContractError> {
+    let id = FLAG_EVENT_COUNT.may_load(storage)?.unwrap_or_default();
+    let event = FlagEvent {
+        id,
+        item,
+        flagged,
+        reporter: reporter.clone(),
+        reason,
+        timestamp,
+        frozen_auction,
+    };
+    FLAG_EVENTS.save(storage, id, &event)?;
+    FLAG_EVENT_COUNT.save(storage, &(id + 1))?;
+    Ok(event)
+}
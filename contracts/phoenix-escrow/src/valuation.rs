@@ -0,0 +1,101 @@
+use cosmwasm_std::{Order, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
+use phoenix_metals::Metal;
+
+use crate::msg::ListingValuation;
+use crate::state::{Auction, AuctionStatus, auctions};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+// What the lot goes for right now: the standing high bid, else the starting price
+fn current_price(auction: &Auction) -> Uint128 {
+    auction
+        .highest_bid
+        .as_ref()
+        .map_or(auction.starting_price, |bid| bid.amount)
+}
+
+// Listings created before metadata was required have nothing to value
+pub fn valuation(auction_id: u64, auction: &Auction) -> StdResult<Option<ListingValuation>> {
+    let metadata = match &auction.metadata {
+        Some(metadata) => metadata,
+        None => return Ok(None),
+    };
+    let price = current_price(auction);
+    Ok(Some(ListingValuation {
+        auction_id,
+        metal: metadata.metal,
+        form: metadata.form,
+        pure_troy_ounces: metadata.pure_troy_ounces()?,
+        price,
+        price_per_troy_ounce: metadata.price_per_troy_ounce(price)?,
+    }))
+}
+
+fn is_open(auction: &Auction, now: u64) -> bool {
+    auction.status == AuctionStatus::Active && auction.ends_at > now
+}
+
+// Open listings in id order
+pub fn open_valuations(
+    storage: &dyn Storage,
+    now: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ListingValuation>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let mut listings = vec![];
+    for item in auctions().range(storage, start, None, Order::Ascending) {
+        let (auction_id, auction) = item?;
+        if !is_open(&auction, now) {
+            continue;
+        }
+        if let Some(listing) = valuation(auction_id, &auction)? {
+            listings.push(listing);
+            if listings.len() == limit {
+                break;
+            }
+        }
+    }
+    Ok(listings)
+}
+
+// Open listings of one metal, cheapest per pure troy ounce first. Ranking
+// needs every candidate, so this reads all active listings of the metal
+// before applying the limit.
+pub fn compare(
+    storage: &dyn Storage,
+    now: u64,
+    metal: Metal,
+    limit: Option<u32>,
+) -> StdResult<Vec<ListingValuation>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let active = (metal.to_string(), AuctionStatus::Active.as_str().to_string());
+    let mut listings = vec![];
+    for item in auctions()
+        .idx
+        .metal_status
+        .prefix(active)
+        .range(storage, None, None, Order::Ascending)
+    {
+        let (auction_id, auction) = item?;
+        // Active listings past their end time are no longer open
+        if !is_open(&auction, now) {
+            continue;
+        }
+        if let Some(listing) = valuation(auction_id, &auction)? {
+            listings.push(listing);
+        }
+    }
+    listings.sort_by(|a, b| {
+        a.price_per_troy_ounce
+            .cmp(&b.price_per_troy_ounce)
+            .then(a.auction_id.cmp(&b.auction_id))
+    });
+    listings.truncate(limit);
+    Ok(listings)
+}
@@ -1,12 +1,40 @@
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_json, Uint128, Addr};
-    use phoenix_escrow::contract::{execute, instantiate, query};
+    use cosmwasm_std::testing::{mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info};
+    use cosmwasm_std::{
+        coins, from_json, to_json_binary, Uint128, Addr, BankMsg, CosmosMsg, Decimal, WasmMsg,
+        WasmQuery, SystemResult, ContractResult, OwnedDeps, Storage, Order
+    };
+    use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+    use phoenix_escrow::contract::{execute, instantiate, migrate, query};
     use phoenix_escrow::msg::{
-        ExecuteMsg, InstantiateMsg, QueryMsg, 
-        ConfigResponse, AuctionResponse
+        ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, 
+        ConfigResponse, AuctionResponse, SellerBondResponse, BalanceResponse,
+        BidderAccountResponse, SolvencyResponse, CoverageRequest, RiskTier,
+        ListingMetadata, Metal, ProductForm, Weight, WeightUnit, ListingValuationsResponse,
+        Certification, GradingService, AttesterResponse, CertListingsResponse, ProvenanceResponse,
+        StolenReportResponse, FlagHistoryResponse, DocumentsResponse, DocumentKind, ContentHash
     };
+    use phoenix_fees::{FeeError, FeeSchedule};
+    use phoenix_insurance::msg::{ExecuteMsg as PoolExecuteMsg, QuoteResponse};
+    use phoenix_pme::msg::QueryMsg as RegistryQueryMsg;
+    use phoenix_escrow::state::{AuctionStatus, BondConfig, BondStatus, DocumentRole, ItemRecord, ItemRef, ITEMS};
+    use phoenix_escrow::error::ContractError;
+    use phoenix_metals::MetalsError;
+
+    // 1 kg .9999 gold bar
+    fn gold_bar() -> ListingMetadata {
+        ListingMetadata {
+            metal: Metal::Gold,
+            form: ProductForm::Bar,
+            weight: Weight::new(Decimal::one(), WeightUnit::Kilogram),
+            fineness: 9999,
+            mint: Some("PAMP Suisse".to_string()),
+            year: Some(2023),
+            certification: None,
+            serial: None,
+        }
+    }
 
     #[test]
     fn test_instantiate() {
@@ -16,9 +44,14 @@ mod tests {
         
         let msg = InstantiateMsg {
             admin: "admin".to_string(),
-            fee_percentage: 1,
+            fee_bps: 110,
             fee_address: "fee_collector".to_string(),
             require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
         };
         
         let res = instantiate(deps.as_mut(), env, info, msg).unwrap();
@@ -29,34 +62,138 @@ mod tests {
         let query_res: ConfigResponse = from_json(&query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
         
         assert_eq!(query_res.admin, Addr::unchecked("admin"));
-        assert_eq!(query_res.fee_percentage, 1);
+        assert_eq!(query_res.fee_bps, 110);
         assert_eq!(query_res.require_kyc, false);
     }
 
+    #[test]
+    fn test_migrate_converts_legacy_config() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        
+        // Config as stored before fees were kept in basis points
+        deps.storage.set(
+            b"config",
+            br#"{"admin":"admin","fee_percentage":2,"fee_address":"fee_collector","require_kyc":true}"#,
+        );
+        assert!(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).is_err());
+        
+        let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        assert_eq!(res.attributes[1].value, "200");
+        let config: ConfigResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(config.fee_bps, 200);
+        assert_eq!(config.fee_address, Addr::unchecked("fee_collector"));
+        assert!(config.require_kyc);
+        assert_eq!(config.denom, "utestcore");
+        assert_eq!(config.delivery_timeout_hours, 14 * 24);
+        
+        // Migrating again keeps the converted config
+        migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        let again: ConfigResponse = from_json(query(deps.as_ref(), env, QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(again, config);
+        
+        // A percentage above 100 cannot be expressed in basis points
+        let mut deps = mock_dependencies();
+        deps.storage.set(
+            b"config",
+            br#"{"admin":"admin","fee_percentage":101,"fee_address":"fee_collector","require_kyc":false}"#,
+        );
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert_eq!(err, ContractError::Fee(FeeError::InvalidBps { bps: 10_100 }));
+    }
+
+    #[test]
+    fn test_migrate_indexes_existing_listings() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: None,
+            duration_hours: 24,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), create_msg).unwrap();
+        
+        // Drop the index, as for listings stored before it existed
+        let index_keys: Vec<Vec<u8>> = deps.storage
+            .range(None, None, Order::Ascending)
+            .map(|(key, _)| key)
+            .filter(|key| key.windows(22).any(|part| part == b"auctions__metal_status"))
+            .collect();
+        assert!(!index_keys.is_empty());
+        for key in &index_keys {
+            deps.storage.remove(key);
+        }
+        let compare = QueryMsg::CompareListings { metal: Metal::Gold, limit: None };
+        let res: ListingValuationsResponse = from_json(query(deps.as_ref(), env.clone(), compare.clone()).unwrap()).unwrap();
+        assert!(res.listings.is_empty());
+        
+        // An item recorded under its serial as typed by the seller
+        let record = ItemRecord { listings: vec![7], transfers: vec![], stolen: None };
+        ITEMS.save(deps.as_mut().storage, ("valcambi", "a123456"), &record).unwrap();
+        
+        migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        let res: ListingValuationsResponse = from_json(query(deps.as_ref(), env.clone(), compare).unwrap()).unwrap();
+        assert_eq!(res.listings.len(), 1);
+        assert_eq!(res.listings[0].auction_id, 0);
+        
+        assert!(ITEMS.may_load(deps.as_ref().storage, ("valcambi", "a123456")).unwrap().is_none());
+        let history = QueryMsg::ProvenanceHistory { refiner: "Valcambi".to_string(), serial: "A123456".to_string() };
+        let res: ProvenanceResponse = from_json(query(deps.as_ref(), env, history).unwrap()).unwrap();
+        assert_eq!(res.listings, vec![7]);
+    }
+
     #[test]
     fn test_create_auction() {
         let mut deps = mock_dependencies();
         let env = mock_env();
-        let info = mock_info("creator", &coins(1000, "utestcore"));
+        let info = mock_info("creator", &[]);
         
         // First instantiate
         let instantiate_msg = InstantiateMsg {
             admin: "admin".to_string(),
-            fee_percentage: 1,
+            fee_bps: 110,
             fee_address: "fee_collector".to_string(),
             require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
         };
         instantiate(deps.as_mut(), env.clone(), info.clone(), instantiate_msg).unwrap();
         
         // Create auction
         let create_msg = ExecuteMsg::CreateAuction {
             item_id: "item123".to_string(),
+            metadata: gold_bar(),
             starting_price: Uint128::from(100u128),
             reserve_price: Some(Uint128::from(200u128)),
             buy_now_price: Some(Uint128::from(500u128)),
             duration_hours: 24,
+            inspector: None,
         };
         
+        // Without a bond to post, attached funds would be stranded
+        let err = execute(deps.as_mut(), env.clone(), mock_info("creator", &coins(1000, "utestcore")), create_msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::BondNotRequired {});
+        
         let res = execute(deps.as_mut(), env.clone(), info, create_msg).unwrap();
         assert_eq!(0, res.messages.len());
         
@@ -66,31 +203,700 @@ mod tests {
         
         assert_eq!(query_res.id, 0);
         assert_eq!(query_res.auction.item_id, "item123");
+        assert_eq!(query_res.auction.metadata, Some(gold_bar()));
         assert_eq!(query_res.auction.starting_price, Uint128::from(100u128));
     }
 
+    #[test]
+    fn test_create_auction_validates_metadata() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), instantiate_msg).unwrap();
+        let create = |metadata: ListingMetadata| ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata,
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: None,
+            duration_hours: 24,
+            inspector: None,
+        };
+
+        let over_fine = ListingMetadata { fineness: 10_001, ..gold_bar() };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), create(over_fine)).unwrap_err();
+        assert_eq!(err, ContractError::Metadata(MetalsError::InvalidFineness { fineness: 10_001 }));
+
+        let weightless = ListingMetadata {
+            weight: Weight::new(Decimal::zero(), WeightUnit::Gram),
+            ..gold_bar()
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), create(weightless)).unwrap_err();
+        assert_eq!(err, ContractError::Metadata(MetalsError::ZeroWeight));
+
+        // 90% silver dimes listed as gold
+        let junk = ListingMetadata {
+            form: ProductForm::JunkSilver,
+            fineness: 9000,
+            ..gold_bar()
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), create(junk)).unwrap_err();
+        assert!(matches!(err, ContractError::Metadata(MetalsError::FormMismatch { .. })));
+
+        let dimes = ListingMetadata {
+            metal: Metal::Silver,
+            form: ProductForm::JunkSilver,
+            weight: Weight::new(Decimal::percent(715), WeightUnit::TroyOunce),
+            fineness: 9000,
+            mint: None,
+            year: Some(1964),
+            certification: None,
+            serial: None,
+        };
+        execute(deps.as_mut(), env.clone(), info, create(dimes.clone())).unwrap();
+        let res: AuctionResponse = from_json(query(deps.as_ref(), env, QueryMsg::Auction { id: 0 }).unwrap()).unwrap();
+        assert_eq!(res.auction.metadata, Some(dimes));
+    }
+
+    #[test]
+    fn test_graded_listings_and_cert_verification() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin = mock_info("admin", &[]);
+        let seller = mock_info("seller", &[]);
+        let attester = mock_info("pcgs_attester", &[]);
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin.clone(), instantiate_msg).unwrap();
+
+        // Only the admin manages attesters, and each must cover a service
+        let add = |services: Vec<GradingService>| ExecuteMsg::AddAttester {
+            address: "pcgs_attester".to_string(),
+            services,
+        };
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), add(vec![GradingService::Pcgs])).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(deps.as_mut(), env.clone(), admin.clone(), add(vec![])).unwrap_err();
+        assert_eq!(err, ContractError::NoGradingServices {});
+        execute(deps.as_mut(), env.clone(), admin, add(vec![GradingService::Pcgs, GradingService::Pcgs])).unwrap();
+        let res: AttesterResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Attester {
+            address: "pcgs_attester".to_string(),
+        }).unwrap()).unwrap();
+        assert_eq!(res.services, vec![GradingService::Pcgs]);
+
+        let graded = |grade: u8| ExecuteMsg::CreateAuction {
+            item_id: "eagle-44871290".to_string(),
+            metadata: ListingMetadata {
+                form: ProductForm::Coin,
+                weight: Weight::new(Decimal::one(), WeightUnit::TroyOunce),
+                fineness: 9167,
+                mint: Some("US Mint".to_string()),
+                year: Some(2024),
+                certification: Some(Certification {
+                    service: GradingService::Pcgs,
+                    cert_number: "44871290".to_string(),
+                    grade,
+                    designation: Some("MS First Strike".to_string()),
+                }),
+                ..gold_bar()
+            },
+            starting_price: Uint128::from(2_500u128),
+            reserve_price: None,
+            buy_now_price: None,
+            duration_hours: 24,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), seller.clone(), graded(70)).unwrap();
+
+        // The same cert cannot be on sale twice
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), graded(70)).unwrap_err();
+        assert_eq!(err, ContractError::CertAlreadyListed {
+            cert_number: "44871290".to_string(),
+            auction_id: 0,
+        });
+
+        // Attesters only vouch for their own grading service
+        let verify = |service: GradingService| ExecuteMsg::VerifyCert {
+            service,
+            cert_number: "44871290".to_string(),
+            grade: 69,
+        };
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), verify(GradingService::Pcgs)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(deps.as_mut(), env.clone(), attester.clone(), verify(GradingService::Ngc)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let res = execute(deps.as_mut(), env.clone(), attester, verify(GradingService::Pcgs)).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "grade" && attr.value == "69"));
+
+        // Once the first listing is off the market the cert can be relisted, at its verified grade
+        execute(deps.as_mut(), env.clone(), seller.clone(), ExecuteMsg::CancelAuction { auction_id: 0 }).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), graded(70)).unwrap_err();
+        assert_eq!(err, ContractError::GradeMismatch {
+            cert_number: "44871290".to_string(),
+            verified: 69,
+        });
+        execute(deps.as_mut(), env.clone(), seller, graded(69)).unwrap();
+
+        let res: CertListingsResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::ListingsByCert {
+            service: GradingService::Pcgs,
+            cert_number: "44871290".to_string(),
+        }).unwrap()).unwrap();
+        assert_eq!(res.listings.iter().map(|listing| listing.id).collect::<Vec<_>>(), vec![0, 1]);
+        let verification = res.verification.unwrap();
+        assert_eq!(verification.attester, Addr::unchecked("pcgs_attester"));
+        assert_eq!(verification.grade, 69);
+        assert_eq!(verification.verified_at, env.block.time.seconds());
+
+        // Certs are keyed per grading service
+        let res: CertListingsResponse = from_json(query(deps.as_ref(), env, QueryMsg::ListingsByCert {
+            service: GradingService::Ngc,
+            cert_number: "44871290".to_string(),
+        }).unwrap()).unwrap();
+        assert!(res.listings.is_empty());
+        assert_eq!(res.verification, None);
+    }
+
+    #[test]
+    fn test_serialized_bar_provenance() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        let list = ExecuteMsg::CreateAuction {
+            item_id: "valcambi-kilo".to_string(),
+            metadata: ListingMetadata {
+                mint: Some("Valcambi".to_string()),
+                serial: Some("A123456".to_string()),
+                ..gold_bar()
+            },
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        };
+        let provenance = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env| -> ProvenanceResponse {
+            from_json(query(deps.as_ref(), env, QueryMsg::ProvenanceHistory {
+                refiner: "Valcambi".to_string(),
+                serial: "A123456".to_string(),
+            }).unwrap()).unwrap()
+        };
+
+        execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), list.clone()).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), mock_info("other", &[]), list.clone()).unwrap_err();
+        assert_eq!(err, ContractError::SerialAlreadyListed {
+            serial: "A123456".to_string(),
+            auction_id: 0,
+        });
+
+        // Still blocked while the sold bar awaits delivery
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 0, insurance: None }).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), mock_info("other", &[]), list.clone()).unwrap_err();
+        assert_eq!(err, ContractError::SerialAlreadyListed {
+            serial: "A123456".to_string(),
+            auction_id: 0,
+        });
+        assert_eq!(provenance(&deps, env.clone()).current_owner, None);
+
+        // Delivery confirmed: the sale enters the chain of custody and the new owner can resell
+        env.block.time = env.block.time.plus_seconds(3600);
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::ReleaseFunds { auction_id: 0 }).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), list).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 1, insurance: None }).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), ExecuteMsg::OpenDispute { auction_id: 1 }).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), ExecuteMsg::ResolveDispute { auction_id: 1, refund_buyer: false }).unwrap();
+
+        let history = provenance(&deps, env.clone());
+        assert_eq!(history.current_owner, Some(Addr::unchecked("bob")));
+        assert_eq!(history.listings, vec![0, 1]);
+        let trail = history.transfers.iter()
+            .map(|transfer| (transfer.auction_id, transfer.from.as_str(), transfer.to.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(trail, vec![(0, "seller", "alice"), (1, "alice", "bob")]);
+        assert_eq!(history.transfers[0].price, Uint128::from(500u128));
+        assert_eq!(history.transfers[0].denom, "utestcore");
+        assert_eq!(history.transfers[0].transferred_at, env.block.time.seconds());
+
+        // Unknown items have an empty trail
+        let res: ProvenanceResponse = from_json(query(deps.as_ref(), env, QueryMsg::ProvenanceHistory {
+            refiner: "Valcambi".to_string(),
+            serial: "A999999".to_string(),
+        }).unwrap()).unwrap();
+        assert!(res.transfers.is_empty() && res.listings.is_empty());
+    }
+
+    #[test]
+    fn test_stolen_items_are_blocked_and_frozen() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin = mock_info("admin", &[]);
+        let reporter = mock_info("compliance", &[]);
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin.clone(), instantiate_msg).unwrap();
+        execute(deps.as_mut(), env.clone(), admin.clone(), ExecuteMsg::AddReporter { address: "compliance".to_string() }).unwrap();
+        let is_reporter: bool = from_json(query(deps.as_ref(), env.clone(), QueryMsg::IsReporter { address: "compliance".to_string() }).unwrap()).unwrap();
+        assert!(is_reporter);
+
+        let bar = ItemRef::Serial { refiner: "Valcambi".to_string(), serial: "A123456".to_string() };
+        let coin = ItemRef::Cert { service: GradingService::Ngc, cert_number: "6123456-001".to_string() };
+        let flag = |item: &ItemRef| ExecuteMsg::FlagStolen {
+            item: item.clone(),
+            reason: "Police report 2026-1182".to_string(),
+        };
+        let list_bar = ExecuteMsg::CreateAuction {
+            item_id: "valcambi-kilo".to_string(),
+            metadata: ListingMetadata {
+                mint: Some("Valcambi".to_string()),
+                serial: Some("A123456".to_string()),
+                ..gold_bar()
+            },
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        };
+        let status = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, id| {
+            let res: AuctionResponse = from_json(query(deps.as_ref(), mock_env(), QueryMsg::Auction { id }).unwrap()).unwrap();
+            res.auction.status
+        };
+
+        // Only reporters flag items
+        let err = execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), flag(&bar)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(deps.as_mut(), env.clone(), reporter.clone(), ExecuteMsg::FlagStolen {
+            item: bar.clone(),
+            reason: " ".to_string(),
+        }).unwrap_err();
+        assert_eq!(err, ContractError::InvalidReason { max: 256 });
+
+        // Flagging a bar mid-auction freezes it with the high bid still locked
+        execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), list_bar.clone()).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(200, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+        let res = execute(deps.as_mut(), env.clone(), reporter.clone(), flag(&bar)).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "frozen_auction" && attr.value == "0"));
+        assert!(res.attributes.iter().any(|attr| attr.key == "item" && attr.value == "serial:VALCAMBI/A123456"));
+        assert_eq!(status(&deps, 0), AuctionStatus::Frozen);
+
+        let err = execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(300, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap_err();
+        assert_eq!(err, ContractError::AuctionNotActive {});
+        let err = execute(deps.as_mut(), env.clone(), mock_info("fence", &[]), list_bar.clone()).unwrap_err();
+        assert_eq!(err, ContractError::ReportedStolen {});
+        let err = execute(deps.as_mut(), env.clone(), reporter.clone(), flag(&bar)).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyFlagged {});
+        
+        // Retyping the serial with other casing or spacing does not get around the flag
+        let relisted = ExecuteMsg::CreateAuction {
+            item_id: "valcambi-kilo".to_string(),
+            metadata: ListingMetadata {
+                mint: Some("VALCAMBI".to_string()),
+                serial: Some("a 123456".to_string()),
+                ..gold_bar()
+            },
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("fence", &[]), relisted).unwrap_err();
+        assert_eq!(err, ContractError::ReportedStolen {});
+        let lowercase = ItemRef::Serial { refiner: "valcambi".to_string(), serial: "a123456".to_string() };
+        let err = execute(deps.as_mut(), env.clone(), reporter.clone(), flag(&lowercase)).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyFlagged {});
+
+        // A misreport is cleared, but the listing only resumes on the arbitrator's ruling
+        execute(deps.as_mut(), env.clone(), reporter.clone(), ExecuteMsg::UnflagStolen {
+            item: bar.clone(),
+            reason: "Serial misread in the report".to_string(),
+        }).unwrap();
+        assert_eq!(status(&deps, 0), AuctionStatus::Frozen);
+        execute(deps.as_mut(), env.clone(), admin.clone(), ExecuteMsg::ResolveFrozen { auction_id: 0, cancel: false }).unwrap();
+        assert_eq!(status(&deps, 0), AuctionStatus::Active);
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(300, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+
+        // A sold coin reported stolen before delivery is refunded to the buyer
+        execute(deps.as_mut(), env.clone(), mock_info("seller", &[]), ExecuteMsg::CreateAuction {
+            item_id: "saint-gaudens".to_string(),
+            metadata: ListingMetadata {
+                form: ProductForm::Coin,
+                weight: Weight::new(Decimal::one(), WeightUnit::TroyOunce),
+                fineness: 9000,
+                mint: Some("US Mint".to_string()),
+                year: Some(1927),
+                certification: Some(Certification {
+                    service: GradingService::Ngc,
+                    cert_number: "6123456-001".to_string(),
+                    grade: 65,
+                    designation: None,
+                }),
+                ..gold_bar()
+            },
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        }).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("carol", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 1, insurance: None }).unwrap();
+        execute(deps.as_mut(), env.clone(), reporter, flag(&coin)).unwrap();
+        assert_eq!(status(&deps, 1), AuctionStatus::Frozen);
+        let err = execute(deps.as_mut(), env.clone(), mock_info("carol", &[]), ExecuteMsg::ReleaseFunds { auction_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::AuctionNotActive {});
+
+        let err = execute(deps.as_mut(), env.clone(), mock_info("carol", &[]), ExecuteMsg::ResolveFrozen { auction_id: 1, cancel: true }).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env.clone(), admin.clone(), ExecuteMsg::ResolveFrozen { auction_id: 1, cancel: true }).unwrap();
+        assert_eq!(status(&deps, 1), AuctionStatus::Refunded);
+        let balance: BalanceResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: "carol".to_string() }).unwrap()).unwrap();
+        assert_eq!(balance.balances, coins(500, "utestcore"));
+        let err = execute(deps.as_mut(), env.clone(), admin, ExecuteMsg::ResolveFrozen { auction_id: 1, cancel: true }).unwrap_err();
+        assert_eq!(err, ContractError::NotFrozen {});
+
+        // Current flags and the full audit trail are queryable, by canonical item
+        let typed = ItemRef::Cert { service: GradingService::Ngc, cert_number: " 6123456-001".to_string() };
+        let res: StolenReportResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::StolenReport { item: typed }).unwrap()).unwrap();
+        assert_eq!(res.item, coin);
+        assert_eq!(res.report.unwrap().reporter, Addr::unchecked("compliance"));
+        let res: StolenReportResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::StolenReport { item: bar.clone() }).unwrap()).unwrap();
+        assert_eq!(res.report, None);
+        let bar = bar.canonical();
+
+        let res: FlagHistoryResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::FlagHistory { start_after: None, limit: None }).unwrap()).unwrap();
+        let trail = res.events.iter()
+            .map(|event| (event.item.clone(), event.flagged, event.frozen_auction))
+            .collect::<Vec<_>>();
+        assert_eq!(trail, vec![(bar.clone(), true, Some(0)), (bar, false, None), (coin, true, Some(1))]);
+        let res: FlagHistoryResponse = from_json(query(deps.as_ref(), env, QueryMsg::FlagHistory { start_after: Some(0), limit: Some(1) }).unwrap()).unwrap();
+        assert_eq!(res.events.len(), 1);
+        assert_eq!(res.events[0].id, 1);
+    }
+
+    #[test]
+    fn test_full_listing_leaves_room_for_later_documents() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let seller = mock_info("seller", &[]);
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        execute(deps.as_mut(), env.clone(), seller.clone(), ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        }).unwrap();
+        let attach = |kind: DocumentKind, n: u32| ExecuteMsg::AttachDocument {
+            auction_id: 0,
+            kind,
+            hash: ContentHash::Sha256(format!("{:064x}", n)),
+        };
+
+        // The seller fills the listing allowance
+        for n in 0..20 {
+            execute(deps.as_mut(), env.clone(), seller.clone(), attach(DocumentKind::Photo, n)).unwrap();
+        }
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), attach(DocumentKind::Photo, 20)).unwrap_err();
+        assert_eq!(err, ContractError::TooManyDocuments { max: 20 });
+
+        // After the sale the shipping label and the buyer's documents still fit
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 0, insurance: None }).unwrap();
+        execute(deps.as_mut(), env.clone(), seller, attach(DocumentKind::ShippingLabel, 21)).unwrap();
+        for n in 22..32 {
+            execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), attach(DocumentKind::Photo, n)).unwrap();
+        }
+        let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), attach(DocumentKind::Photo, 32)).unwrap_err();
+        assert_eq!(err, ContractError::TooManyDocuments { max: 10 });
+
+        let res: DocumentsResponse = from_json(query(deps.as_ref(), env, QueryMsg::Documents { auction_id: 0 }).unwrap()).unwrap();
+        assert_eq!(res.documents.len(), 31);
+    }
+
+    #[test]
+    fn test_document_attachments_follow_the_lifecycle() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let seller = mock_info("seller", &[]);
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        execute(deps.as_mut(), env.clone(), seller.clone(), ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: None,
+            duration_hours: 24,
+            inspector: None,
+        }).unwrap();
+
+        let sha256 = |byte: char| ContentHash::Sha256(byte.to_string().repeat(64));
+        let attach = |kind: DocumentKind, hash: ContentHash| ExecuteMsg::AttachDocument {
+            auction_id: 0,
+            kind,
+            hash,
+        };
+
+        // Before bidding the seller curates the listing documents
+        execute(deps.as_mut(), env.clone(), seller.clone(), attach(DocumentKind::Photo, sha256('a'))).unwrap();
+        execute(deps.as_mut(), env.clone(), seller.clone(), attach(
+            DocumentKind::AssayCertificate,
+            ContentHash::Cid("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string()),
+        )).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), mock_info("stranger", &[]), attach(DocumentKind::Photo, sha256('b'))).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), attach(DocumentKind::ShippingLabel, sha256('b'))).unwrap_err();
+        assert_eq!(err, ContractError::DocumentNotAllowed {});
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), attach(DocumentKind::Photo, sha256('g'))).unwrap_err();
+        assert_eq!(err, ContractError::Metadata(MetalsError::InvalidContentHash));
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), attach(DocumentKind::Invoice, sha256('a'))).unwrap_err();
+        assert_eq!(err, ContractError::DuplicateDocument {});
+
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), ExecuteMsg::RemoveDocument { auction_id: 0, index: 2 }).unwrap_err();
+        assert_eq!(err, ContractError::DocumentNotFound {});
+        execute(deps.as_mut(), env.clone(), seller.clone(), ExecuteMsg::RemoveDocument { auction_id: 0, index: 0 }).unwrap();
+        execute(deps.as_mut(), env.clone(), seller.clone(), attach(DocumentKind::Photo, sha256('c'))).unwrap();
+
+        // The first bid locks what was promised
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(200, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), attach(DocumentKind::Photo, sha256('d'))).unwrap_err();
+        assert_eq!(err, ContractError::DocumentsLocked {});
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), ExecuteMsg::RemoveDocument { auction_id: 0, index: 0 }).unwrap_err();
+        assert_eq!(err, ContractError::DocumentsLocked {});
+
+        // Once sold the seller adds the shipping label and the buyer documents what arrived
+        env.block.time = env.block.time.plus_seconds(25 * 3600);
+        execute(deps.as_mut(), env.clone(), seller.clone(), ExecuteMsg::EndAuction { auction_id: 0 }).unwrap();
+        execute(deps.as_mut(), env.clone(), seller, attach(DocumentKind::ShippingLabel, sha256('e'))).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), attach(DocumentKind::Invoice, sha256('f'))).unwrap_err();
+        assert_eq!(err, ContractError::DocumentNotAllowed {});
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), attach(DocumentKind::Photo, sha256('f'))).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), attach(DocumentKind::AssayCertificate, sha256('1'))).unwrap_err();
+        assert_eq!(err, ContractError::DocumentNotAllowed {});
+
+        // The arbitrator adds evidence during a dispute
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::OpenDispute { auction_id: 0 }).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), attach(DocumentKind::AssayCertificate, sha256('1'))).unwrap();
+
+        let res: DocumentsResponse = from_json(query(deps.as_ref(), env, QueryMsg::Documents { auction_id: 0 }).unwrap()).unwrap();
+        let trail = res.documents.iter()
+            .map(|document| (document.kind, document.role, document.stage.clone(), document.uploader.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(trail, vec![
+            (DocumentKind::AssayCertificate, DocumentRole::Seller, AuctionStatus::Active, "seller"),
+            (DocumentKind::Photo, DocumentRole::Seller, AuctionStatus::Active, "seller"),
+            (DocumentKind::ShippingLabel, DocumentRole::Seller, AuctionStatus::Sold, "seller"),
+            (DocumentKind::Photo, DocumentRole::Buyer, AuctionStatus::Sold, "alice"),
+            (DocumentKind::AssayCertificate, DocumentRole::Arbitrator, AuctionStatus::Disputed, "admin"),
+        ]);
+    }
+
+    #[test]
+    fn test_inspection_gates_release() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin = mock_info("admin", &[]);
+        let seller = mock_info("seller", &[]);
+        let assayer = mock_info("assayer", &[]);
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin.clone(), instantiate_msg).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), ExecuteMsg::AddInspector { address: "assayer".to_string() }).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env.clone(), admin, ExecuteMsg::AddInspector { address: "assayer".to_string() }).unwrap();
+        let is_inspector: bool = from_json(query(deps.as_ref(), env.clone(), QueryMsg::IsInspector { address: "assayer".to_string() }).unwrap()).unwrap();
+        assert!(is_inspector);
+
+        let list = |inspector: Option<&str>| ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: inspector.map(|inspector| inspector.to_string()),
+        };
+        let inspect = |auction_id: u64, passed: bool, measured_fineness: u16, report: char| ExecuteMsg::RecordInspection {
+            auction_id,
+            passed,
+            measured_weight: Weight::new(Decimal::one(), WeightUnit::Kilogram),
+            measured_fineness,
+            report: ContentHash::Sha256(report.to_string().repeat(64)),
+        };
+        let auction = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, id| {
+            let res: AuctionResponse = from_json(query(deps.as_ref(), mock_env(), QueryMsg::Auction { id }).unwrap()).unwrap();
+            res.auction
+        };
+
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), list(Some("nobody"))).unwrap_err();
+        assert_eq!(err, ContractError::UnknownInspector {});
+
+        // The listing requires inspection: the sale waits on the inspector
+        execute(deps.as_mut(), env.clone(), seller.clone(), list(Some("assayer"))).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 0, insurance: None }).unwrap();
+        assert_eq!(auction(&deps, 0).status, AuctionStatus::AwaitingInspection);
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::ReleaseFunds { auction_id: 0 }).unwrap_err();
+        assert_eq!(err, ContractError::InspectionPending {});
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), inspect(0, true, 9999, 'a')).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(deps.as_mut(), env.clone(), assayer.clone(), inspect(0, true, 0, 'a')).unwrap_err();
+        assert_eq!(err, ContractError::Metadata(MetalsError::InvalidFineness { fineness: 0 }));
+
+        // A pass records the measurements and report, then the buyer can release
+        execute(deps.as_mut(), env.clone(), assayer.clone(), inspect(0, true, 9999, 'a')).unwrap();
+        let inspected = auction(&deps, 0);
+        assert_eq!(inspected.status, AuctionStatus::Sold);
+        let result = inspected.inspection.unwrap().result.unwrap();
+        assert!(result.passed);
+        assert_eq!(result.measured_fineness, 9999);
+        let documents: DocumentsResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Documents { auction_id: 0 }).unwrap()).unwrap();
+        assert_eq!(documents.documents[0].role, DocumentRole::Inspector);
+        assert_eq!(documents.documents[0].hash, result.report);
+
+        let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::RequireInspection {
+            auction_id: 0,
+            inspector: "assayer".to_string(),
+        }).unwrap_err();
+        assert_eq!(err, ContractError::InspectionAlreadyRequired {});
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::ReleaseFunds { auction_id: 0 }).unwrap();
+        assert_eq!(auction(&deps, 0).status, AuctionStatus::Completed);
+
+        // The buyer opts into inspection after the sale; a failure refunds them
+        execute(deps.as_mut(), env.clone(), seller.clone(), list(None)).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 1, insurance: None }).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), seller.clone(), ExecuteMsg::RequireInspection {
+            auction_id: 1,
+            inspector: "assayer".to_string(),
+        }).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), ExecuteMsg::RequireInspection {
+            auction_id: 1,
+            inspector: "assayer".to_string(),
+        }).unwrap();
+        assert_eq!(auction(&deps, 1).status, AuctionStatus::AwaitingInspection);
+
+        // Tungsten-cored bar
+        execute(deps.as_mut(), env.clone(), assayer.clone(), inspect(1, false, 5800, 'b')).unwrap();
+        assert_eq!(auction(&deps, 1).status, AuctionStatus::Refunded);
+        let balance: BalanceResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: "bob".to_string() }).unwrap()).unwrap();
+        assert_eq!(balance.balances, coins(500, "utestcore"));
+
+        // A lot stuck with the inspector goes to the arbitrator instead of refunding outright
+        execute(deps.as_mut(), env.clone(), seller, list(Some("assayer"))).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("carol", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 2, insurance: None }).unwrap();
+        let mut late = env.clone();
+        late.block.time = late.block.time.plus_seconds(15 * 24 * 3600);
+        let res = execute(deps.as_mut(), late.clone(), mock_info("carol", &[]), ExecuteMsg::ClaimNonDelivery { auction_id: 2 }).unwrap();
+        assert_eq!(res.attributes.last().unwrap().value, "true");
+        assert_eq!(auction(&deps, 2).status, AuctionStatus::Disputed);
+        let balance: BalanceResponse = from_json(query(deps.as_ref(), late.clone(), QueryMsg::Balance { address: "carol".to_string() }).unwrap()).unwrap();
+        assert!(balance.balances.is_empty());
+        let err = execute(deps.as_mut(), late.clone(), assayer, inspect(2, true, 9999, 'c')).unwrap_err();
+        assert_eq!(err, ContractError::AuctionNotActive {});
+        execute(deps.as_mut(), late.clone(), mock_info("admin", &[]), ExecuteMsg::ResolveDispute { auction_id: 2, refund_buyer: true }).unwrap();
+        let balance: BalanceResponse = from_json(query(deps.as_ref(), late, QueryMsg::Balance { address: "carol".to_string() }).unwrap()).unwrap();
+        assert_eq!(balance.balances, coins(500, "utestcore"));
+    }
+
     #[test]
     fn test_place_bid() {
         let mut deps = mock_dependencies();
         let env = mock_env();
-        let creator_info = mock_info("creator", &coins(1000, "utestcore"));
+        let creator_info = mock_info("creator", &[]);
         
         // Instantiate
         let instantiate_msg = InstantiateMsg {
             admin: "admin".to_string(),
-            fee_percentage: 1,
+            fee_bps: 110,
             fee_address: "fee_collector".to_string(),
             require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
         };
         instantiate(deps.as_mut(), env.clone(), creator_info.clone(), instantiate_msg).unwrap();
         
         // Create auction
         let create_msg = ExecuteMsg::CreateAuction {
             item_id: "item123".to_string(),
+            metadata: gold_bar(),
             starting_price: Uint128::from(100u128),
             reserve_price: Some(Uint128::from(200u128)),
             buy_now_price: Some(Uint128::from(500u128)),
             duration_hours: 24,
+            inspector: None,
         };
         execute(deps.as_mut(), env.clone(), creator_info, create_msg).unwrap();
         
@@ -114,30 +920,37 @@ mod tests {
     fn test_buy_now() {
         let mut deps = mock_dependencies();
         let env = mock_env();
-        let creator_info = mock_info("creator", &coins(1000, "utestcore"));
+        let creator_info = mock_info("creator", &[]);
         
         // Instantiate
         let instantiate_msg = InstantiateMsg {
             admin: "admin".to_string(),
-            fee_percentage: 1,
+            fee_bps: 110,
             fee_address: "fee_collector".to_string(),
             require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
         };
         instantiate(deps.as_mut(), env.clone(), creator_info.clone(), instantiate_msg).unwrap();
         
         // Create auction with buy now price
         let create_msg = ExecuteMsg::CreateAuction {
             item_id: "item123".to_string(),
+            metadata: gold_bar(),
             starting_price: Uint128::from(100u128),
             reserve_price: Some(Uint128::from(200u128)),
             buy_now_price: Some(Uint128::from(500u128)),
             duration_hours: 24,
+            inspector: None,
         };
         execute(deps.as_mut(), env.clone(), creator_info, create_msg).unwrap();
         
         // Buy now
         let buyer_info = mock_info("buyer", &coins(500, "utestcore"));
-        let buy_msg = ExecuteMsg::BuyNow { auction_id: 0 };
+        let buy_msg = ExecuteMsg::BuyNow { auction_id: 0, insurance: None };
         
         let res = execute(deps.as_mut(), env.clone(), buyer_info, buy_msg).unwrap();
         assert_eq!(0, res.messages.len());
@@ -160,9 +973,14 @@ mod tests {
         // Instantiate with KYC required
         let instantiate_msg = InstantiateMsg {
             admin: "admin".to_string(),
-            fee_percentage: 1,
+            fee_bps: 110,
             fee_address: "fee_collector".to_string(),
             require_kyc: Some(true),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
         };
         instantiate(deps.as_mut(), env.clone(), admin_info.clone(), instantiate_msg).unwrap();
         
@@ -182,4 +1000,723 @@ mod tests {
         
         assert_eq!(is_verified, true);
     }
+
+
+    #[test]
+    fn test_seller_bond_required_and_returned() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+        
+        // Instantiate with a seller bond
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: Some(BondConfig {
+                amount: Uint128::from(50u128),
+                verified_amount: None,
+                waive_after_sales: None,
+                slash_bps: 5000,
+                buyer_share_bps: 5000,
+            }),
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info, instantiate_msg).unwrap();
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        };
+        
+        // Listing without the bond fails
+        let err = execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), create_msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::BondRequired { required: Uint128::from(50u128) });
+        
+        // Listing with the bond succeeds
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &coins(50, "utestcore")), create_msg).unwrap();
+        let bond: SellerBondResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::SellerBond { auction_id: 0 }).unwrap()).unwrap();
+        assert_eq!(bond.bond.unwrap().status, BondStatus::Held);
+        
+        // Buyer buys and confirms delivery
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 0, insurance: None }).unwrap();
+        let res = execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), ExecuteMsg::ReleaseFunds { auction_id: 0 }).unwrap();
+        
+        // Sale proceeds less the 1.1% fee and the bond are credited to the seller
+        assert_eq!(res.messages.len(), 0);
+        let balance: BalanceResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: "creator".to_string() }).unwrap()).unwrap();
+        assert_eq!(balance.balances, coins(544, "utestcore"));
+        let balance: BalanceResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: "fee_collector".to_string() }).unwrap()).unwrap();
+        assert_eq!(balance.balances, coins(6, "utestcore"));
+        
+        // Funds can only be released once
+        let err = execute(deps.as_mut(), env, mock_info("buyer", &[]), ExecuteMsg::ReleaseFunds { auction_id: 0 }).unwrap_err();
+        assert_eq!(err, ContractError::AuctionNotActive {});
+    }
+
+    #[test]
+    fn test_non_delivery_slashes_bond() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+        
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: Some(48),
+            seller_bond: Some(BondConfig {
+                amount: Uint128::from(100u128),
+                verified_amount: None,
+                waive_after_sales: None,
+                slash_bps: 5000,
+                buyer_share_bps: 6000,
+            }),
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info, instantiate_msg).unwrap();
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &coins(100, "utestcore")), create_msg).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 0, insurance: None }).unwrap();
+        
+        // Too early to claim
+        let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), ExecuteMsg::ClaimNonDelivery { auction_id: 0 }).unwrap_err();
+        assert_eq!(err, ContractError::DeliveryWindowOpen {});
+        
+        // Only the buyer can claim
+        env.block.time = env.block.time.plus_seconds(48 * 3600 + 1);
+        let err = execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), ExecuteMsg::ClaimNonDelivery { auction_id: 0 }).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), ExecuteMsg::ClaimNonDelivery { auction_id: 0 }).unwrap();
+        
+        // Refund plus the buyer's 60% of the slashed half, fee_address gets 40%, seller keeps the other half
+        for (address, amount) in [("buyer", 530u128), ("fee_collector", 20), ("creator", 50)] {
+            let balance: BalanceResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: address.to_string() }).unwrap()).unwrap();
+            assert_eq!(balance.balances, coins(amount, "utestcore"));
+        }
+        
+        let bond: SellerBondResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::SellerBond { auction_id: 0 }).unwrap()).unwrap();
+        assert_eq!(bond.bond.unwrap().status, BondStatus::Slashed { slashed: Uint128::from(50u128) });
+        
+        let auction: AuctionResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Auction { id: 0 }).unwrap()).unwrap();
+        assert_eq!(auction.auction.status, phoenix_escrow::state::AuctionStatus::Refunded);
+        
+        // With an insurance pool configured its share is deposited there instead
+        let update = ExecuteMsg::UpdateInsurancePool { insurance_pool: Some("pool".to_string()) };
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update).unwrap();
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item456".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &coins(100, "utestcore")), create_msg).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 1, insurance: None }).unwrap();
+        env.block.time = env.block.time.plus_seconds(48 * 3600 + 1);
+        let res = execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), ExecuteMsg::ClaimNonDelivery { auction_id: 1 }).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+                assert_eq!(contract_addr, "pool");
+                assert_eq!(funds, &coins(20, "utestcore"));
+                let deposit: PoolExecuteMsg = from_json(msg).unwrap();
+                assert_eq!(deposit, PoolExecuteMsg::DepositFee { auction_id: 1 });
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        let balance: BalanceResponse = from_json(query(deps.as_ref(), env, QueryMsg::Balance { address: "fee_collector".to_string() }).unwrap()).unwrap();
+        assert_eq!(balance.balances, coins(20, "utestcore"));
+    }
+
+    #[test]
+    fn test_dispute_resolution_and_bond_waiver() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+        
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: Some(BondConfig {
+                amount: Uint128::from(100u128),
+                verified_amount: Some(Uint128::from(40u128)),
+                waive_after_sales: Some(1),
+                slash_bps: 10000,
+                buyer_share_bps: 10000,
+            }),
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), instantiate_msg).unwrap();
+        
+        // Verified sellers post the reduced bond
+        execute(deps.as_mut(), env.clone(), admin_info.clone(), ExecuteMsg::VerifyUser { address: "creator".to_string() }).unwrap();
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &coins(40, "utestcore")), create_msg.clone()).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 0, insurance: None }).unwrap();
+        
+        // Buyer disputes, arbitrator sides with the seller
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), ExecuteMsg::OpenDispute { auction_id: 0 }).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), ExecuteMsg::ResolveDispute { auction_id: 0, refund_buyer: true }).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env.clone(), admin_info, ExecuteMsg::ResolveDispute { auction_id: 0, refund_buyer: false }).unwrap();
+        let balance: BalanceResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: "creator".to_string() }).unwrap()).unwrap();
+        assert_eq!(balance.balances, coins(534, "utestcore"));
+        
+        // One completed sale waives the bond for the next listing
+        let res = execute(deps.as_mut(), env, mock_info("creator", &[]), create_msg).unwrap();
+        assert!(res.attributes.iter().any(|a| a.key == "bond" && a.value == "0"));
+    }
+
+    #[test]
+    fn test_outbid_refund_and_withdraw() {
+        // Contract holds both bids
+        let mut deps = mock_dependencies_with_balance(&coins(350, "utestcore"));
+        let env = mock_env();
+        
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: None,
+            duration_hours: 24,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), create_msg).unwrap();
+        
+        // Coins in other denoms would never reach the ledger
+        let mut mixed = coins(150, "utestcore");
+        mixed.extend(coins(5, "uatom"));
+        let err = execute(deps.as_mut(), env.clone(), mock_info("bidder1", &mixed), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap_err();
+        assert_eq!(err, ContractError::InvalidDenom {});
+        
+        execute(deps.as_mut(), env.clone(), mock_info("bidder1", &coins(150, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("bidder2", &coins(200, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+        
+        // Outbid bidder is credited, not paid directly
+        let balance: BalanceResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: "bidder1".to_string() }).unwrap()).unwrap();
+        assert_eq!(balance.balances, coins(150, "utestcore"));
+        
+        // Cannot withdraw more than the ledger balance
+        let err = execute(deps.as_mut(), env.clone(), mock_info("bidder1", &[]), ExecuteMsg::Withdraw { denom: "utestcore".to_string(), amount: Uint128::from(151u128) }).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds {});
+        
+        let res = execute(deps.as_mut(), env.clone(), mock_info("bidder1", &[]), ExecuteMsg::Withdraw { denom: "utestcore".to_string(), amount: Uint128::from(150u128) }).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "bidder1".to_string(),
+            amount: coins(150, "utestcore"),
+        }));
+        
+        let balance: BalanceResponse = from_json(&query(deps.as_ref(), env, QueryMsg::Balance { address: "bidder1".to_string() }).unwrap()).unwrap();
+        assert!(balance.balances.is_empty());
+    }
+
+    #[test]
+    fn test_withdraw_refused_when_insolvent() {
+        // Bank balance is short of what the ledger owes
+        let mut deps = mock_dependencies_with_balance(&coins(100, "utestcore"));
+        let env = mock_env();
+        
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: None,
+            duration_hours: 24,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), create_msg).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("bidder1", &coins(150, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("bidder2", &coins(200, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+        
+        let err = execute(deps.as_mut(), env, mock_info("bidder1", &[]), ExecuteMsg::Withdraw { denom: "utestcore".to_string(), amount: Uint128::from(150u128) }).unwrap_err();
+        assert_eq!(err, ContractError::Insolvent { denom: "utestcore".to_string() });
+    }
+
+    #[test]
+    fn test_deposit_backed_bidding() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: None,
+            duration_hours: 24,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), create_msg).unwrap();
+        
+        // Deposit once, then bid without attaching funds
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(300, "utestcore")), ExecuteMsg::Deposit {}).unwrap();
+        let bid = |amount: u128| ExecuteMsg::PlaceBidFromDeposit { auction_id: 0, amount: Uint128::from(amount) };
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), bid(150)).unwrap();
+        
+        let account: BidderAccountResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::BidderAccount { address: "alice".to_string() }).unwrap()).unwrap();
+        assert_eq!(account.available, Uint128::from(150u128));
+        assert_eq!(account.locked, Uint128::from(150u128));
+        assert_eq!(account.locked_bids[0].auction_id, 0);
+        
+        // Being outbid unlocks the funds
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(200, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+        let account: BidderAccountResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::BidderAccount { address: "alice".to_string() }).unwrap()).unwrap();
+        assert_eq!(account.available, Uint128::from(300u128));
+        assert!(account.locked_bids.is_empty());
+        
+        // Re-bid, then raise her own standing bid
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), bid(250)).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), bid(300)).unwrap();
+        let account: BidderAccountResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::BidderAccount { address: "alice".to_string() }).unwrap()).unwrap();
+        assert_eq!(account.available, Uint128::zero());
+        assert_eq!(account.locked, Uint128::from(300u128));
+        
+        // Bids beyond the deposit fail
+        let err = execute(deps.as_mut(), env.clone(), mock_info("carol", &[]), bid(400)).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds {});
+        
+        // Attaching funds to a deposit-backed bid is rejected
+        let err = execute(deps.as_mut(), env, mock_info("alice", &coins(10, "utestcore")), bid(400)).unwrap_err();
+        assert_eq!(err, ContractError::UnexpectedFunds {});
+    }
+
+    #[test]
+    fn test_solvency_report() {
+        // Contract is 50 short of what it owes
+        let mut deps = mock_dependencies_with_balance(&coins(400, "utestcore"));
+        let mut env = mock_env();
+        
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: Some(1),
+            seller_bond: Some(BondConfig {
+                amount: Uint128::from(100u128),
+                verified_amount: None,
+                waive_after_sales: None,
+                slash_bps: 5000,
+                buyer_share_bps: 0,
+            }),
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: None,
+            duration_hours: 1,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &coins(100, "utestcore")), create_msg).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &coins(150, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(200, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+        
+        let report: SolvencyResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Solvency {}).unwrap()).unwrap();
+        let usd = &report.denoms[0];
+        assert_eq!(usd.open_bids, Uint128::from(200u128));
+        assert_eq!(usd.bonds_held, Uint128::from(100u128));
+        assert_eq!(usd.withdrawable, Uint128::from(150u128));
+        assert_eq!(usd.liabilities, Uint128::from(450u128));
+        assert_eq!(usd.shortfall, Uint128::from(50u128));
+        assert!(!report.solvent);
+        
+        // Winning bid moves to awaiting release
+        env.block.time = env.block.time.plus_seconds(3601);
+        execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::EndAuction { auction_id: 0 }).unwrap();
+        let report: SolvencyResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Solvency {}).unwrap()).unwrap();
+        assert_eq!(report.denoms[0].open_bids, Uint128::zero());
+        assert_eq!(report.denoms[0].awaiting_release, Uint128::from(200u128));
+        
+        // Non-delivery refunds the buyer and sends half the bond to the pool as unpaid fees
+        env.block.time = env.block.time.plus_seconds(3601);
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), ExecuteMsg::ClaimNonDelivery { auction_id: 0 }).unwrap();
+        let report: SolvencyResponse = from_json(&query(deps.as_ref(), env, QueryMsg::Solvency {}).unwrap()).unwrap();
+        let usd = &report.denoms[0];
+        assert_eq!(usd.awaiting_release, Uint128::zero());
+        assert_eq!(usd.bonds_held, Uint128::zero());
+        assert_eq!(usd.unpaid_fees, Uint128::from(50u128));
+        assert_eq!(usd.withdrawable, Uint128::from(400u128));
+        assert_eq!(usd.liabilities, Uint128::from(450u128));
+        assert_eq!(usd.shortfall, Uint128::from(50u128));
+    }
+
+    // Insurance pool quoting a 10 premium, with or without capacity
+    fn mock_pool_quote(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, available: bool) {
+        let quote = to_json_binary(&QuoteResponse {
+            declared_value: Uint128::from(500u128),
+            tier: RiskTier::Standard,
+            premium: Uint128::from(10u128),
+            capacity: Uint128::from(if available { 1000u128 } else { 0u128 }),
+            available,
+        }).unwrap();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "pool" => {
+                SystemResult::Ok(ContractResult::Ok(quote.clone()))
+            }
+            _ => panic!("unexpected query"),
+        });
+    }
+    
+    fn coverage() -> Option<CoverageRequest> {
+        Some(CoverageRequest { declared_value: Uint128::from(500u128), tier: RiskTier::Standard })
+    }
+
+    #[test]
+    fn test_buy_now_with_insurance() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: Some("pool".to_string()),
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        mock_pool_quote(&mut deps, true);
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), create_msg).unwrap();
+        
+        // Price plus premium must be attached
+        let buy_msg = ExecuteMsg::BuyNow { auction_id: 0, insurance: coverage() };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(505, "utestcore")), buy_msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientFunds {});
+        
+        // Cover cannot exceed the buy-now price
+        let insurance = Some(CoverageRequest { declared_value: Uint128::from(501u128), tier: RiskTier::Standard });
+        let overvalued = ExecuteMsg::BuyNow { auction_id: 0, insurance };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(520, "utestcore")), overvalued).unwrap_err();
+        assert_eq!(err, ContractError::DeclaredValueTooHigh {});
+        
+        let res = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(520, "utestcore")), buy_msg).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+                assert_eq!(contract_addr, "pool");
+                assert_eq!(funds, &coins(10, "utestcore"));
+                let purchase: PoolExecuteMsg = from_json(msg).unwrap();
+                assert_eq!(purchase, PoolExecuteMsg::PurchaseCoverage {
+                    auction_id: 0,
+                    buyer: "buyer".to_string(),
+                    declared_value: Uint128::from(500u128),
+                    tier: RiskTier::Standard,
+                });
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        
+        // Overpayment beyond price and premium is credited back
+        let balance: BalanceResponse = from_json(&query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: "buyer".to_string() }).unwrap()).unwrap();
+        assert_eq!(balance.balances, coins(10, "utestcore"));
+        
+        // The sale fee is deposited with the pool rather than credited to fee_address
+        let release = ExecuteMsg::ReleaseFunds { auction_id: 0 };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), release).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+                assert_eq!(contract_addr, "pool");
+                assert_eq!(funds, &coins(6, "utestcore"));
+                let deposit: PoolExecuteMsg = from_json(msg).unwrap();
+                assert_eq!(deposit, PoolExecuteMsg::DepositFee { auction_id: 0 });
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        let balance: BalanceResponse = from_json(query(deps.as_ref(), env, QueryMsg::Balance { address: "fee_collector".to_string() }).unwrap()).unwrap();
+        assert!(balance.balances.is_empty());
+    }
+
+    #[test]
+    fn test_insurance_refused_and_won_lot_insured() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 1,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), create_msg).unwrap();
+        
+        // No pool configured
+        let buy_msg = ExecuteMsg::BuyNow { auction_id: 0, insurance: coverage() };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(510, "utestcore")), buy_msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::InsuranceUnavailable {});
+        
+        // Pool without capacity
+        let update = ExecuteMsg::UpdateInsurancePool { insurance_pool: Some("pool".to_string()) };
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update).unwrap();
+        mock_pool_quote(&mut deps, false);
+        let err = execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(510, "utestcore")), buy_msg).unwrap_err();
+        assert_eq!(err, ContractError::CoverageUnavailable {});
+        
+        // Winning bidder insures the lot after the auction ends
+        execute(deps.as_mut(), env.clone(), mock_info("bidder", &coins(300, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+        env.block.time = env.block.time.plus_seconds(3601);
+        execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::EndAuction { auction_id: 0 }).unwrap();
+        mock_pool_quote(&mut deps, true);
+        
+        let insure = ExecuteMsg::InsureOrder { auction_id: 0, insurance: coverage().unwrap() };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("creator", &coins(10, "utestcore")), insure.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        
+        // Cover cannot exceed the winning bid
+        let err = execute(deps.as_mut(), env.clone(), mock_info("bidder", &coins(10, "utestcore")), insure).unwrap_err();
+        assert_eq!(err, ContractError::DeclaredValueTooHigh {});
+        let insurance = CoverageRequest { declared_value: Uint128::from(300u128), tier: RiskTier::Standard };
+        let insure = ExecuteMsg::InsureOrder { auction_id: 0, insurance };
+        
+        let res = execute(deps.as_mut(), env, mock_info("bidder", &coins(10, "utestcore")), insure).unwrap();
+        assert_eq!(res.messages.len(), 1);
+    }
+
+    #[test]
+    fn test_settlement_reads_fee_from_registry() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+        
+        let update = ExecuteMsg::UpdateRegistry { registry: Some("registry".to_string()) };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), update.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update).unwrap();
+        let config: ConfigResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(config.registry, Some(Addr::unchecked("registry")));
+        
+        let create_msg = ExecuteMsg::CreateAuction {
+            item_id: "item123".to_string(),
+            metadata: gold_bar(),
+            starting_price: Uint128::from(100u128),
+            reserve_price: None,
+            buy_now_price: Some(Uint128::from(500u128)),
+            duration_hours: 24,
+            inspector: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), create_msg).unwrap();
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &coins(500, "utestcore")), ExecuteMsg::BuyNow { auction_id: 0, insurance: None }).unwrap();
+        
+        // The registry raised the fee to 2% after the sale
+        let fees = to_json_binary(&FeeSchedule::with_rate(200)).unwrap();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "registry" => {
+                let msg: RegistryQueryMsg = from_json(msg).unwrap();
+                assert_eq!(msg, RegistryQueryMsg::GetFeeSchedule {});
+                SystemResult::Ok(ContractResult::Ok(fees.clone()))
+            }
+            _ => panic!("unexpected query"),
+        });
+        execute(deps.as_mut(), env.clone(), mock_info("buyer", &[]), ExecuteMsg::ReleaseFunds { auction_id: 0 }).unwrap();
+        
+        let seller: BalanceResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::Balance { address: "creator".to_string() }).unwrap()).unwrap();
+        assert_eq!(seller.balances, coins(490, "utestcore"));
+        let fee: BalanceResponse = from_json(query(deps.as_ref(), env, QueryMsg::Balance { address: "fee_collector".to_string() }).unwrap()).unwrap();
+        assert_eq!(fee.balances, coins(10, "utestcore"));
+    }
+
+    #[test]
+    fn test_listing_valuations_and_comparison() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let instantiate_msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            fee_bps: 110,
+            fee_address: "fee_collector".to_string(),
+            require_kyc: Some(false),
+            denom: None,
+            delivery_timeout_hours: None,
+            seller_bond: None,
+            insurance_pool: None,
+            registry: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), instantiate_msg).unwrap();
+
+        let ounce_bar = ListingMetadata {
+            weight: Weight::new(Decimal::one(), WeightUnit::TroyOunce),
+            mint: None,
+            year: None,
+            ..gold_bar()
+        };
+        let eagle = ListingMetadata {
+            form: ProductForm::Coin,
+            fineness: 9167,
+            mint: Some("US Mint".to_string()),
+            ..ounce_bar.clone()
+        };
+        let silver_bar = ListingMetadata {
+            metal: Metal::Silver,
+            weight: Weight::new(Decimal::percent(1000), WeightUnit::TroyOunce),
+            fineness: 9990,
+            ..ounce_bar.clone()
+        };
+        let listings = [
+            (gold_bar(), 2_000_000u128, 24),
+            (eagle, 60_000, 24),
+            (silver_bar, 300, 24),
+            (ounce_bar.clone(), 64_000, 24),
+            // Ends before the comparison below
+            (ounce_bar, 1, 1),
+        ];
+        for (metadata, price, hours) in listings {
+            let create_msg = ExecuteMsg::CreateAuction {
+                item_id: "item123".to_string(),
+                metadata,
+                starting_price: Uint128::from(price),
+                reserve_price: None,
+                buy_now_price: None,
+                duration_hours: hours,
+                inspector: None,
+            };
+            execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), create_msg).unwrap();
+        }
+        // The kilo bar is valued at its standing bid
+        execute(deps.as_mut(), env.clone(), mock_info("bidder", &coins(2_100_000, "utestcore")), ExecuteMsg::PlaceBid { auction_id: 0 }).unwrap();
+
+        let query_msg = QueryMsg::ListingValuations { start_after: Some(1), limit: Some(2) };
+        let res: ListingValuationsResponse = from_json(query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.denom, "utestcore");
+        let ids: Vec<u64> = res.listings.iter().map(|listing| listing.auction_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+        assert_eq!(res.listings[0].pure_troy_ounces, Decimal::percent(999));
+        assert_eq!(res.listings[0].price_per_troy_ounce, Decimal::from_ratio(300_000u128, 9_990u128));
+        assert_eq!(res.listings[1].price, Uint128::new(64_000));
+        assert_eq!(res.listings[1].price_per_troy_ounce.to_string(), "64006.40064006400640064");
+
+        let mut later = env.clone();
+        later.block.time = later.block.time.plus_seconds(2 * 3600);
+        let query_msg = QueryMsg::CompareListings { metal: Metal::Gold, limit: None };
+        let res: ListingValuationsResponse = from_json(query(deps.as_ref(), later, query_msg).unwrap()).unwrap();
+        let ranked: Vec<(u64, Uint128)> = res.listings.iter().map(|listing| (listing.auction_id, listing.price)).collect();
+        assert_eq!(ranked, vec![
+            (3, Uint128::new(64_000)),
+            (0, Uint128::new(2_100_000)),
+            (1, Uint128::new(60_000)),
+        ]);
+        assert!(res.listings.iter().all(|listing| listing.metal == Metal::Gold));
+    }
 }
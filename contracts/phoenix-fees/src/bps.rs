@@ -0,0 +1,79 @@
+use cosmwasm_std::{Decimal, Uint128, Uint256};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::FeeError;
+
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+// A rate in basis points, 110 = 1.1%
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Bps(u16);
+
+impl Bps {
+    pub const ZERO: Bps = Bps(0);
+    pub const MAX: Bps = Bps(BPS_DENOMINATOR);
+
+    pub fn new(bps: u16) -> Result<Self, FeeError> {
+        if bps > BPS_DENOMINATOR {
+            return Err(FeeError::InvalidBps { bps });
+        }
+        Ok(Bps(bps))
+    }
+
+    // Converts a fractional rate (0.011) to basis points; the rate must be exact
+    pub fn from_decimal(rate: Decimal) -> Result<Self, FeeError> {
+        let scaled = Uint128::from(BPS_DENOMINATOR) * rate;
+        let bps = u16::try_from(scaled.u128()).map_err(|_| FeeError::Overflow)?;
+        if Decimal::from_ratio(bps, BPS_DENOMINATOR) != rate {
+            return Err(FeeError::InexactRate { rate: rate.to_string() });
+        }
+        Bps::new(bps)
+    }
+
+    pub fn value(self) -> u16 {
+        self.0
+    }
+
+    // Fractional rate, 110 bps -> 0.011
+    pub fn to_decimal(self) -> Decimal {
+        Decimal::from_ratio(self.0, BPS_DENOMINATOR)
+    }
+
+    // Rate in percent, 110 bps -> 1.1
+    pub fn percent(self) -> Decimal {
+        Decimal::from_ratio(self.0, 100u16)
+    }
+
+    // `amount * bps / 10000` with explicit rounding of the remainder
+    pub fn apply(self, amount: Uint128, rounding: Rounding) -> Result<Uint128, FeeError> {
+        let numerator = amount.full_mul(self.0);
+        let denominator = Uint256::from(BPS_DENOMINATOR);
+        let quotient = numerator / denominator;
+        let remainder = numerator % denominator;
+
+        let round_up = match rounding {
+            Rounding::Floor => false,
+            Rounding::Ceil => !remainder.is_zero(),
+            Rounding::HalfEven => {
+                let twice = remainder + remainder;
+                twice > denominator
+                    || (twice == denominator && quotient % Uint256::from(2u8) == Uint256::from(1u8))
+            }
+        };
+        let result = if round_up { quotient + Uint256::from(1u8) } else { quotient };
+        Uint128::try_from(result).map_err(|_| FeeError::Overflow)
+    }
+}
+
+// How the fractional part of a fee is resolved
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Rounding {
+    // Fee rounds down, the seller keeps the dust
+    Floor,
+    // Fee rounds up, the seller's share rounds down
+    Ceil,
+    // Banker's rounding, ties go to the even amount
+    HalfEven,
+}
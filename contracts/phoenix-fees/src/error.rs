@@ -0,0 +1,27 @@
+use cosmwasm_std::{StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum FeeError {
+    #[error("Basis points must be at most 10000, got {bps}")]
+    InvalidBps { bps: u16 },
+
+    #[error("Fee rate {rate} is not a whole number of basis points")]
+    InexactRate { rate: String },
+
+    #[error("Minimum fee {min} exceeds maximum fee {max}")]
+    InvalidCaps { min: Uint128, max: Uint128 },
+
+    #[error("Fee shares must add up to 10000 bps, got {total}")]
+    InvalidSplit { total: u32 },
+
+    #[error("Fee calculation overflow")]
+    Overflow,
+}
+
+// Lets contracts that return StdResult use `?` on fee math
+impl From<FeeError> for StdError {
+    fn from(err: FeeError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
@@ -0,0 +1,12 @@
+pub mod bps;
+pub mod error;
+pub mod schedule;
+pub mod split;
+
+// Re-export key types for easier access
+pub use crate::bps::{Bps, Rounding, BPS_DENOMINATOR};
+pub use crate::error::FeeError;
+pub use crate::schedule::{FeeAmounts, FeeSchedule, PLATFORM_FEE_BPS};
+pub use crate::split::{split_fee, FeeShare};
+#[cfg(test)]
+mod tests;
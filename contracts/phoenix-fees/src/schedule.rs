@@ -0,0 +1,72 @@
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::bps::{Bps, Rounding};
+use crate::error::FeeError;
+
+// The 1.1% platform fee every marketplace contract charges
+pub const PLATFORM_FEE_BPS: u16 = 110;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FeeSchedule {
+    pub rate_bps: u16,
+    pub rounding: Rounding,
+    pub min_fee: Option<Uint128>,
+    pub max_fee: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FeeAmounts {
+    pub gross: Uint128,
+    pub fee: Uint128,
+    pub net: Uint128,
+}
+
+impl FeeSchedule {
+    // 1.1%, fee rounded up so the seller's share is floored, no caps
+    pub fn platform() -> Self {
+        FeeSchedule {
+            rate_bps: PLATFORM_FEE_BPS,
+            rounding: Rounding::Ceil,
+            min_fee: None,
+            max_fee: None,
+        }
+    }
+
+    pub fn with_rate(rate_bps: u16) -> Self {
+        FeeSchedule {
+            rate_bps,
+            ..FeeSchedule::platform()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), FeeError> {
+        Bps::new(self.rate_bps)?;
+        if let (Some(min), Some(max)) = (self.min_fee, self.max_fee) {
+            if min > max {
+                return Err(FeeError::InvalidCaps { min, max });
+            }
+        }
+        Ok(())
+    }
+
+    // Fee on a sale amount, clamped to the caps and never more than the amount itself
+    pub fn compute(&self, amount: Uint128) -> Result<FeeAmounts, FeeError> {
+        self.validate()?;
+        let mut fee = Bps::new(self.rate_bps)?.apply(amount, self.rounding)?;
+        if let Some(min) = self.min_fee {
+            fee = fee.max(min);
+        }
+        if let Some(max) = self.max_fee {
+            fee = fee.min(max);
+        }
+        let fee = fee.min(amount);
+
+        Ok(FeeAmounts {
+            gross: amount,
+            fee,
+            net: amount - fee,
+        })
+    }
+}
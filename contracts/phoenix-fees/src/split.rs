@@ -0,0 +1,41 @@
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::bps::{Bps, Rounding, BPS_DENOMINATOR};
+use crate::error::FeeError;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FeeShare<R> {
+    pub recipient: R,
+    pub share_bps: u16,
+}
+
+// Splits a fee across recipients whose shares add up to 10000 bps. Each
+// share is floored and the rounding dust goes to the first recipient, so
+// the parts always add up to the fee.
+pub fn split_fee<R: Clone>(
+    fee: Uint128,
+    shares: &[FeeShare<R>],
+) -> Result<Vec<(R, Uint128)>, FeeError> {
+    let total: u32 = shares.iter().map(|share| share.share_bps as u32).sum();
+    if total != BPS_DENOMINATOR as u32 {
+        return Err(FeeError::InvalidSplit { total });
+    }
+
+    let mut parts = shares
+        .iter()
+        .map(|share| {
+            let amount = Bps::new(share.share_bps)?.apply(fee, Rounding::Floor)?;
+            Ok((share.recipient.clone(), amount))
+        })
+        .collect::<Result<Vec<_>, FeeError>>()?;
+
+    let allocated = parts
+        .iter()
+        .fold(Uint128::zero(), |total, (_, amount)| total + *amount);
+    if let Some((_, first)) = parts.first_mut() {
+        *first += fee - allocated;
+    }
+    Ok(parts)
+}
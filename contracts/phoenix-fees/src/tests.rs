@@ -0,0 +1,70 @@
+use cosmwasm_std::{Decimal, Uint128};
+
+use crate::{split_fee, Bps, FeeError, FeeSchedule, FeeShare, Rounding};
+
+#[test]
+fn test_bps_bounds_and_decimal() {
+    assert_eq!(Bps::new(10_001), Err(FeeError::InvalidBps { bps: 10_001 }));
+    let rate = Bps::new(110).unwrap();
+    assert_eq!(rate.to_decimal(), Decimal::permille(11));
+    assert_eq!(rate.percent(), Decimal::from_ratio(11u128, 10u128));
+    assert_eq!(Bps::from_decimal(Decimal::permille(11)).unwrap(), rate);
+    assert!(matches!(
+        Bps::from_decimal(Decimal::from_ratio(1u128, 30_000u128)),
+        Err(FeeError::InexactRate { .. })
+    ));
+}
+
+#[test]
+fn test_rounding_modes() {
+    let rate = Bps::new(110).unwrap();
+    // 500 * 1.1% = 5.5
+    let amount = Uint128::new(500);
+    assert_eq!(rate.apply(amount, Rounding::Floor).unwrap(), Uint128::new(5));
+    assert_eq!(rate.apply(amount, Rounding::Ceil).unwrap(), Uint128::new(6));
+    assert_eq!(rate.apply(amount, Rounding::HalfEven).unwrap(), Uint128::new(6));
+    // 1500 * 1.1% = 16.5 rounds to the even 16
+    assert_eq!(rate.apply(Uint128::new(1500), Rounding::HalfEven).unwrap(), Uint128::new(16));
+    // Exact results are unaffected
+    assert_eq!(rate.apply(Uint128::new(1000), Rounding::Ceil).unwrap(), Uint128::new(11));
+    // No overflow on the full range
+    assert!(Bps::MAX.apply(Uint128::MAX, Rounding::Ceil).is_ok());
+}
+
+#[test]
+fn test_platform_schedule_and_caps() {
+    let amounts = FeeSchedule::platform().compute(Uint128::new(200)).unwrap();
+    assert_eq!(amounts.fee, Uint128::new(3));
+    assert_eq!(amounts.net, Uint128::new(197));
+
+    let capped = FeeSchedule {
+        min_fee: Some(Uint128::new(10)),
+        max_fee: Some(Uint128::new(50)),
+        ..FeeSchedule::platform()
+    };
+    assert_eq!(capped.compute(Uint128::new(100)).unwrap().fee, Uint128::new(10));
+    assert_eq!(capped.compute(Uint128::new(1_000_000)).unwrap().fee, Uint128::new(50));
+    // The fee never exceeds the sale
+    assert_eq!(capped.compute(Uint128::new(4)).unwrap().net, Uint128::zero());
+
+    let inverted = FeeSchedule {
+        min_fee: Some(Uint128::new(50)),
+        max_fee: Some(Uint128::new(10)),
+        ..FeeSchedule::platform()
+    };
+    assert!(matches!(inverted.validate(), Err(FeeError::InvalidCaps { .. })));
+}
+
+#[test]
+fn test_split_fee() {
+    let shares = vec![
+        FeeShare { recipient: "insurance", share_bps: 9_000 },
+        FeeShare { recipient: "developer", share_bps: 1_000 },
+    ];
+    let parts = split_fee(Uint128::new(55), &shares).unwrap();
+    // 49.5 / 5.5 floored, dust to the first recipient
+    assert_eq!(parts, vec![("insurance", Uint128::new(50)), ("developer", Uint128::new(5))]);
+
+    let bad = vec![FeeShare { recipient: "insurance", share_bps: 9_000 }];
+    assert_eq!(split_fee(Uint128::new(55), &bad), Err(FeeError::InvalidSplit { total: 9_000 }));
+}
@@ -0,0 +1,978 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, BankMsg, Binary, Decimal, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use phoenix_fees::{Bps, Rounding};
+use phoenix_metals::ContentHash;
+
+use crate::coverage;
+use crate::developer;
+use crate::error::ContractError;
+use crate::market::load_order;
+use crate::shares;
+use crate::msg::{
+    ClaimsResponse, ConfigResponse, ContributionResponse, ContributionsResponse,
+    DeveloperStakeResponse, ExecuteMsg, InstantiateMsg, MarketsResponse, PoolResponse, QueryMsg,
+    QuoteResponse, UnderwriterResponse,
+};
+use crate::state::{
+    Claim, ClaimStatus, Config, DeveloperStake, PendingWithdrawal, Policy, PolicyStatus, Pool,
+    PoolStatus, PremiumRates, RiskTier, CLAIMANT_CLAIMS, CLAIMS, CLAIM_COUNT, CONFIG,
+    CONTRIBUTIONS, DEVELOPER_ACCOUNT, MARKETS, ORDER_CLAIMS, POLICIES, POOL, SHARES,
+    SOURCE_TOTALS, WITHDRAWALS,
+};
+
+const CONTRACT_NAME: &str = "crates.io:phoenix-insurance";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// 50,000 RLUSD at 6 decimals
+const DEFAULT_TARGET: u128 = 50_000_000_000;
+const DEFAULT_MAX_CLAIM_BPS: u16 = 1_000;
+const DEFAULT_DEVELOPER_SHARE_BPS: u16 = 1_000;
+const DEFAULT_MAX_EXPOSURE_BPS: u16 = 10_000;
+const DEFAULT_COVERAGE_DAYS: u64 = 60;
+const DEFAULT_WITHDRAWAL_COOLDOWN: u64 = 7 * 24 * 3600;
+const MAX_EVIDENCE: usize = 10;
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let target = msg.target.unwrap_or_else(|| Uint128::new(DEFAULT_TARGET));
+    if target.is_zero() {
+        return Err(ContractError::InvalidTarget {});
+    }
+    let admin = deps.api.addr_validate(&msg.admin)?;
+    let assessor = match msg.assessor {
+        Some(assessor) => deps.api.addr_validate(&assessor)?,
+        None => admin.clone(),
+    };
+    let max_claim_bps = msg.max_claim_bps.unwrap_or(DEFAULT_MAX_CLAIM_BPS);
+    Bps::new(max_claim_bps)?;
+
+    let developer = match msg.developer {
+        Some(stake) => {
+            let share_bps = stake.share_bps.unwrap_or(DEFAULT_DEVELOPER_SHARE_BPS);
+            Bps::new(share_bps)?;
+            if stake.vesting_seconds < stake.cliff_seconds {
+                return Err(ContractError::InvalidVesting {});
+            }
+            Some(DeveloperStake {
+                address: deps.api.addr_validate(&stake.address)?,
+                share_bps,
+                vesting_start: env.block.time.seconds(),
+                cliff_seconds: stake.cliff_seconds,
+                vesting_seconds: stake.vesting_seconds,
+            })
+        }
+        None => None,
+    };
+
+    let premium_rates = msg.premium_rates.unwrap_or(PremiumRates {
+        low: 100,
+        standard: 200,
+        high: 400,
+    });
+    validate_premium_rates(&premium_rates)?;
+    let max_exposure_bps = msg.max_exposure_bps.unwrap_or(DEFAULT_MAX_EXPOSURE_BPS);
+    Bps::new(max_exposure_bps)?;
+
+    let config = Config {
+        admin,
+        denom: msg.denom,
+        target,
+        assessor,
+        max_claim_bps,
+        developer,
+        premium_rates,
+        max_exposure_bps,
+        coverage_seconds: msg.coverage_days.unwrap_or(DEFAULT_COVERAGE_DAYS) * 24 * 3600,
+        withdrawal_cooldown_seconds: msg.withdrawal_cooldown_seconds
+            .unwrap_or(DEFAULT_WITHDRAWAL_COOLDOWN),
+        solvency_floor: msg.solvency_floor.unwrap_or_default(),
+    };
+    CONFIG.save(deps.storage, &config)?;
+    POOL.save(
+        deps.storage,
+        &Pool {
+            balance: Uint128::zero(),
+            total_contributed: Uint128::zero(),
+            total_paid: Uint128::zero(),
+            exposure: Uint128::zero(),
+            total_shares: Uint128::zero(),
+            status: PoolStatus::Accumulating,
+            activated_at: None,
+        },
+    )?;
+    CLAIM_COUNT.save(deps.storage, &0u64)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("admin", config.admin)
+        .add_attribute("target", config.target))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::DepositFee { auction_id } => execute_deposit_fee(deps, env, info, auction_id),
+        ExecuteMsg::UpdateConfig {
+            admin,
+            target,
+            assessor,
+            max_claim_bps,
+            premium_rates,
+            max_exposure_bps,
+        } => execute_update_config(
+            deps, env, info, admin, target, assessor, max_claim_bps, premium_rates, max_exposure_bps,
+        ),
+        ExecuteMsg::AddMarket { address } => execute_set_market(deps, info, address, true),
+        ExecuteMsg::RemoveMarket { address } => execute_set_market(deps, info, address, false),
+        ExecuteMsg::PurchaseCoverage { auction_id, buyer, declared_value, tier } => {
+            execute_purchase_coverage(deps, env, info, auction_id, buyer, declared_value, tier)
+        }
+        ExecuteMsg::ExpirePolicy { market, auction_id } => {
+            execute_expire_policy(deps, env, market, auction_id)
+        }
+        ExecuteMsg::SubmitClaim { market, auction_id, amount, evidence } => {
+            execute_submit_claim(deps, env, info, market, auction_id, amount, evidence)
+        }
+        ExecuteMsg::ApproveClaim { claim_id, amount } => {
+            execute_approve_claim(deps, env, info, claim_id, amount)
+        }
+        ExecuteMsg::DenyClaim { claim_id, reason } => {
+            execute_deny_claim(deps, env, info, claim_id, reason)
+        }
+        ExecuteMsg::UpdateUnderwriting { withdrawal_cooldown_seconds, solvency_floor } => {
+            execute_update_underwriting(deps, info, withdrawal_cooldown_seconds, solvency_floor)
+        }
+        ExecuteMsg::ProvideLiquidity {} => execute_provide_liquidity(deps, env, info),
+        ExecuteMsg::RequestWithdrawal { shares } => {
+            execute_request_withdrawal(deps, env, info, shares)
+        }
+        ExecuteMsg::CancelWithdrawal {} => execute_cancel_withdrawal(deps, info),
+        ExecuteMsg::CompleteWithdrawal {} => execute_complete_withdrawal(deps, env, info),
+        ExecuteMsg::WithdrawDeveloperStake { amount } => {
+            execute_withdraw_developer_stake(deps, env, info, amount)
+        }
+    }
+}
+
+// ============================================================
+// FUNDING
+// ============================================================
+
+fn execute_deposit_fee(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = funds_sent(&info, &config.denom)?;
+
+    CONTRIBUTIONS.update(deps.storage, (&info.sender, auction_id), |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default().checked_add(amount)?)
+    })?;
+    SOURCE_TOTALS.update(deps.storage, &info.sender, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default().checked_add(amount)?)
+    })?;
+
+    let (developer_share, pool_share) =
+        developer::accrue(deps.storage, config.developer.as_ref(), amount)?;
+
+    // Fee income is capital owned by the pool itself, so it mints shares to the
+    // contract rather than accruing to underwriters
+    let mut pool = POOL.load(deps.storage)?;
+    shares::deposit(deps.storage, &mut pool, &env.contract.address, pool_share)?;
+    pool.total_contributed = pool
+        .total_contributed
+        .checked_add(amount)
+        .map_err(StdError::from)?;
+    let activated = try_activate(&mut pool, &config, &env);
+    POOL.save(deps.storage, &pool)?;
+
+    let mut res = Response::new()
+        .add_attribute("method", "deposit_fee")
+        .add_attribute("source", info.sender)
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("developer_share", developer_share)
+        .add_attribute("balance", pool.balance);
+    if activated {
+        res = res.add_attribute("pool_activated", env.block.time.seconds().to_string());
+    }
+    Ok(res)
+}
+
+fn funds_sent(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    let amount = match info.funds.as_slice() {
+        [] => return Err(ContractError::NoFunds {}),
+        [coin] if coin.denom == denom => coin.amount,
+        _ => return Err(ContractError::InvalidDenom {}),
+    };
+    if amount.is_zero() {
+        return Err(ContractError::NoFunds {});
+    }
+    Ok(amount)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    admin: Option<String>,
+    target: Option<Uint128>,
+    assessor: Option<String>,
+    max_claim_bps: Option<u16>,
+    premium_rates: Option<PremiumRates>,
+    max_exposure_bps: Option<u16>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(admin) = admin {
+        config.admin = deps.api.addr_validate(&admin)?;
+    }
+    if let Some(target) = target {
+        if target.is_zero() {
+            return Err(ContractError::InvalidTarget {});
+        }
+        config.target = target;
+    }
+    if let Some(assessor) = assessor {
+        config.assessor = deps.api.addr_validate(&assessor)?;
+    }
+    if let Some(max_claim_bps) = max_claim_bps {
+        Bps::new(max_claim_bps)?;
+        config.max_claim_bps = max_claim_bps;
+    }
+    if let Some(premium_rates) = premium_rates {
+        validate_premium_rates(&premium_rates)?;
+        config.premium_rates = premium_rates;
+    }
+    if let Some(max_exposure_bps) = max_exposure_bps {
+        Bps::new(max_exposure_bps)?;
+        config.max_exposure_bps = max_exposure_bps;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    // A lowered target may already be met
+    let mut pool = POOL.load(deps.storage)?;
+    let activated = try_activate(&mut pool, &config, &env);
+    POOL.save(deps.storage, &pool)?;
+
+    let mut res = Response::new()
+        .add_attribute("method", "update_config")
+        .add_attribute("admin", config.admin)
+        .add_attribute("target", config.target);
+    if activated {
+        res = res.add_attribute("pool_activated", env.block.time.seconds().to_string());
+    }
+    Ok(res)
+}
+
+// Moves an accumulating pool to active once the target is reached
+fn try_activate(pool: &mut Pool, config: &Config, env: &Env) -> bool {
+    if pool.status != PoolStatus::Accumulating || pool.balance < config.target {
+        return false;
+    }
+    pool.status = PoolStatus::Active;
+    pool.activated_at = Some(env.block.time.seconds());
+    true
+}
+
+fn validate_premium_rates(rates: &PremiumRates) -> Result<(), ContractError> {
+    Bps::new(rates.low)?;
+    Bps::new(rates.standard)?;
+    Bps::new(rates.high)?;
+    Ok(())
+}
+
+// ============================================================
+// UNDERWRITERS
+// ============================================================
+
+fn execute_update_underwriting(
+    deps: DepsMut,
+    info: MessageInfo,
+    withdrawal_cooldown_seconds: Option<u64>,
+    solvency_floor: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    if let Some(cooldown) = withdrawal_cooldown_seconds {
+        config.withdrawal_cooldown_seconds = cooldown;
+    }
+    if let Some(floor) = solvency_floor {
+        config.solvency_floor = floor;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_underwriting")
+        .add_attribute("withdrawal_cooldown_seconds", config.withdrawal_cooldown_seconds.to_string())
+        .add_attribute("solvency_floor", config.solvency_floor))
+}
+
+fn execute_provide_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = funds_sent(&info, &config.denom)?;
+
+    let mut pool = POOL.load(deps.storage)?;
+    if shares::shares_for(&pool, amount).is_zero() {
+        return Err(ContractError::ZeroSharePrice {});
+    }
+    let minted = shares::deposit(deps.storage, &mut pool, &info.sender, amount)?;
+    let activated = try_activate(&mut pool, &config, &env);
+    POOL.save(deps.storage, &pool)?;
+
+    let mut res = Response::new()
+        .add_attribute("method", "provide_liquidity")
+        .add_attribute("provider", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("shares", minted)
+        .add_attribute("balance", pool.balance);
+    if activated {
+        res = res.add_attribute("pool_activated", env.block.time.seconds().to_string());
+    }
+    Ok(res)
+}
+
+fn execute_request_withdrawal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    shares: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if WITHDRAWALS.has(deps.storage, &info.sender) {
+        return Err(ContractError::WithdrawalPending {});
+    }
+    let held = SHARES.may_load(deps.storage, &info.sender)?.unwrap_or_default();
+    if shares.is_zero() || shares > held {
+        return Err(ContractError::InsufficientShares {});
+    }
+
+    SHARES.save(deps.storage, &info.sender, &(held - shares))?;
+    let pending = PendingWithdrawal {
+        shares,
+        available_at: env.block.time.seconds() + config.withdrawal_cooldown_seconds,
+    };
+    WITHDRAWALS.save(deps.storage, &info.sender, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "request_withdrawal")
+        .add_attribute("provider", info.sender)
+        .add_attribute("shares", shares)
+        .add_attribute("available_at", pending.available_at.to_string()))
+}
+
+fn execute_cancel_withdrawal(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let pending = WITHDRAWALS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NoPendingWithdrawal {})?;
+    WITHDRAWALS.remove(deps.storage, &info.sender);
+    SHARES.update(deps.storage, &info.sender, |held| -> StdResult<_> {
+        Ok(held.unwrap_or_default().checked_add(pending.shares)?)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "cancel_withdrawal")
+        .add_attribute("provider", info.sender)
+        .add_attribute("shares", pending.shares))
+}
+
+fn execute_complete_withdrawal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pending = WITHDRAWALS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NoPendingWithdrawal {})?;
+    if env.block.time.seconds() < pending.available_at {
+        return Err(ContractError::CooldownActive {});
+    }
+
+    // Paid at today's share price, so claims during the cooldown are shared
+    let mut pool = POOL.load(deps.storage)?;
+    let amount = shares::burn(&mut pool, pending.shares);
+    let floor = config.solvency_floor.max(pool.exposure);
+    if pool.balance < floor {
+        return Err(ContractError::BelowSolvencyFloor {});
+    }
+    POOL.save(deps.storage, &pool)?;
+    WITHDRAWALS.remove(deps.storage, &info.sender);
+
+    let mut res = Response::new()
+        .add_attribute("method", "complete_withdrawal")
+        .add_attribute("provider", info.sender.clone())
+        .add_attribute("shares", pending.shares)
+        .add_attribute("amount", amount);
+    if !amount.is_zero() {
+        res = res.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), config.denom),
+        });
+    }
+    Ok(res)
+}
+
+// ============================================================
+// COVERAGE
+// ============================================================
+
+fn execute_set_market(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    registered: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let market = deps.api.addr_validate(&address)?;
+    if registered {
+        MARKETS.save(deps.storage, &market, &Empty {})?;
+    } else {
+        MARKETS.remove(deps.storage, &market);
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", if registered { "add_market" } else { "remove_market" })
+        .add_attribute("market", market))
+}
+
+fn ensure_market(deps: Deps, market: &Addr) -> Result<(), ContractError> {
+    if !MARKETS.has(deps.storage, market) {
+        return Err(ContractError::UnknownMarket {});
+    }
+    Ok(())
+}
+
+fn execute_purchase_coverage(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_id: u64,
+    buyer: String,
+    declared_value: Uint128,
+    tier: RiskTier,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut pool = POOL.load(deps.storage)?;
+    let market = info.sender.clone();
+    ensure_market(deps.as_ref(), &market)?;
+    if POLICIES.has(deps.storage, (&market, auction_id)) {
+        return Err(ContractError::AlreadyCovered {});
+    }
+
+    if declared_value.is_zero() || declared_value > coverage::capacity(&config, &pool)? {
+        return Err(ContractError::InsufficientCapacity { declared_value });
+    }
+    let premium = coverage::premium(&config, tier, declared_value)?;
+    let paid = match info.funds.as_slice() {
+        [] => Uint128::zero(),
+        [coin] if coin.denom == config.denom => coin.amount,
+        _ => return Err(ContractError::InvalidDenom {}),
+    };
+    if paid != premium {
+        return Err(ContractError::WrongPremium { premium });
+    }
+
+    let policy = Policy {
+        market: market.clone(),
+        auction_id,
+        buyer: deps.api.addr_validate(&buyer)?,
+        declared_value,
+        tier,
+        premium,
+        expires_at: env.block.time.seconds() + config.coverage_seconds,
+        status: PolicyStatus::Active,
+    };
+    POLICIES.save(deps.storage, (&market, auction_id), &policy)?;
+
+    let (developer_share, pool_share) =
+        developer::accrue(deps.storage, config.developer.as_ref(), premium)?;
+    pool.balance = pool.balance.checked_add(pool_share).map_err(StdError::from)?;
+    pool.exposure = pool.exposure.checked_add(declared_value).map_err(StdError::from)?;
+    POOL.save(deps.storage, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "purchase_coverage")
+        .add_attribute("market", market)
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("buyer", policy.buyer)
+        .add_attribute("declared_value", declared_value)
+        .add_attribute("premium", premium)
+        .add_attribute("developer_share", developer_share))
+}
+
+fn execute_expire_policy(
+    deps: DepsMut,
+    env: Env,
+    market: String,
+    auction_id: u64,
+) -> Result<Response, ContractError> {
+    let market = deps.api.addr_validate(&market)?;
+    let mut policy = POLICIES
+        .may_load(deps.storage, (&market, auction_id))?
+        .ok_or(ContractError::NotCovered {})?;
+    if policy.status != PolicyStatus::Active || env.block.time.seconds() <= policy.expires_at {
+        return Err(ContractError::PolicyActive {});
+    }
+    // A claim still under review keeps the exposure until it is resolved
+    if let Some(claim_id) = ORDER_CLAIMS.may_load(deps.storage, (&market, auction_id))? {
+        if CLAIMS.load(deps.storage, claim_id)?.status == ClaimStatus::Pending {
+            return Err(ContractError::PolicyActive {});
+        }
+    }
+
+    policy.status = PolicyStatus::Expired;
+    POLICIES.save(deps.storage, (&market, auction_id), &policy)?;
+    release_exposure(deps.storage, policy.declared_value)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "expire_policy")
+        .add_attribute("market", market)
+        .add_attribute("auction_id", auction_id.to_string()))
+}
+
+fn release_exposure(storage: &mut dyn Storage, declared_value: Uint128) -> StdResult<()> {
+    POOL.update(storage, |mut pool| -> StdResult<_> {
+        pool.exposure = pool.exposure.checked_sub(declared_value)?;
+        Ok(pool)
+    })?;
+    Ok(())
+}
+
+// ============================================================
+// CLAIMS
+// ============================================================
+
+fn execute_submit_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    market: String,
+    auction_id: u64,
+    amount: Uint128,
+    evidence: Vec<ContentHash>,
+) -> Result<Response, ContractError> {
+    let pool = POOL.load(deps.storage)?;
+    if pool.status != PoolStatus::Active {
+        return Err(ContractError::PoolNotActive {});
+    }
+    validate_evidence(&evidence)?;
+
+    let market = deps.api.addr_validate(&market)?;
+    ensure_market(deps.as_ref(), &market)?;
+    let policy = POLICIES
+        .may_load(deps.storage, (&market, auction_id))?
+        .filter(|policy| {
+            policy.status == PolicyStatus::Active && env.block.time.seconds() <= policy.expires_at
+        })
+        .ok_or(ContractError::NotCovered {})?;
+    if policy.buyer != info.sender {
+        return Err(ContractError::NotBuyer {});
+    }
+    if amount.is_zero() || amount > policy.declared_value {
+        return Err(ContractError::InvalidClaimAmount {});
+    }
+
+    if let Some(existing) = ORDER_CLAIMS.may_load(deps.storage, (&market, auction_id))? {
+        // A denied claim can be resubmitted with new evidence
+        let existing = CLAIMS.load(deps.storage, existing)?;
+        if !matches!(existing.status, ClaimStatus::Denied { .. }) {
+            return Err(ContractError::DuplicateClaim {});
+        }
+    }
+
+    let order = load_order(deps.as_ref(), &market, auction_id)?;
+    if !order.is_claimable() {
+        return Err(ContractError::OrderNotClaimable {});
+    }
+    if order.buyer() != Some(&info.sender) {
+        return Err(ContractError::NotBuyer {});
+    }
+
+    let id = CLAIM_COUNT.load(deps.storage)? + 1;
+    CLAIM_COUNT.save(deps.storage, &id)?;
+    let claim = Claim {
+        id,
+        claimant: info.sender.clone(),
+        market: market.clone(),
+        auction_id,
+        declared_value: policy.declared_value,
+        amount,
+        evidence,
+        status: ClaimStatus::Pending,
+        submitted_at: env.block.time.seconds(),
+        resolved_at: None,
+    };
+    CLAIMS.save(deps.storage, id, &claim)?;
+    ORDER_CLAIMS.save(deps.storage, (&market, auction_id), &id)?;
+    CLAIMANT_CLAIMS.save(deps.storage, (&info.sender, id), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("method", "submit_claim")
+        .add_attribute("claim_id", id.to_string())
+        .add_attribute("claimant", info.sender)
+        .add_attribute("market", market)
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+fn execute_approve_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    claim_id: u64,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut claim = load_pending_claim(deps.as_ref(), &config, &info, claim_id)?;
+    let mut pool = POOL.load(deps.storage)?;
+
+    // Never more than claimed or declared, and never more than the per-claim share of the pool
+    let pool_cap = Bps::new(config.max_claim_bps)?.apply(pool.balance, Rounding::Floor)?;
+    let payout = amount
+        .unwrap_or(claim.amount)
+        .min(claim.amount)
+        .min(claim.declared_value)
+        .min(pool_cap);
+    if payout.is_zero() {
+        return Err(ContractError::InvalidClaimAmount {});
+    }
+
+    // A paid policy stops counting towards exposure
+    let mut policy = POLICIES.load(deps.storage, (&claim.market, claim.auction_id))?;
+    policy.status = PolicyStatus::Claimed;
+    POLICIES.save(deps.storage, (&claim.market, claim.auction_id), &policy)?;
+
+    pool.balance -= payout;
+    pool.total_paid = pool.total_paid.checked_add(payout).map_err(StdError::from)?;
+    pool.exposure = pool.exposure.checked_sub(policy.declared_value).map_err(StdError::from)?;
+    POOL.save(deps.storage, &pool)?;
+
+    claim.status = ClaimStatus::Paid { payout };
+    claim.resolved_at = Some(env.block.time.seconds());
+    CLAIMS.save(deps.storage, claim_id, &claim)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: claim.claimant.to_string(),
+            amount: coins(payout.u128(), config.denom),
+        })
+        .add_attribute("method", "approve_claim")
+        .add_attribute("claim_id", claim_id.to_string())
+        .add_attribute("claimant", claim.claimant)
+        .add_attribute("payout", payout)
+        .add_attribute("balance", pool.balance))
+}
+
+fn execute_deny_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    claim_id: u64,
+    reason: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut claim = load_pending_claim(deps.as_ref(), &config, &info, claim_id)?;
+
+    claim.status = ClaimStatus::Denied { reason: reason.clone() };
+    claim.resolved_at = Some(env.block.time.seconds());
+    CLAIMS.save(deps.storage, claim_id, &claim)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "deny_claim")
+        .add_attribute("claim_id", claim_id.to_string())
+        .add_attribute("reason", reason))
+}
+
+fn load_pending_claim(
+    deps: Deps,
+    config: &Config,
+    info: &MessageInfo,
+    claim_id: u64,
+) -> Result<Claim, ContractError> {
+    if info.sender != config.assessor {
+        return Err(ContractError::Unauthorized {});
+    }
+    let claim = CLAIMS
+        .may_load(deps.storage, claim_id)?
+        .ok_or(ContractError::ClaimNotFound {})?;
+    if claim.status != ClaimStatus::Pending {
+        return Err(ContractError::ClaimResolved {});
+    }
+    Ok(claim)
+}
+
+fn validate_evidence(evidence: &[ContentHash]) -> Result<(), ContractError> {
+    let valid = !evidence.is_empty()
+        && evidence.len() <= MAX_EVIDENCE
+        && evidence.iter().all(|hash| hash.validate().is_ok());
+    if !valid {
+        return Err(ContractError::InvalidEvidence {});
+    }
+    Ok(())
+}
+
+// ============================================================
+// DEVELOPER STAKE
+// ============================================================
+
+fn execute_withdraw_developer_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let stake = config.developer.ok_or(ContractError::NoDeveloperStake {})?;
+    if info.sender != stake.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut account = developer::account(deps.storage)?;
+    let withdrawable = developer::vested(&stake, &account, env.block.time.seconds()) - account.withdrawn;
+    let amount = amount.unwrap_or(withdrawable);
+    if amount.is_zero() {
+        return Err(ContractError::NoFunds {});
+    }
+    if amount > withdrawable {
+        return Err(ContractError::NotVested {});
+    }
+
+    account.withdrawn += amount;
+    DEVELOPER_ACCOUNT.save(deps.storage, &account)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: stake.address.to_string(),
+            amount: coins(amount.u128(), config.denom),
+        })
+        .add_attribute("method", "withdraw_developer_stake")
+        .add_attribute("amount", amount)
+        .add_attribute("withdrawn", account.withdrawn))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_json_binary(&ConfigResponse {
+                admin: config.admin.to_string(),
+                denom: config.denom,
+                target: config.target,
+                assessor: config.assessor.to_string(),
+                max_claim_bps: config.max_claim_bps,
+                developer: config.developer,
+                premium_rates: config.premium_rates,
+                max_exposure_bps: config.max_exposure_bps,
+                coverage_seconds: config.coverage_seconds,
+                withdrawal_cooldown_seconds: config.withdrawal_cooldown_seconds,
+                solvency_floor: config.solvency_floor,
+            })
+        }
+        QueryMsg::Pool {} => to_json_binary(&query_pool(deps, env)?),
+        QueryMsg::Contribution { source, auction_id } => {
+            let source = deps.api.addr_validate(&source)?;
+            let amount = CONTRIBUTIONS
+                .may_load(deps.storage, (&source, auction_id))?
+                .unwrap_or_default();
+            to_json_binary(&ContributionResponse {
+                source: source.to_string(),
+                auction_id,
+                amount,
+            })
+        }
+        QueryMsg::Contributions { source, start_after, limit } => {
+            to_json_binary(&query_contributions(deps, source, start_after, limit)?)
+        }
+        QueryMsg::Claim { claim_id } => to_json_binary(&CLAIMS.load(deps.storage, claim_id)?),
+        QueryMsg::Claims { claimant, start_after, limit } => {
+            to_json_binary(&query_claims(deps, claimant, start_after, limit)?)
+        }
+        QueryMsg::DeveloperStake {} => to_json_binary(&query_developer_stake(deps, env)?),
+        QueryMsg::Quote { declared_value, tier } => {
+            to_json_binary(&query_quote(deps, declared_value, tier)?)
+        }
+        QueryMsg::Policy { market, auction_id } => {
+            let market = deps.api.addr_validate(&market)?;
+            to_json_binary(&POLICIES.may_load(deps.storage, (&market, auction_id))?)
+        }
+        QueryMsg::Underwriter { address } => to_json_binary(&query_underwriter(deps, address)?),
+        QueryMsg::Markets {} => {
+            let markets = MARKETS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .map(|market| market.map(String::from))
+                .collect::<StdResult<_>>()?;
+            to_json_binary(&MarketsResponse { markets })
+        }
+    }
+}
+
+fn query_pool(deps: Deps, env: Env) -> StdResult<PoolResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool = POOL.load(deps.storage)?;
+    let progress = Decimal::from_ratio(pool.balance, config.target).min(Decimal::one());
+
+    Ok(PoolResponse {
+        denom: config.denom,
+        balance: pool.balance,
+        total_contributed: pool.total_contributed,
+        total_paid: pool.total_paid,
+        exposure: pool.exposure,
+        total_shares: pool.total_shares,
+        pool_shares: SHARES
+            .may_load(deps.storage, &env.contract.address)?
+            .unwrap_or_default(),
+        share_price: shares::price(&pool),
+        target: config.target,
+        progress,
+        remaining: config.target.saturating_sub(pool.balance),
+        status: pool.status,
+        activated_at: pool.activated_at,
+    })
+}
+
+fn query_contributions(
+    deps: Deps,
+    source: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ContributionsResponse> {
+    let source: Addr = deps.api.addr_validate(&source)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let contributions = CONTRIBUTIONS
+        .prefix(&source)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(auction_id, amount)| ContributionResponse {
+                source: source.to_string(),
+                auction_id,
+                amount,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ContributionsResponse {
+        source: source.to_string(),
+        total: SOURCE_TOTALS.may_load(deps.storage, &source)?.unwrap_or_default(),
+        contributions,
+    })
+}
+
+fn query_claims(
+    deps: Deps,
+    claimant: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ClaimsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let ids: Vec<u64> = match claimant {
+        Some(claimant) => {
+            let claimant = deps.api.addr_validate(&claimant)?;
+            CLAIMANT_CLAIMS
+                .prefix(&claimant)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<_>>()?
+        }
+        None => CLAIMS
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<_>>()?,
+    };
+    let claims = ids
+        .into_iter()
+        .map(|id| CLAIMS.load(deps.storage, id))
+        .collect::<StdResult<_>>()?;
+
+    Ok(ClaimsResponse { claims })
+}
+
+fn query_developer_stake(deps: Deps, env: Env) -> StdResult<DeveloperStakeResponse> {
+    let stake = CONFIG
+        .load(deps.storage)?
+        .developer
+        .ok_or_else(|| StdError::not_found("developer stake"))?;
+    let account = developer::account(deps.storage)?;
+    let vested = developer::vested(&stake, &account, env.block.time.seconds());
+
+    Ok(DeveloperStakeResponse {
+        address: stake.address.to_string(),
+        share_bps: stake.share_bps,
+        accrued: account.accrued,
+        vested,
+        withdrawn: account.withdrawn,
+        withdrawable: vested - account.withdrawn,
+    })
+}
+
+fn query_quote(deps: Deps, declared_value: Uint128, tier: RiskTier) -> StdResult<QuoteResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool = POOL.load(deps.storage)?;
+    let premium = coverage::premium(&config, tier, declared_value)?;
+    let capacity = coverage::capacity(&config, &pool)?;
+
+    Ok(QuoteResponse {
+        declared_value,
+        tier,
+        premium,
+        capacity,
+        available: !declared_value.is_zero() && declared_value <= capacity,
+    })
+}
+
+fn query_underwriter(deps: Deps, address: String) -> StdResult<UnderwriterResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let pool = POOL.load(deps.storage)?;
+    let held = SHARES.may_load(deps.storage, &address)?.unwrap_or_default();
+    let pending_withdrawal = WITHDRAWALS.may_load(deps.storage, &address)?;
+    let queued = pending_withdrawal.as_ref().map(|p| p.shares).unwrap_or_default();
+
+    Ok(UnderwriterResponse {
+        address: address.to_string(),
+        shares: held,
+        pending_withdrawal,
+        value: shares::value_of(&pool, held + queued),
+    })
+}
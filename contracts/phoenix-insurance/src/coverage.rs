@@ -0,0 +1,28 @@
+use cosmwasm_std::Uint128;
+use phoenix_fees::{Bps, FeeError, Rounding};
+
+use crate::state::{Config, Pool, PoolStatus, RiskTier};
+
+pub fn premium(
+    config: &Config,
+    tier: RiskTier,
+    declared_value: Uint128,
+) -> Result<Uint128, FeeError> {
+    let rate = match tier {
+        RiskTier::Low => config.premium_rates.low,
+        RiskTier::Standard => config.premium_rates.standard,
+        RiskTier::High => config.premium_rates.high,
+    };
+    Bps::new(rate)?.apply(declared_value, Rounding::Ceil)
+}
+
+// Largest declared value a new policy can have: bounded by the per-claim cap
+// and by the room left under the exposure limit
+pub fn capacity(config: &Config, pool: &Pool) -> Result<Uint128, FeeError> {
+    if pool.status != PoolStatus::Active {
+        return Ok(Uint128::zero());
+    }
+    let per_policy = Bps::new(config.max_claim_bps)?.apply(pool.balance, Rounding::Floor)?;
+    let exposure_limit = Bps::new(config.max_exposure_bps)?.apply(pool.balance, Rounding::Floor)?;
+    Ok(per_policy.min(exposure_limit.saturating_sub(pool.exposure)))
+}
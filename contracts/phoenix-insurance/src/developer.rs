@@ -0,0 +1,39 @@
+use cosmwasm_std::{StdResult, Storage, Uint128};
+use phoenix_fees::{Bps, Rounding};
+
+use crate::error::ContractError;
+use crate::state::{DeveloperAccount, DeveloperStake, DEVELOPER_ACCOUNT};
+
+// Sets aside the developer's share of an inflow and returns what is left for the pool
+pub fn accrue(
+    storage: &mut dyn Storage,
+    stake: Option<&DeveloperStake>,
+    amount: Uint128,
+) -> Result<(Uint128, Uint128), ContractError> {
+    let Some(stake) = stake else {
+        return Ok((Uint128::zero(), amount));
+    };
+    let share = Bps::new(stake.share_bps)?.apply(amount, Rounding::Floor)?;
+    if !share.is_zero() {
+        let mut account = account(storage)?;
+        account.accrued += share;
+        DEVELOPER_ACCOUNT.save(storage, &account)?;
+    }
+    Ok((share, amount - share))
+}
+
+pub fn account(storage: &dyn Storage) -> StdResult<DeveloperAccount> {
+    Ok(DEVELOPER_ACCOUNT.may_load(storage)?.unwrap_or_default())
+}
+
+// Portion of everything accrued so far that has vested at `now`
+pub fn vested(stake: &DeveloperStake, account: &DeveloperAccount, now: u64) -> Uint128 {
+    let elapsed = now.saturating_sub(stake.vesting_start);
+    if elapsed < stake.cliff_seconds {
+        return Uint128::zero();
+    }
+    if elapsed >= stake.vesting_seconds {
+        return account.accrued;
+    }
+    account.accrued.multiply_ratio(elapsed, stake.vesting_seconds)
+}
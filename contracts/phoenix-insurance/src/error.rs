@@ -0,0 +1,97 @@
+use cosmwasm_std::{StdError, Uint128};
+use phoenix_fees::FeeError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Fee(#[from] FeeError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("No funds sent")]
+    NoFunds {},
+
+    #[error("Invalid denom")]
+    InvalidDenom {},
+
+    #[error("Pool target must be greater than zero")]
+    InvalidTarget {},
+
+    // Claim errors
+    #[error("Pool is not active yet")]
+    PoolNotActive {},
+
+    #[error("Claim not found")]
+    ClaimNotFound {},
+
+    #[error("Claim already resolved")]
+    ClaimResolved {},
+
+    #[error("Order has an open or paid claim")]
+    DuplicateClaim {},
+
+    #[error("Order is not completed")]
+    OrderNotClaimable {},
+
+    #[error("Only the buyer can claim against an order")]
+    NotBuyer {},
+
+    #[error("Evidence must be sha256 digests or IPFS CIDs")]
+    InvalidEvidence {},
+
+    #[error("Claim amount must be between zero and the declared value")]
+    InvalidClaimAmount {},
+
+    // Developer stake errors
+    #[error("No developer stake configured")]
+    NoDeveloperStake {},
+
+    #[error("Vesting must last at least as long as the cliff")]
+    InvalidVesting {},
+
+    #[error("Amount exceeds the vested balance")]
+    NotVested {},
+
+    // Coverage errors
+    #[error("Pool cannot cover a declared value of {declared_value}")]
+    InsufficientCapacity { declared_value: Uint128 },
+
+    #[error("Premium of {premium} required")]
+    WrongPremium { premium: Uint128 },
+
+    #[error("Market is not registered")]
+    UnknownMarket {},
+
+    #[error("Order is already covered")]
+    AlreadyCovered {},
+
+    #[error("Order is not covered")]
+    NotCovered {},
+
+    #[error("Policy has not expired")]
+    PolicyActive {},
+
+    // Underwriter errors
+    #[error("Pool shares are worthless, deposits are closed")]
+    ZeroSharePrice {},
+
+    #[error("Insufficient shares")]
+    InsufficientShares {},
+
+    #[error("A withdrawal is already pending")]
+    WithdrawalPending {},
+
+    #[error("No pending withdrawal")]
+    NoPendingWithdrawal {},
+
+    #[error("Withdrawal cooldown has not passed")]
+    CooldownActive {},
+
+    #[error("Withdrawal would take the pool below its solvency floor")]
+    BelowSolvencyFloor {},
+}
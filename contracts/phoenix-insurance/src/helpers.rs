@@ -0,0 +1,61 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, CosmosMsg, QuerierWrapper, StdResult, Uint128, WasmMsg,
+};
+
+use crate::msg::{ExecuteMsg, QueryMsg, QuoteResponse};
+use crate::state::RiskTier;
+
+// Address of a deployed insurance pool, used by the marketplace contracts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct InsurancePool(pub Addr);
+
+impl InsurancePool {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    // Forward a platform fee, attributed to the auction it was taken from
+    pub fn deposit_fee(&self, auction_id: u64, fee: Coin) -> StdResult<CosmosMsg> {
+        self.call(ExecuteMsg::DepositFee { auction_id }, vec![fee])
+    }
+
+    // Buy shipping cover for an order; `premium` must match the pool's quote
+    pub fn purchase_coverage(
+        &self,
+        auction_id: u64,
+        buyer: &Addr,
+        declared_value: Uint128,
+        tier: RiskTier,
+        premium: Coin,
+    ) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::PurchaseCoverage {
+            auction_id,
+            buyer: buyer.to_string(),
+            declared_value,
+            tier,
+        };
+        let funds = if premium.amount.is_zero() { vec![] } else { vec![premium] };
+        self.call(msg, funds)
+    }
+
+    pub fn quote(
+        &self,
+        querier: &QuerierWrapper,
+        declared_value: Uint128,
+        tier: RiskTier,
+    ) -> StdResult<QuoteResponse> {
+        querier.query_wasm_smart(self.addr(), &QueryMsg::Quote { declared_value, tier })
+    }
+
+    fn call(&self, msg: ExecuteMsg, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg: to_json_binary(&msg)?,
+            funds,
+        }
+        .into())
+    }
+}
@@ -0,0 +1,9 @@
+pub mod contract;
+pub mod msg;
+pub mod state;
+pub mod error;
+pub mod helpers;
+pub mod market;
+pub mod developer;
+pub mod coverage;
+pub mod shares;
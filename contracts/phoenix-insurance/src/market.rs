@@ -0,0 +1,51 @@
+use cosmwasm_std::{Addr, Deps, StdResult};
+use serde::{Deserialize, Serialize};
+
+// Subset of phoenix-escrow's query interface needed to check an order
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum MarketQueryMsg {
+    Auction { id: u64 },
+}
+
+#[derive(Deserialize)]
+struct AuctionResponse {
+    auction: Order,
+}
+
+#[derive(Deserialize)]
+pub struct Order {
+    pub status: OrderStatus,
+    pub highest_bid: Option<OrderBid>,
+}
+
+#[derive(Deserialize)]
+pub struct OrderBid {
+    pub bidder: Addr,
+}
+
+#[derive(Deserialize, PartialEq)]
+pub enum OrderStatus {
+    Completed,
+    #[serde(other)]
+    Other,
+}
+
+impl Order {
+    pub fn buyer(&self) -> Option<&Addr> {
+        self.highest_bid.as_ref().map(|bid| &bid.bidder)
+    }
+
+    // Only delivered orders can be claimed against. A disputed order may
+    // still end in an escrow refund, so paying it out would double recover.
+    pub fn is_claimable(&self) -> bool {
+        self.status == OrderStatus::Completed
+    }
+}
+
+pub fn load_order(deps: Deps, market: &Addr, auction_id: u64) -> StdResult<Order> {
+    let res: AuctionResponse = deps
+        .querier
+        .query_wasm_smart(market, &MarketQueryMsg::Auction { id: auction_id })?;
+    Ok(res.auction)
+}
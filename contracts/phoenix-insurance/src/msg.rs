@@ -0,0 +1,238 @@
+use cosmwasm_std::{Decimal, Uint128};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use crate::state::{
+    Claim, DeveloperStake, PendingWithdrawal, PoolStatus, PremiumRates, RiskTier,
+};
+pub use phoenix_metals::ContentHash;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub admin: String,
+    pub denom: String,
+    // Defaults to 50,000 RLUSD
+    pub target: Option<Uint128>,
+    // Defaults to the admin
+    pub assessor: Option<String>,
+    // Defaults to 10% of the pool per claim
+    pub max_claim_bps: Option<u16>,
+    pub developer: Option<DeveloperStakeMsg>,
+    // Defaults to 1% / 2% / 4% of the declared value
+    pub premium_rates: Option<PremiumRates>,
+    // Defaults to 100% of the pool
+    pub max_exposure_bps: Option<u16>,
+    // Defaults to 60 days
+    pub coverage_days: Option<u64>,
+    // Defaults to 7 days
+    pub withdrawal_cooldown_seconds: Option<u64>,
+    // Defaults to zero, outstanding exposure is always kept
+    pub solvency_floor: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DeveloperStakeMsg {
+    pub address: String,
+    // Defaults to 10%
+    pub share_bps: Option<u16>,
+    pub cliff_seconds: u64,
+    pub vesting_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    // Sent by marketplace contracts with the platform fee attached
+    DepositFee {
+        auction_id: u64,
+    },
+    UpdateConfig {
+        admin: Option<String>,
+        target: Option<Uint128>,
+        assessor: Option<String>,
+        max_claim_bps: Option<u16>,
+        premium_rates: Option<PremiumRates>,
+        max_exposure_bps: Option<u16>,
+    },
+    // Admin only; registers a marketplace contract that may sell cover
+    AddMarket {
+        address: String,
+    },
+    // Admin only; existing policies of the market can no longer be claimed
+    RemoveMarket {
+        address: String,
+    },
+    // Sent by a registered marketplace contract with the quoted premium attached
+    PurchaseCoverage {
+        auction_id: u64,
+        buyer: String,
+        declared_value: Uint128,
+        tier: RiskTier,
+    },
+    // Releases the exposure of a policy whose claim window has passed
+    ExpirePolicy {
+        market: String,
+        auction_id: u64,
+    },
+    // Buyer claims against a covered, completed order
+    SubmitClaim {
+        market: String,
+        auction_id: u64,
+        amount: Uint128,
+        evidence: Vec<ContentHash>,
+    },
+    // Assessor only; pays the claim, optionally reducing the amount
+    ApproveClaim {
+        claim_id: u64,
+        amount: Option<Uint128>,
+    },
+    // Assessor only
+    DenyClaim {
+        claim_id: u64,
+        reason: String,
+    },
+    UpdateUnderwriting {
+        withdrawal_cooldown_seconds: Option<u64>,
+        solvency_floor: Option<Uint128>,
+    },
+    // Underwriter capital, minted into pool shares at the current share price
+    ProvideLiquidity {},
+    // Starts the cooldown; queued shares still carry premiums and claims
+    RequestWithdrawal {
+        shares: Uint128,
+    },
+    CancelWithdrawal {},
+    // Pays out queued shares at the share price at completion
+    CompleteWithdrawal {},
+    // Developer only; withdraws everything vested when no amount is given
+    WithdrawDeveloperStake {
+        amount: Option<Uint128>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    Pool {},
+    Contribution {
+        source: String,
+        auction_id: u64,
+    },
+    Contributions {
+        source: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    Claim {
+        claim_id: u64,
+    },
+    // Claims history, optionally for a single claimant
+    Claims {
+        claimant: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    DeveloperStake {},
+    Quote {
+        declared_value: Uint128,
+        tier: RiskTier,
+    },
+    // Returns Option<Policy>
+    Policy {
+        market: String,
+        auction_id: u64,
+    },
+    Underwriter {
+        address: String,
+    },
+    Markets {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub admin: String,
+    pub denom: String,
+    pub target: Uint128,
+    pub assessor: String,
+    pub max_claim_bps: u16,
+    pub developer: Option<DeveloperStake>,
+    pub premium_rates: PremiumRates,
+    pub max_exposure_bps: u16,
+    pub coverage_seconds: u64,
+    pub withdrawal_cooldown_seconds: u64,
+    pub solvency_floor: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolResponse {
+    pub denom: String,
+    pub balance: Uint128,
+    pub total_contributed: Uint128,
+    pub total_paid: Uint128,
+    pub exposure: Uint128,
+    pub total_shares: Uint128,
+    // Shares minted for fee income, owned by the pool
+    pub pool_shares: Uint128,
+    pub share_price: Decimal,
+    pub target: Uint128,
+    // Share of the target reached, capped at 1
+    pub progress: Decimal,
+    pub remaining: Uint128,
+    pub status: PoolStatus,
+    pub activated_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContributionResponse {
+    pub source: String,
+    pub auction_id: u64,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContributionsResponse {
+    pub source: String,
+    pub total: Uint128,
+    pub contributions: Vec<ContributionResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimsResponse {
+    pub claims: Vec<Claim>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DeveloperStakeResponse {
+    pub address: String,
+    pub share_bps: u16,
+    pub accrued: Uint128,
+    pub vested: Uint128,
+    pub withdrawn: Uint128,
+    // Vested but not yet withdrawn
+    pub withdrawable: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QuoteResponse {
+    pub declared_value: Uint128,
+    pub tier: RiskTier,
+    pub premium: Uint128,
+    // Largest declared value the pool can cover right now
+    pub capacity: Uint128,
+    pub available: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnderwriterResponse {
+    pub address: String,
+    pub shares: Uint128,
+    pub pending_withdrawal: Option<PendingWithdrawal>,
+    // Current value of held and queued shares
+    pub value: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MarketsResponse {
+    pub markets: Vec<String>,
+}
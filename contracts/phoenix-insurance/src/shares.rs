@@ -0,0 +1,57 @@
+use cosmwasm_std::{Addr, Decimal, StdResult, Storage, Uint128};
+
+use crate::state::{Pool, SHARES};
+
+// Shares minted for `amount` at the current share price; zero once the
+// outstanding shares are worthless or the amount is below one share
+pub fn shares_for(pool: &Pool, amount: Uint128) -> Uint128 {
+    if pool.total_shares.is_zero() {
+        return amount;
+    }
+    if pool.balance.is_zero() {
+        return Uint128::zero();
+    }
+    amount.multiply_ratio(pool.total_shares, pool.balance)
+}
+
+// Adds capital to the pool, minting shares to `owner`; anything too small to
+// mint a share is left as income for existing holders
+pub fn deposit(
+    storage: &mut dyn Storage,
+    pool: &mut Pool,
+    owner: &Addr,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let shares = shares_for(pool, amount);
+    if !shares.is_zero() {
+        SHARES.update(storage, owner, |held| -> StdResult<_> {
+            Ok(held.unwrap_or_default().checked_add(shares)?)
+        })?;
+        pool.total_shares = pool.total_shares.checked_add(shares)?;
+    }
+    pool.balance = pool.balance.checked_add(amount)?;
+    Ok(shares)
+}
+
+// Removes shares and the capital behind them from the pool
+pub fn burn(pool: &mut Pool, shares: Uint128) -> Uint128 {
+    let amount = value_of(pool, shares);
+    pool.total_shares -= shares;
+    pool.balance -= amount;
+    amount
+}
+
+pub fn value_of(pool: &Pool, shares: Uint128) -> Uint128 {
+    if pool.total_shares.is_zero() {
+        return Uint128::zero();
+    }
+    shares.multiply_ratio(pool.balance, pool.total_shares)
+}
+
+// Pool balance per share; premiums raise it and paid claims lower it
+pub fn price(pool: &Pool) -> Decimal {
+    if pool.total_shares.is_zero() {
+        return Decimal::one();
+    }
+    Decimal::from_ratio(pool.balance, pool.total_shares)
+}
@@ -0,0 +1,163 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_storage_plus::{Item, Map};
+use phoenix_metals::ContentHash;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub admin: Addr,
+    // Denom the pool is funded and pays out in (RLUSD)
+    pub denom: String,
+    // Balance at which the pool starts underwriting
+    pub target: Uint128,
+    // Approves or denies claims
+    pub assessor: Addr,
+    // Largest single payout as a share of the pool balance, in basis points
+    pub max_claim_bps: u16,
+    // Developer's cut of pool inflows; none is taken when unset
+    pub developer: Option<DeveloperStake>,
+    pub premium_rates: PremiumRates,
+    // Outstanding coverage as a share of the pool balance, in basis points
+    pub max_exposure_bps: u16,
+    // How long a policy can be claimed against after purchase
+    pub coverage_seconds: u64,
+    // Delay between requesting and completing an underwriter withdrawal
+    pub withdrawal_cooldown_seconds: u64,
+    // Balance withdrawals may not take the pool below, on top of outstanding exposure
+    pub solvency_floor: Uint128,
+}
+
+// Premium per risk tier, in basis points of the declared value
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PremiumRates {
+    pub low: u16,
+    pub standard: u16,
+    pub high: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskTier {
+    // Insured carrier, signature on delivery
+    Low,
+    Standard,
+    // International or high-value uninsured routes
+    High,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DeveloperStake {
+    pub address: Addr,
+    // Share of every inflow set aside for the developer, in basis points (1000 = 10%)
+    pub share_bps: u16,
+    // Everything accrued vests linearly from vesting_start over vesting_seconds,
+    // with nothing withdrawable before the cliff
+    pub vesting_start: u64,
+    pub cliff_seconds: u64,
+    pub vesting_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct DeveloperAccount {
+    pub accrued: Uint128,
+    pub withdrawn: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Pool {
+    // Funds currently held for coverage
+    pub balance: Uint128,
+    // Everything ever contributed, never decreases
+    pub total_contributed: Uint128,
+    // Everything paid out on claims
+    pub total_paid: Uint128,
+    // Declared value of all active policies
+    pub exposure: Uint128,
+    // Shares held by underwriters and, for fee income, by the pool itself
+    pub total_shares: Uint128,
+    pub status: PoolStatus,
+    pub activated_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolStatus {
+    // Collecting fees until the target is reached
+    Accumulating,
+    // Target reached; stays active from here on
+    Active,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingWithdrawal {
+    pub shares: Uint128,
+    pub available_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Policy {
+    pub market: Addr,
+    pub auction_id: u64,
+    pub buyer: Addr,
+    pub declared_value: Uint128,
+    pub tier: RiskTier,
+    pub premium: Uint128,
+    pub expires_at: u64,
+    pub status: PolicyStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyStatus {
+    Active,
+    // A claim was paid; the policy no longer counts towards exposure
+    Claimed,
+    Expired,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Claim {
+    pub id: u64,
+    pub claimant: Addr,
+    // Marketplace contract and auction the claim is made against
+    pub market: Addr,
+    pub auction_id: u64,
+    pub declared_value: Uint128,
+    pub amount: Uint128,
+    // Fingerprints of photos, carrier reports and the like
+    pub evidence: Vec<ContentHash>,
+    pub status: ClaimStatus,
+    pub submitted_at: u64,
+    pub resolved_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimStatus {
+    Pending,
+    Paid { payout: Uint128 },
+    Denied { reason: String },
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const POOL: Item<Pool> = Item::new("pool");
+// Fees received per (source contract, auction id)
+pub const CONTRIBUTIONS: Map<(&Addr, u64), Uint128> = Map::new("contributions");
+// Fees received per source contract
+pub const SOURCE_TOTALS: Map<&Addr, Uint128> = Map::new("source_totals");
+
+pub const DEVELOPER_ACCOUNT: Item<DeveloperAccount> = Item::new("developer_account");
+// Freely transferable shares per holder; shares queued for withdrawal are held separately
+pub const SHARES: Map<&Addr, Uint128> = Map::new("shares");
+pub const WITHDRAWALS: Map<&Addr, PendingWithdrawal> = Map::new("withdrawals");
+// Marketplace contracts allowed to sell cover and have their orders claimed against
+pub const MARKETS: Map<&Addr, Empty> = Map::new("markets");
+// Policies per (market, auction id)
+pub const POLICIES: Map<(&Addr, u64), Policy> = Map::new("policies");
+pub const CLAIMS: Map<u64, Claim> = Map::new("claims");
+pub const CLAIM_COUNT: Item<u64> = Item::new("claim_count");
+// Latest claim per (market, auction id)
+pub const ORDER_CLAIMS: Map<(&Addr, u64), u64> = Map::new("order_claims");
+// Claim ids per claimant
+pub const CLAIMANT_CLAIMS: Map<(&Addr, u64), Empty> = Map::new("claimant_claims");
@@ -0,0 +1,653 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        coins, from_json, to_json_binary, Addr, BankMsg, ContractResult, CosmosMsg, Decimal,
+        OwnedDeps, SystemResult, Uint128, WasmQuery,
+    };
+    use phoenix_insurance::contract::{execute, instantiate, query};
+    use phoenix_insurance::error::ContractError;
+    use phoenix_insurance::msg::{
+        ClaimsResponse, ContentHash, ContributionResponse, ContributionsResponse, DeveloperStakeMsg,
+        DeveloperStakeResponse, ExecuteMsg, InstantiateMsg, MarketsResponse, PoolResponse, QueryMsg,
+        QuoteResponse, UnderwriterResponse,
+    };
+    use phoenix_insurance::state::{Claim, ClaimStatus, Policy, PolicyStatus, PoolStatus, RiskTier};
+    use phoenix_escrow::msg::AuctionResponse;
+    use phoenix_escrow::state::{Auction, AuctionStatus, Bid};
+
+    const DENOM: &str = "urlusd";
+
+    fn setup(target: Option<Uint128>) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            denom: DENOM.to_string(),
+            target,
+            assessor: Some("assessor".to_string()),
+            max_claim_bps: None,
+            developer: None,
+            premium_rates: None,
+            max_exposure_bps: None,
+            coverage_days: None,
+            withdrawal_cooldown_seconds: None,
+            solvency_floor: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+        let market = ExecuteMsg::AddMarket { address: "escrow".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), market).unwrap();
+        deps
+    }
+
+    #[test]
+    fn test_default_target() {
+        let deps = setup(None);
+        let pool: PoolResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Pool {}).unwrap()).unwrap();
+        assert_eq!(pool.target, Uint128::new(50_000_000_000));
+        assert_eq!(pool.balance, Uint128::zero());
+        assert_eq!(pool.progress, Decimal::zero());
+        assert_eq!(pool.status, PoolStatus::Accumulating);
+    }
+
+    #[test]
+    fn test_contributions_tracked_per_auction() {
+        let mut deps = setup(Some(Uint128::new(1_000)));
+
+        let fee = |auction_id| ExecuteMsg::DepositFee { auction_id };
+        execute(deps.as_mut(), mock_env(), mock_info("auction", &coins(100, DENOM)), fee(1)).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("auction", &coins(50, DENOM)), fee(1)).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("auction", &coins(25, DENOM)), fee(2)).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(25, DENOM)), fee(1)).unwrap();
+
+        let single: ContributionResponse = from_json(query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Contribution { source: "auction".to_string(), auction_id: 1 },
+        ).unwrap()).unwrap();
+        assert_eq!(single.amount, Uint128::new(150));
+
+        let all: ContributionsResponse = from_json(query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Contributions { source: "auction".to_string(), start_after: None, limit: None },
+        ).unwrap()).unwrap();
+        assert_eq!(all.total, Uint128::new(175));
+        assert_eq!(all.contributions.len(), 2);
+        assert_eq!(all.contributions[1].auction_id, 2);
+
+        let pool: PoolResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Pool {}).unwrap()).unwrap();
+        assert_eq!(pool.balance, Uint128::new(200));
+        assert_eq!(pool.progress, Decimal::percent(20));
+        assert_eq!(pool.remaining, Uint128::new(800));
+    }
+
+    #[test]
+    fn test_activates_at_target() {
+        let mut deps = setup(Some(Uint128::new(1_000)));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("auction", &coins(999, DENOM)),
+            ExecuteMsg::DepositFee { auction_id: 1 },
+        ).unwrap();
+        assert!(!res.attributes.iter().any(|a| a.key == "pool_activated"));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("auction", &coins(5, DENOM)),
+            ExecuteMsg::DepositFee { auction_id: 2 },
+        ).unwrap();
+        assert!(res.attributes.iter().any(|a| a.key == "pool_activated"));
+
+        let pool: PoolResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Pool {}).unwrap()).unwrap();
+        assert_eq!(pool.status, PoolStatus::Active);
+        assert_eq!(pool.progress, Decimal::one());
+        assert_eq!(pool.remaining, Uint128::zero());
+        assert_eq!(pool.activated_at, Some(mock_env().block.time.seconds()));
+    }
+
+    #[test]
+    fn test_lowering_target_activates() {
+        let mut deps = setup(Some(Uint128::new(1_000)));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("auction", &coins(600, DENOM)),
+            ExecuteMsg::DepositFee { auction_id: 1 },
+        ).unwrap();
+
+        let update = ExecuteMsg::UpdateConfig {
+            admin: None,
+            target: Some(Uint128::new(500)),
+            assessor: None,
+            max_claim_bps: None,
+            premium_rates: None,
+            max_exposure_bps: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), update.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), update).unwrap();
+        let pool: PoolResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Pool {}).unwrap()).unwrap();
+        assert_eq!(pool.status, PoolStatus::Active);
+    }
+
+    #[test]
+    fn test_rejects_bad_deposits() {
+        let mut deps = setup(None);
+        let msg = ExecuteMsg::DepositFee { auction_id: 1 };
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("auction", &[]), msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::NoFunds {});
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("auction", &coins(10, "ucore")), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidDenom {});
+    }
+
+    // Escrow order as returned by phoenix-escrow's Auction query
+    fn mock_market(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        status: AuctionStatus,
+        buyer: &str,
+    ) {
+        let auction = Auction {
+            creator: Addr::unchecked("seller"),
+            item_id: "gold-bar-1oz".to_string(),
+            metadata: None,
+            starting_price: Uint128::new(100),
+            reserve_price: None,
+            buy_now_price: None,
+            ends_at: 0,
+            bids: vec![],
+            highest_bid: Some(Bid {
+                bidder: Addr::unchecked(buyer),
+                amount: Uint128::new(2_000),
+                timestamp: 0,
+            }),
+            status,
+            created_at: 0,
+            sold_at: Some(0),
+            frozen_from: None,
+            inspection: None,
+        };
+        let response = to_json_binary(&AuctionResponse { id: 1, auction }).unwrap();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "escrow" => {
+                SystemResult::Ok(ContractResult::Ok(response.clone()))
+            }
+            _ => panic!("unexpected query"),
+        });
+    }
+
+    fn funded_pool() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = setup(Some(Uint128::new(1_000)));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("auction", &coins(1_000, DENOM)),
+            ExecuteMsg::DepositFee { auction_id: 1 },
+        ).unwrap();
+        deps
+    }
+
+    fn cover(auction_id: u64, declared_value: u128) -> ExecuteMsg {
+        ExecuteMsg::PurchaseCoverage {
+            auction_id,
+            buyer: "buyer".to_string(),
+            declared_value: Uint128::new(declared_value),
+            tier: RiskTier::Standard,
+        }
+    }
+
+    // Funded pool with escrow order 1 covered for a declared value of 100
+    fn active_pool() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = funded_pool();
+        execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(2, DENOM)), cover(1, 100)).unwrap();
+        deps
+    }
+
+    fn claim(amount: u128) -> ExecuteMsg {
+        ExecuteMsg::SubmitClaim {
+            market: "escrow".to_string(),
+            auction_id: 1,
+            amount: Uint128::new(amount),
+            evidence: vec![
+                ContentHash::Sha256("ab".repeat(32)),
+                ContentHash::Cid("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string()),
+            ],
+        }
+    }
+
+    fn pool(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> PoolResponse {
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Pool {}).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_coverage_quote_and_purchase() {
+        let mut deps = funded_pool();
+
+        // 2% standard premium; a single policy can cover up to 10% of the pool
+        let quote: QuoteResponse = from_json(query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Quote { declared_value: Uint128::new(100), tier: RiskTier::Standard },
+        ).unwrap()).unwrap();
+        assert_eq!(quote.premium, Uint128::new(2));
+        assert_eq!(quote.capacity, Uint128::new(100));
+        assert!(quote.available);
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(1, DENOM)), cover(1, 100)).unwrap_err();
+        assert_eq!(err, ContractError::WrongPremium { premium: Uint128::new(2) });
+
+        execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(2, DENOM)), cover(1, 100)).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(2, DENOM)), cover(1, 100)).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyCovered {});
+
+        let policy: Option<Policy> = from_json(query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Policy { market: "escrow".to_string(), auction_id: 1 },
+        ).unwrap()).unwrap();
+        let policy = policy.unwrap();
+        assert_eq!(policy.buyer, Addr::unchecked("buyer"));
+        assert_eq!(policy.premium, Uint128::new(2));
+        assert_eq!(policy.status, PolicyStatus::Active);
+
+        let pool = pool(&deps);
+        assert_eq!(pool.balance, Uint128::new(1_002));
+        assert_eq!(pool.exposure, Uint128::new(100));
+    }
+
+    #[test]
+    fn test_coverage_refused_without_capacity() {
+        let mut deps = setup(Some(Uint128::new(1_000)));
+        let err = execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(2, DENOM)), cover(1, 100)).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientCapacity { declared_value: Uint128::new(100) });
+
+        let mut deps = funded_pool();
+        let err = execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(3, DENOM)), cover(1, 101)).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientCapacity { declared_value: Uint128::new(101) });
+
+        // Total exposure limited to 15% of the pool
+        let update = ExecuteMsg::UpdateConfig {
+            admin: None,
+            target: None,
+            assessor: None,
+            max_claim_bps: None,
+            premium_rates: None,
+            max_exposure_bps: Some(1_500),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), update).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(2, DENOM)), cover(1, 100)).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(2, DENOM)), cover(2, 100)).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientCapacity { declared_value: Uint128::new(100) });
+        execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(1, DENOM)), cover(2, 50)).unwrap();
+    }
+
+    #[test]
+    fn test_expired_policy_releases_exposure() {
+        let mut deps = active_pool();
+        mock_market(&mut deps, AuctionStatus::Completed, "buyer");
+        let expire = ExecuteMsg::ExpirePolicy { market: "escrow".to_string(), auction_id: 1 };
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), expire.clone()).unwrap_err();
+        assert_eq!(err, ContractError::PolicyActive {});
+
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(61 * 24 * 3600);
+        let err = execute(deps.as_mut(), later.clone(), mock_info("buyer", &[]), claim(100)).unwrap_err();
+        assert_eq!(err, ContractError::NotCovered {});
+
+        execute(deps.as_mut(), later, mock_info("anyone", &[]), expire).unwrap();
+        assert_eq!(pool(&deps).exposure, Uint128::zero());
+    }
+
+    #[test]
+    fn test_claim_paid_from_pool() {
+        let mut deps = active_pool();
+        mock_market(&mut deps, AuctionStatus::Completed, "buyer");
+
+        execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(100)).unwrap();
+
+        let approve = ExecuteMsg::ApproveClaim { claim_id: 1, amount: None };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("assessor", &[]), approve).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "buyer".to_string(),
+                amount: coins(100, DENOM),
+            })
+        );
+
+        let pool = pool(&deps);
+        assert_eq!(pool.balance, Uint128::new(902));
+        assert_eq!(pool.total_paid, Uint128::new(100));
+        assert_eq!(pool.total_contributed, Uint128::new(1_000));
+        assert_eq!(pool.exposure, Uint128::zero());
+
+        let history: ClaimsResponse = from_json(query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Claims { claimant: Some("buyer".to_string()), start_after: None, limit: None },
+        ).unwrap()).unwrap();
+        assert_eq!(history.claims.len(), 1);
+        assert_eq!(history.claims[0].status, ClaimStatus::Paid { payout: Uint128::new(100) });
+
+        // A paid order cannot be claimed again
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(100)).unwrap_err();
+        assert_eq!(err, ContractError::NotCovered {});
+    }
+
+    #[test]
+    fn test_denied_claim_can_be_resubmitted() {
+        let mut deps = active_pool();
+        mock_market(&mut deps, AuctionStatus::Completed, "buyer");
+
+        execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(50)).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(50)).unwrap_err();
+        assert_eq!(err, ContractError::DuplicateClaim {});
+
+        let deny = ExecuteMsg::DenyClaim { claim_id: 1, reason: "no carrier report".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("assessor", &[]), deny).unwrap();
+
+        let approve = ExecuteMsg::ApproveClaim { claim_id: 1, amount: None };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("assessor", &[]), approve).unwrap_err();
+        assert_eq!(err, ContractError::ClaimResolved {});
+
+        execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(50)).unwrap();
+        let approve = ExecuteMsg::ApproveClaim { claim_id: 2, amount: Some(Uint128::new(30)) };
+        execute(deps.as_mut(), mock_env(), mock_info("assessor", &[]), approve).unwrap();
+
+        let claim: Claim =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Claim { claim_id: 2 }).unwrap()).unwrap();
+        assert_eq!(claim.status, ClaimStatus::Paid { payout: Uint128::new(30) });
+
+        let history: ClaimsResponse = from_json(query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Claims { claimant: None, start_after: None, limit: None },
+        ).unwrap()).unwrap();
+        assert_eq!(history.claims.len(), 2);
+    }
+
+    #[test]
+    fn test_only_registered_markets_sell_cover() {
+        let mut deps = funded_pool();
+
+        let rogue = ExecuteMsg::AddMarket { address: "rogue".to_string() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("rogue", &[]), rogue).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Unregistered contracts cannot tie up capacity
+        let err = execute(deps.as_mut(), mock_env(), mock_info("rogue", &coins(2, DENOM)), cover(1, 100)).unwrap_err();
+        assert_eq!(err, ContractError::UnknownMarket {});
+        assert_eq!(pool(&deps).exposure, Uint128::zero());
+
+        // Nor serve orders to claim against
+        execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(2, DENOM)), cover(1, 100)).unwrap();
+        mock_market(&mut deps, AuctionStatus::Completed, "buyer");
+        let forged = ExecuteMsg::SubmitClaim {
+            market: "rogue".to_string(),
+            auction_id: 1,
+            amount: Uint128::new(100),
+            evidence: vec![ContentHash::Sha256("ab".repeat(32))],
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), forged).unwrap_err();
+        assert_eq!(err, ContractError::UnknownMarket {});
+
+        let markets: MarketsResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Markets {}).unwrap()).unwrap();
+        assert_eq!(markets.markets, vec!["escrow".to_string()]);
+
+        // A removed market's policies can no longer be claimed
+        let remove = ExecuteMsg::RemoveMarket { address: "escrow".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), remove).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(100)).unwrap_err();
+        assert_eq!(err, ContractError::UnknownMarket {});
+    }
+
+    #[test]
+    fn test_claim_rejections() {
+        let mut deps = setup(Some(Uint128::new(1_000)));
+        mock_market(&mut deps, AuctionStatus::Sold, "buyer");
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(100)).unwrap_err();
+        assert_eq!(err, ContractError::PoolNotActive {});
+
+        let mut deps = active_pool();
+        mock_market(&mut deps, AuctionStatus::Sold, "buyer");
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(100)).unwrap_err();
+        assert_eq!(err, ContractError::OrderNotClaimable {});
+
+        // An open escrow dispute may still refund the buyer
+        mock_market(&mut deps, AuctionStatus::Disputed, "buyer");
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(100)).unwrap_err();
+        assert_eq!(err, ContractError::OrderNotClaimable {});
+
+        mock_market(&mut deps, AuctionStatus::Completed, "buyer");
+        let err = execute(deps.as_mut(), mock_env(), mock_info("thief", &[]), claim(100)).unwrap_err();
+        assert_eq!(err, ContractError::NotBuyer {});
+
+        // Capped by the declared value of the policy
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(101)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidClaimAmount {});
+
+        let uncovered = ExecuteMsg::SubmitClaim {
+            market: "escrow".to_string(),
+            auction_id: 2,
+            amount: Uint128::new(100),
+            evidence: vec![ContentHash::Sha256("ab".repeat(32))],
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), uncovered).unwrap_err();
+        assert_eq!(err, ContractError::NotCovered {});
+
+        let bad_evidence = ExecuteMsg::SubmitClaim {
+            market: "escrow".to_string(),
+            auction_id: 1,
+            amount: Uint128::new(100),
+            evidence: vec![ContentHash::Sha256("AB".repeat(32))],
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), bad_evidence).unwrap_err();
+        assert_eq!(err, ContractError::InvalidEvidence {});
+
+        execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(100)).unwrap();
+        let approve = ExecuteMsg::ApproveClaim { claim_id: 1, amount: None };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), approve).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn test_developer_stake_vests_and_withdraws() {
+        let mut deps = mock_dependencies();
+        let start = mock_env();
+        let msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            denom: DENOM.to_string(),
+            target: None,
+            assessor: None,
+            max_claim_bps: None,
+            developer: Some(DeveloperStakeMsg {
+                address: "dev".to_string(),
+                share_bps: None,
+                cliff_seconds: 100,
+                vesting_seconds: 1_000,
+            }),
+            premium_rates: None,
+            max_exposure_bps: None,
+            coverage_days: None,
+            withdrawal_cooldown_seconds: None,
+            solvency_floor: None,
+        };
+        instantiate(deps.as_mut(), start.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        execute(
+            deps.as_mut(),
+            start.clone(),
+            mock_info("auction", &coins(1_000, DENOM)),
+            ExecuteMsg::DepositFee { auction_id: 1 },
+        ).unwrap();
+
+        // 10% of the inflow is set aside for the developer
+        let pool: PoolResponse =
+            from_json(query(deps.as_ref(), start.clone(), QueryMsg::Pool {}).unwrap()).unwrap();
+        assert_eq!(pool.balance, Uint128::new(900));
+        assert_eq!(pool.total_contributed, Uint128::new(1_000));
+
+        let at = |seconds: u64| {
+            let mut env = start.clone();
+            env.block.time = env.block.time.plus_seconds(seconds);
+            env
+        };
+        let before_cliff: DeveloperStakeResponse =
+            from_json(query(deps.as_ref(), at(50), QueryMsg::DeveloperStake {}).unwrap()).unwrap();
+        assert_eq!(before_cliff.accrued, Uint128::new(100));
+        assert_eq!(before_cliff.vested, Uint128::zero());
+
+        let withdraw = |amount: Option<u128>| ExecuteMsg::WithdrawDeveloperStake {
+            amount: amount.map(Uint128::new),
+        };
+        let err = execute(deps.as_mut(), at(50), mock_info("dev", &[]), withdraw(None)).unwrap_err();
+        assert_eq!(err, ContractError::NoFunds {});
+
+        let err = execute(deps.as_mut(), at(500), mock_info("admin", &[]), withdraw(None)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let err = execute(deps.as_mut(), at(500), mock_info("dev", &[]), withdraw(Some(60))).unwrap_err();
+        assert_eq!(err, ContractError::NotVested {});
+
+        execute(deps.as_mut(), at(500), mock_info("dev", &[]), withdraw(Some(30))).unwrap();
+        let halfway: DeveloperStakeResponse =
+            from_json(query(deps.as_ref(), at(500), QueryMsg::DeveloperStake {}).unwrap()).unwrap();
+        assert_eq!(halfway.vested, Uint128::new(50));
+        assert_eq!(halfway.withdrawn, Uint128::new(30));
+        assert_eq!(halfway.withdrawable, Uint128::new(20));
+
+        let res = execute(deps.as_mut(), at(1_000), mock_info("dev", &[]), withdraw(None)).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dev".to_string(),
+                amount: coins(70, DENOM),
+            })
+        );
+    }
+
+    fn underwriter(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, address: &str) -> UnderwriterResponse {
+        let msg = QueryMsg::Underwriter { address: address.to_string() };
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+    }
+
+    fn after_cooldown() -> cosmwasm_std::Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(7 * 24 * 3600);
+        env
+    }
+
+    #[test]
+    fn test_underwriter_shares_track_premiums_and_claims() {
+        let mut deps = setup(Some(Uint128::new(1_000)));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("auction", &coins(400, DENOM)),
+            ExecuteMsg::DepositFee { auction_id: 1 },
+        ).unwrap();
+
+        // Outside capital counts towards the activation target
+        let res = execute(deps.as_mut(), mock_env(), mock_info("underwriter1", &coins(600, DENOM)), ExecuteMsg::ProvideLiquidity {}).unwrap();
+        assert!(res.attributes.iter().any(|a| a.key == "pool_activated"));
+        let state = pool(&deps);
+        assert_eq!(state.total_shares, Uint128::new(1_000));
+        assert_eq!(state.pool_shares, Uint128::new(400));
+
+        // Premiums raise the share price for every holder
+        execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(2, DENOM)), cover(1, 100)).unwrap();
+        assert_eq!(pool(&deps).share_price, Decimal::permille(1_002));
+        assert_eq!(underwriter(&deps, "underwriter1").value, Uint128::new(601));
+
+        // Later capital buys in at the higher price
+        execute(deps.as_mut(), mock_env(), mock_info("underwriter2", &coins(501, DENOM)), ExecuteMsg::ProvideLiquidity {}).unwrap();
+        assert_eq!(underwriter(&deps, "underwriter2").shares, Uint128::new(500));
+
+        // A paid claim is shared by all holders
+        mock_market(&mut deps, AuctionStatus::Completed, "buyer");
+        execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), claim(100)).unwrap();
+        let approve = ExecuteMsg::ApproveClaim { claim_id: 1, amount: None };
+        execute(deps.as_mut(), mock_env(), mock_info("assessor", &[]), approve).unwrap();
+        assert_eq!(pool(&deps).balance, Uint128::new(1_403));
+        assert_eq!(underwriter(&deps, "underwriter1").value, Uint128::new(561));
+
+        let request = ExecuteMsg::RequestWithdrawal { shares: Uint128::new(600) };
+        execute(deps.as_mut(), mock_env(), mock_info("underwriter1", &[]), request.clone()).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info("underwriter1", &[]), request).unwrap_err();
+        assert_eq!(err, ContractError::WithdrawalPending {});
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("underwriter1", &[]), ExecuteMsg::CompleteWithdrawal {}).unwrap_err();
+        assert_eq!(err, ContractError::CooldownActive {});
+
+        let res = execute(deps.as_mut(), after_cooldown(), mock_info("underwriter1", &[]), ExecuteMsg::CompleteWithdrawal {}).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "underwriter1".to_string(),
+                amount: coins(561, DENOM),
+            })
+        );
+        let state = pool(&deps);
+        assert_eq!(state.balance, Uint128::new(842));
+        assert_eq!(state.total_shares, Uint128::new(900));
+    }
+
+    #[test]
+    fn test_withdrawal_respects_solvency_floor() {
+        let mut deps = setup(Some(Uint128::new(1_000)));
+        execute(deps.as_mut(), mock_env(), mock_info("underwriter1", &coins(1_000, DENOM)), ExecuteMsg::ProvideLiquidity {}).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("escrow", &coins(2, DENOM)), cover(1, 100)).unwrap();
+
+        let update = ExecuteMsg::UpdateUnderwriting {
+            withdrawal_cooldown_seconds: None,
+            solvency_floor: Some(Uint128::new(500)),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), update).unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("underwriter1", &[]),
+            ExecuteMsg::RequestWithdrawal { shares: Uint128::new(1_001) },
+        ).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientShares {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("underwriter1", &[]),
+            ExecuteMsg::RequestWithdrawal { shares: Uint128::new(1_000) },
+        ).unwrap();
+        let err = execute(deps.as_mut(), after_cooldown(), mock_info("underwriter1", &[]), ExecuteMsg::CompleteWithdrawal {}).unwrap_err();
+        assert_eq!(err, ContractError::BelowSolvencyFloor {});
+
+        execute(deps.as_mut(), mock_env(), mock_info("underwriter1", &[]), ExecuteMsg::CancelWithdrawal {}).unwrap();
+        let provider = underwriter(&deps, "underwriter1");
+        assert_eq!(provider.shares, Uint128::new(1_000));
+        assert_eq!(provider.pending_withdrawal, None);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("underwriter1", &[]),
+            ExecuteMsg::RequestWithdrawal { shares: Uint128::new(400) },
+        ).unwrap();
+        let res = execute(deps.as_mut(), after_cooldown(), mock_info("underwriter1", &[]), ExecuteMsg::CompleteWithdrawal {}).unwrap();
+        assert!(res.attributes.iter().any(|a| a.key == "amount" && a.value == "400"));
+        assert_eq!(pool(&deps).balance, Uint128::new(602));
+    }
+}
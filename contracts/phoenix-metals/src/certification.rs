@@ -0,0 +1,71 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::MetalsError;
+
+const MAX_CERT_NUMBER_LENGTH: usize = 32;
+const MAX_DESIGNATION_LENGTH: usize = 64;
+// Sheldon scale, 1 (poor) to 70 (perfect)
+const MAX_GRADE: u8 = 70;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GradingService {
+    Pcgs,
+    Ngc,
+    Anacs,
+    Icg,
+}
+
+impl GradingService {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GradingService::Pcgs => "pcgs",
+            GradingService::Ngc => "ngc",
+            GradingService::Anacs => "anacs",
+            GradingService::Icg => "icg",
+        }
+    }
+}
+
+// Third-party grading of a numismatic item, as printed on the holder
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Certification {
+    pub service: GradingService,
+    pub cert_number: String,
+    pub grade: u8,
+    // Strike and label qualifiers such as "MS", "PF Ultra Cameo" or "First Strike"
+    pub designation: Option<String>,
+}
+
+// Cert numbers are matched exactly, so only one spelling of each is accepted
+fn validate_cert_number(cert_number: &str) -> Result<(), MetalsError> {
+    let valid = !cert_number.is_empty()
+        && cert_number.len() <= MAX_CERT_NUMBER_LENGTH
+        && cert_number
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase() || c == '-');
+    if !valid {
+        return Err(MetalsError::InvalidCertNumber {
+            max: MAX_CERT_NUMBER_LENGTH,
+        });
+    }
+    Ok(())
+}
+
+impl Certification {
+    pub fn validate(&self) -> Result<(), MetalsError> {
+        validate_cert_number(&self.cert_number)?;
+        if self.grade == 0 || self.grade > MAX_GRADE {
+            return Err(MetalsError::InvalidGrade { grade: self.grade });
+        }
+        if let Some(designation) = &self.designation {
+            if designation.trim().is_empty() || designation.len() > MAX_DESIGNATION_LENGTH {
+                return Err(MetalsError::InvalidDesignation {
+                    max: MAX_DESIGNATION_LENGTH,
+                });
+            }
+        }
+        Ok(())
+    }
+}
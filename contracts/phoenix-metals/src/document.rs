@@ -0,0 +1,78 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::error::MetalsError;
+
+const SHA256_HEX_LENGTH: usize = 64;
+const CID_V0_LENGTH: usize = 46;
+const MAX_CID_LENGTH: usize = 128;
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentKind {
+    Photo,
+    AssayCertificate,
+    Invoice,
+    ShippingLabel,
+}
+
+// Fingerprint of a document kept off chain
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentHash {
+    // Lowercase hex digest
+    Sha256(String),
+    // IPFS content identifier, v0 (base58 "Qm...") or v1 (base32 "b...")
+    Cid(String),
+}
+
+impl ContentHash {
+    pub fn validate(&self) -> Result<(), MetalsError> {
+        let valid = match self {
+            ContentHash::Sha256(digest) => {
+                digest.len() == SHA256_HEX_LENGTH
+                    && digest
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+            }
+            ContentHash::Cid(cid) if cid.starts_with("Qm") => {
+                cid.len() == CID_V0_LENGTH && cid.chars().all(|c| BASE58_ALPHABET.contains(c))
+            }
+            ContentHash::Cid(cid) => {
+                cid.len() > 1
+                    && cid.len() <= MAX_CID_LENGTH
+                    && cid.starts_with('b')
+                    && cid
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || ('2'..='7').contains(&c))
+            }
+        };
+        if !valid {
+            return Err(MetalsError::InvalidContentHash);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DocumentKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            DocumentKind::Photo => "photo",
+            DocumentKind::AssayCertificate => "assay_certificate",
+            DocumentKind::Invoice => "invoice",
+            DocumentKind::ShippingLabel => "shipping_label",
+        };
+        f.write_str(name)
+    }
+}
+
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ContentHash::Sha256(digest) => write!(f, "sha256:{}", digest),
+            ContentHash::Cid(cid) => write!(f, "cid:{}", cid),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MetalsError {
+    #[error("Weight must be greater than zero")]
+    ZeroWeight,
+
+    #[error("Fineness must be between 1 and 10000 parts per ten thousand, got {fineness}")]
+    InvalidFineness { fineness: u16 },
+
+    #[error("{form} is not a {metal} product")]
+    FormMismatch { metal: String, form: String },
+
+    #[error("Mint or refiner must be 1-{max} characters")]
+    InvalidMint { max: usize },
+
+    #[error("Year {year} is out of range")]
+    InvalidYear { year: u16 },
+
+    #[error("Metal content calculation overflow")]
+    Overflow,
+
+    #[error("Cert number must be 1-{max} digits, capital letters or dashes")]
+    InvalidCertNumber { max: usize },
+
+    #[error("Grade {grade} is not on the 1-70 scale")]
+    InvalidGrade { grade: u8 },
+
+    #[error("Designation must be 1-{max} characters")]
+    InvalidDesignation { max: usize },
+
+    #[error("Serial number must be 1-{max} characters")]
+    InvalidSerial { max: usize },
+
+    #[error("Serial number requires the refiner in `mint`")]
+    SerialWithoutRefiner,
+
+    #[error("Content hash must be a hex sha256 digest or an IPFS CID")]
+    InvalidContentHash,
+}
+
+// Lets contracts that return StdResult use `?` on metadata checks
+impl From<MetalsError> for StdError {
+    fn from(err: MetalsError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
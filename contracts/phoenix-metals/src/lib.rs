@@ -0,0 +1,16 @@
+pub mod certification;
+pub mod document;
+pub mod error;
+pub mod listing;
+pub mod metal;
+pub mod weight;
+
+// Re-export key types for easier access
+pub use crate::certification::{Certification, GradingService};
+pub use crate::document::{ContentHash, DocumentKind};
+pub use crate::error::MetalsError;
+pub use crate::listing::{canonical_serial_key, ListingMetadata, FINENESS_DENOMINATOR};
+pub use crate::metal::{Metal, ProductForm};
+pub use crate::weight::{Weight, WeightUnit};
+#[cfg(test)]
+mod tests;
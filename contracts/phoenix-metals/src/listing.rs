@@ -0,0 +1,119 @@
+use cosmwasm_std::{Decimal, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::certification::Certification;
+use crate::error::MetalsError;
+use crate::metal::{Metal, ProductForm};
+use crate::weight::Weight;
+
+// Fineness is in parts per ten thousand, 9999 = .9999 fine
+pub const FINENESS_DENOMINATOR: u16 = 10_000;
+
+const MAX_MINT_LENGTH: usize = 64;
+const MAX_SERIAL_LENGTH: usize = 64;
+// Bounds for a plausible minting year
+const MIN_YEAR: u16 = 1700;
+const MAX_YEAR: u16 = 2200;
+
+// The same bar listed as "a 123456" by "valcambi" and "A123456" by "Valcambi" has
+// one key: both parts in ASCII uppercase, whitespace dropped from the serial and
+// collapsed to single spaces in the refiner name
+pub fn canonical_serial_key(refiner: &str, serial: &str) -> (String, String) {
+    let refiner = refiner.split_whitespace().collect::<Vec<_>>().join(" ");
+    let serial: String = serial.chars().filter(|c| !c.is_whitespace()).collect();
+    (refiner.to_ascii_uppercase(), serial.to_ascii_uppercase())
+}
+
+// What a precious-metal listing is, checked when the listing is created
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ListingMetadata {
+    pub metal: Metal,
+    pub form: ProductForm,
+    pub weight: Weight,
+    pub fineness: u16,
+    // Mint or refiner that produced the item
+    pub mint: Option<String>,
+    pub year: Option<u16>,
+    // Grading for numismatic items, absent for bullion
+    #[serde(default)]
+    pub certification: Option<Certification>,
+    // Serial number stamped by the refiner, identifies the bar together with `mint`
+    #[serde(default)]
+    pub serial: Option<String>,
+}
+
+impl ListingMetadata {
+    pub fn validate(&self) -> Result<(), MetalsError> {
+        if self.weight.amount.is_zero() {
+            return Err(MetalsError::ZeroWeight);
+        }
+        if self.fineness == 0 || self.fineness > FINENESS_DENOMINATOR {
+            return Err(MetalsError::InvalidFineness {
+                fineness: self.fineness,
+            });
+        }
+        if self.form == ProductForm::JunkSilver && self.metal != Metal::Silver {
+            return Err(MetalsError::FormMismatch {
+                metal: self.metal.to_string(),
+                form: self.form.to_string(),
+            });
+        }
+        if let Some(mint) = &self.mint {
+            if mint.trim().is_empty() || mint.len() > MAX_MINT_LENGTH {
+                return Err(MetalsError::InvalidMint {
+                    max: MAX_MINT_LENGTH,
+                });
+            }
+        }
+        if let Some(year) = self.year {
+            if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+                return Err(MetalsError::InvalidYear { year });
+            }
+        }
+        if let Some(certification) = &self.certification {
+            certification.validate()?;
+        }
+        if let Some(serial) = &self.serial {
+            if serial.trim().is_empty() || serial.len() > MAX_SERIAL_LENGTH {
+                return Err(MetalsError::InvalidSerial {
+                    max: MAX_SERIAL_LENGTH,
+                });
+            }
+            if self.mint.is_none() {
+                return Err(MetalsError::SerialWithoutRefiner);
+            }
+        }
+        Ok(())
+    }
+
+    // (refiner, serial) identifying a serialized item across listings
+    pub fn serial_key(&self) -> Option<(String, String)> {
+        match (&self.mint, &self.serial) {
+            (Some(mint), Some(serial)) => Some(canonical_serial_key(mint, serial)),
+            _ => None,
+        }
+    }
+
+    // Troy ounces of pure metal, the gross weight times the fineness
+    pub fn pure_troy_ounces(&self) -> Result<Decimal, MetalsError> {
+        let fineness = Decimal::from_ratio(self.fineness, FINENESS_DENOMINATOR);
+        self.weight
+            .troy_ounces()?
+            .checked_mul(fineness)
+            .map_err(|_| MetalsError::Overflow)
+    }
+
+    // What `price` for the whole item pays per troy ounce of pure metal
+    pub fn price_per_troy_ounce(&self, price: Uint128) -> Result<Decimal, MetalsError> {
+        let ounces = self.pure_troy_ounces()?;
+        if ounces.is_zero() {
+            return Err(MetalsError::ZeroWeight);
+        }
+        // Both sides at the decimal's fixed point so the ratio keeps full precision
+        let price = price
+            .checked_mul(Decimal::one().atomics())
+            .map_err(|_| MetalsError::Overflow)?;
+        Decimal::checked_from_ratio(price, ounces.atomics()).map_err(|_| MetalsError::Overflow)
+    }
+}
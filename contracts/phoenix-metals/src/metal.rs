@@ -0,0 +1,53 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Metal {
+    Gold,
+    Silver,
+    Platinum,
+    Palladium,
+    Copper,
+    Rhodium,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductForm {
+    Bar,
+    Coin,
+    // Coin-like pieces from private mints, no face value
+    Round,
+    Ingot,
+    // Pre-1965 US 90% silver coinage, traded by metal content
+    JunkSilver,
+}
+
+impl fmt::Display for Metal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Metal::Gold => "gold",
+            Metal::Silver => "silver",
+            Metal::Platinum => "platinum",
+            Metal::Palladium => "palladium",
+            Metal::Copper => "copper",
+            Metal::Rhodium => "rhodium",
+        };
+        f.write_str(name)
+    }
+}
+
+impl fmt::Display for ProductForm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ProductForm::Bar => "bar",
+            ProductForm::Coin => "coin",
+            ProductForm::Round => "round",
+            ProductForm::Ingot => "ingot",
+            ProductForm::JunkSilver => "junk silver",
+        };
+        f.write_str(name)
+    }
+}
@@ -0,0 +1,251 @@
+use cosmwasm_std::{from_json, to_json_vec, Decimal, Uint128};
+
+use crate::{
+    Certification, ContentHash, GradingService, ListingMetadata, Metal, MetalsError, ProductForm,
+    Weight, WeightUnit,
+};
+
+fn eagle() -> ListingMetadata {
+    ListingMetadata {
+        metal: Metal::Gold,
+        form: ProductForm::Coin,
+        weight: Weight::new(Decimal::one(), WeightUnit::TroyOunce),
+        fineness: 9167,
+        mint: Some("US Mint".to_string()),
+        year: Some(2024),
+        certification: None,
+        serial: None,
+    }
+}
+
+#[test]
+fn test_metadata_validation() {
+    assert_eq!(eagle().validate(), Ok(()));
+
+    let zero_weight = ListingMetadata {
+        weight: Weight::new(Decimal::zero(), WeightUnit::Gram),
+        ..eagle()
+    };
+    assert_eq!(zero_weight.validate(), Err(MetalsError::ZeroWeight));
+
+    for fineness in [0, 10_001] {
+        let metadata = ListingMetadata {
+            fineness,
+            ..eagle()
+        };
+        assert_eq!(
+            metadata.validate(),
+            Err(MetalsError::InvalidFineness { fineness })
+        );
+    }
+
+    let junk_gold = ListingMetadata {
+        form: ProductForm::JunkSilver,
+        ..eagle()
+    };
+    assert!(matches!(
+        junk_gold.validate(),
+        Err(MetalsError::FormMismatch { .. })
+    ));
+
+    let blank_mint = ListingMetadata {
+        mint: Some("  ".to_string()),
+        ..eagle()
+    };
+    assert_eq!(
+        blank_mint.validate(),
+        Err(MetalsError::InvalidMint { max: 64 })
+    );
+
+    let year = ListingMetadata {
+        year: Some(1066),
+        ..eagle()
+    };
+    assert_eq!(
+        year.validate(),
+        Err(MetalsError::InvalidYear { year: 1066 })
+    );
+}
+
+#[test]
+fn test_metadata_wire_format() {
+    let metadata = ListingMetadata {
+        metal: Metal::Silver,
+        form: ProductForm::JunkSilver,
+        weight: Weight::new(Decimal::percent(71_500), WeightUnit::TroyOunce),
+        fineness: 9000,
+        mint: None,
+        year: None,
+        certification: None,
+        serial: None,
+    };
+    let json = String::from_utf8(to_json_vec(&metadata).unwrap()).unwrap();
+    assert_eq!(
+        json,
+        r#"{"metal":"silver","form":"junk_silver","weight":{"amount":"715","unit":"troy_ounce"},"fineness":9000,"mint":null,"year":null,"certification":null,"serial":null}"#
+    );
+    assert_eq!(
+        from_json::<ListingMetadata>(json.as_bytes()).unwrap(),
+        metadata
+    );
+}
+
+#[test]
+fn test_pure_content_and_price_per_ounce() {
+    // 1 oz at 22 karat
+    assert_eq!(
+        eagle().pure_troy_ounces().unwrap(),
+        Decimal::permille(9167) / Uint128::new(10)
+    );
+
+    // A kilo bar is 32.1507... troy ounces
+    let kilo = Weight::new(Decimal::one(), WeightUnit::Kilogram);
+    assert_eq!(
+        kilo.troy_ounces().unwrap().to_string(),
+        "32.150746568627980522"
+    );
+    let gram = Weight::new(
+        Decimal::from_ratio(311_034_768u128, 10_000_000u128),
+        WeightUnit::Gram,
+    );
+    assert_eq!(gram.troy_ounces().unwrap(), Decimal::one());
+
+    // $1.40 face of 90% silver holds 1 oz of silver, so it prices per ounce at par
+    let dimes = ListingMetadata {
+        metal: Metal::Silver,
+        form: ProductForm::JunkSilver,
+        weight: Weight::new(Decimal::from_ratio(10u128, 9u128), WeightUnit::TroyOunce),
+        fineness: 9000,
+        mint: None,
+        year: None,
+        certification: None,
+        serial: None,
+    };
+    let per_ounce = dimes
+        .price_per_troy_ounce(Uint128::new(30_000_000))
+        .unwrap();
+    assert!(per_ounce > Decimal::from_ratio(29_999_999u128, 1u128));
+    assert!(per_ounce < Decimal::from_ratio(30_000_001u128, 1u128));
+
+    let dust = ListingMetadata {
+        weight: Weight::new(
+            Decimal::from_ratio(1u128, 1_000_000_000_000_000_000u128),
+            WeightUnit::Gram,
+        ),
+        ..eagle()
+    };
+    assert_eq!(
+        dust.price_per_troy_ounce(Uint128::new(1)),
+        Err(MetalsError::ZeroWeight)
+    );
+}
+
+#[test]
+fn test_certification_validation() {
+    let graded = |cert_number: &str, grade: u8, designation: Option<&str>| ListingMetadata {
+        certification: Some(Certification {
+            service: GradingService::Pcgs,
+            cert_number: cert_number.to_string(),
+            grade,
+            designation: designation.map(|d| d.to_string()),
+        }),
+        ..eagle()
+    };
+    assert_eq!(
+        graded("44871290", 70, Some("MS First Strike")).validate(),
+        Ok(())
+    );
+    assert_eq!(graded("2174-33", 69, None).validate(), Ok(()));
+
+    for cert_number in ["", "4487 1290", "ab123", &"9".repeat(33)] {
+        assert_eq!(
+            graded(cert_number, 70, None).validate(),
+            Err(MetalsError::InvalidCertNumber { max: 32 })
+        );
+    }
+    for grade in [0, 71] {
+        assert_eq!(
+            graded("44871290", grade, None).validate(),
+            Err(MetalsError::InvalidGrade { grade })
+        );
+    }
+    assert_eq!(
+        graded("44871290", 70, Some(" ")).validate(),
+        Err(MetalsError::InvalidDesignation { max: 64 })
+    );
+
+    // Listings stored before certification existed still load
+    let json = r#"{"metal":"gold","form":"bar","weight":{"amount":"1","unit":"kilogram"},"fineness":9999,"mint":null,"year":null}"#;
+    assert_eq!(
+        from_json::<ListingMetadata>(json.as_bytes())
+            .unwrap()
+            .certification,
+        None
+    );
+}
+
+#[test]
+fn test_serial_number() {
+    let bar = ListingMetadata {
+        metal: Metal::Gold,
+        form: ProductForm::Bar,
+        weight: Weight::new(Decimal::one(), WeightUnit::Kilogram),
+        fineness: 9999,
+        mint: Some("Valcambi".to_string()),
+        year: None,
+        certification: None,
+        serial: Some("A123456".to_string()),
+    };
+    assert_eq!(bar.validate(), Ok(()));
+    let key = Some(("VALCAMBI".to_string(), "A123456".to_string()));
+    assert_eq!(bar.serial_key(), key);
+    let restamped = ListingMetadata {
+        mint: Some(" valcambi ".to_string()),
+        serial: Some("a 123 456".to_string()),
+        ..bar.clone()
+    };
+    assert_eq!(restamped.serial_key(), key);
+    assert_eq!(eagle().serial_key(), None);
+
+    let anonymous = ListingMetadata {
+        mint: None,
+        ..bar.clone()
+    };
+    assert_eq!(anonymous.validate(), Err(MetalsError::SerialWithoutRefiner));
+    assert_eq!(anonymous.serial_key(), None);
+
+    let blank = ListingMetadata {
+        serial: Some(" ".to_string()),
+        ..bar
+    };
+    assert_eq!(
+        blank.validate(),
+        Err(MetalsError::InvalidSerial { max: 64 })
+    );
+}
+
+#[test]
+fn test_content_hash_validation() {
+    let digest = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+    assert_eq!(ContentHash::Sha256(digest.to_string()).validate(), Ok(()));
+    assert_eq!(
+        ContentHash::Cid("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string()).validate(),
+        Ok(())
+    );
+    assert_eq!(
+        ContentHash::Cid("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string())
+            .validate(),
+        Ok(())
+    );
+
+    for invalid in [
+        ContentHash::Sha256(digest.to_uppercase()),
+        ContentHash::Sha256(digest[1..].to_string()),
+        // "0" is not in the base58 alphabet
+        ContentHash::Cid("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0".to_string()),
+        ContentHash::Cid("bafy BEIG".to_string()),
+        ContentHash::Cid("".to_string()),
+    ] {
+        assert_eq!(invalid.validate(), Err(MetalsError::InvalidContentHash));
+    }
+}
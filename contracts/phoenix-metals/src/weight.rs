@@ -0,0 +1,48 @@
+use cosmwasm_std::{Decimal, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::MetalsError;
+
+// A troy ounce is exactly 31.1034768 grams
+const GRAMS_PER_TROY_OUNCE_E7: u128 = 311_034_768;
+const GRAM_E7: u128 = 10_000_000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightUnit {
+    TroyOunce,
+    Gram,
+    Kilogram,
+}
+
+// Gross weight of the item as stated by the seller
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Weight {
+    pub amount: Decimal,
+    pub unit: WeightUnit,
+}
+
+impl Weight {
+    pub fn new(amount: Decimal, unit: WeightUnit) -> Self {
+        Weight { amount, unit }
+    }
+
+    pub fn troy_ounces(&self) -> Result<Decimal, MetalsError> {
+        let grams = match self.unit {
+            WeightUnit::TroyOunce => return Ok(self.amount),
+            WeightUnit::Gram => self.amount,
+            WeightUnit::Kilogram => self
+                .amount
+                .checked_mul(Decimal::from_ratio(1000u128, 1u128))
+                .map_err(|_| MetalsError::Overflow)?,
+        };
+        // grams * 1e7 / 311034768, with both sides at the decimal's fixed point
+        let numerator = grams
+            .atomics()
+            .checked_mul(Uint128::new(GRAM_E7))
+            .map_err(|_| MetalsError::Overflow)?;
+        let denominator = Uint128::new(GRAMS_PER_TROY_OUNCE_E7) * Decimal::one().atomics();
+        Decimal::checked_from_ratio(numerator, denominator).map_err(|_| MetalsError::Overflow)
+    }
+}
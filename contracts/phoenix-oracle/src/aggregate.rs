@@ -0,0 +1,83 @@
+use cosmwasm_std::{Decimal, StdError, StdResult, Uint128};
+
+use crate::state::Observation;
+
+const MAX_BPS: u128 = 10_000;
+// Decimal places of `Decimal` atomics
+const DECIMAL_PLACES: u32 = 18;
+
+// Middle report, or the mean of the two middle reports for an even count
+pub fn median(mut prices: Vec<Decimal>) -> Option<Decimal> {
+    if prices.is_empty() {
+        return None;
+    }
+    prices.sort();
+    let middle = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        Some(prices[middle])
+    } else {
+        Some((prices[middle - 1] + prices[middle]) * Decimal::percent(50))
+    }
+}
+
+pub fn exceeds_deviation(current: Decimal, price: Decimal, max_deviation_bps: u16) -> bool {
+    let difference = if price > current {
+        price - current
+    } else {
+        current - price
+    };
+    difference > current * Decimal::from_ratio(max_deviation_bps as u128, MAX_BPS)
+}
+
+// Appends the newly published price and drops observations that no longer
+// affect the window. The last observation before the window start is kept, as
+// it sets the price the window opens at.
+pub fn observe(observations: &mut Vec<Observation>, price: Decimal, now: u64, window: u64) {
+    match observations.last_mut() {
+        Some(last) if last.timestamp == now => last.price = price,
+        _ => observations.push(Observation {
+            price,
+            timestamp: now,
+        }),
+    }
+    let start = now.saturating_sub(window);
+    let opening = observations
+        .iter()
+        .rposition(|observation| observation.timestamp <= start)
+        .unwrap_or(0);
+    observations.drain(..opening);
+}
+
+// Time-weighted average over the window ending at `now`, each price weighted by
+// how long it stood. Returns the average and where the covered period starts,
+// which is later than the window start while the feed is younger than the window.
+pub fn twap(
+    observations: &[Observation],
+    now: u64,
+    window: u64,
+) -> StdResult<Option<(Decimal, u64)>> {
+    let start = now.saturating_sub(window);
+    let from = match observations.first() {
+        Some(first) => first.timestamp.max(start),
+        None => return Ok(None),
+    };
+    if from >= now {
+        // Only a price published this block; there is no period to average over
+        return Ok(observations.last().map(|last| (last.price, now)));
+    }
+
+    let mut weighted = Uint128::zero();
+    for (i, observation) in observations.iter().enumerate() {
+        let until = observations.get(i + 1).map_or(now, |next| next.timestamp);
+        let since = observation.timestamp.max(start);
+        if until <= since {
+            continue;
+        }
+        let seconds = Uint128::from(until - since);
+        weighted = weighted.checked_add(observation.price.atomics().checked_mul(seconds)?)?;
+    }
+    let average = weighted / Uint128::from(now - from);
+    let average = Decimal::from_atomics(average, DECIMAL_PLACES)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    Ok(Some((average, from)))
+}
@@ -0,0 +1,445 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdError, StdResult, Storage,
+};
+use cw2::set_contract_version;
+use phoenix_metals::Metal;
+
+use crate::aggregate;
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, PriceInput, PriceResponse, PricesResponse,
+    QueryMsg, ReportersResponse, TwapResponse,
+};
+use crate::state::{
+    Config, Observation, Report, SpotPrice, CONFIG, OBSERVATIONS, PRICES, REPORTERS, REPORTS,
+};
+
+const CONTRACT_NAME: &str = "crates.io:phoenix-oracle";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const SUPPORTED_METALS: [Metal; 4] = [
+    Metal::Gold,
+    Metal::Silver,
+    Metal::Platinum,
+    Metal::Palladium,
+];
+const DEFAULT_MAX_STALENESS_SECONDS: u64 = 10 * 60;
+const DEFAULT_MAX_DEVIATION_BPS: u16 = 500;
+const DEFAULT_TWAP_WINDOW_SECONDS: u64 = 3600;
+const MAX_BPS: u16 = 10_000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        admin: deps.api.addr_validate(&msg.admin)?,
+        denoms: msg.denoms,
+        max_staleness_seconds: msg
+            .max_staleness_seconds
+            .unwrap_or(DEFAULT_MAX_STALENESS_SECONDS),
+        max_deviation_bps: msg.max_deviation_bps.unwrap_or(DEFAULT_MAX_DEVIATION_BPS),
+        min_reporters: msg.min_reporters,
+        twap_window_seconds: msg
+            .twap_window_seconds
+            .unwrap_or(DEFAULT_TWAP_WINDOW_SECONDS),
+    };
+    validate_config(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    for reporter in &msg.reporters {
+        let reporter = deps.api.addr_validate(reporter)?;
+        REPORTERS.save(deps.storage, &reporter, &true)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("admin", config.admin)
+        .add_attribute("reporters", msg.reporters.len().to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SubmitPrices { prices } => execute_submit_prices(deps, env, info, prices),
+        ExecuteMsg::AddReporter { address } => execute_add_reporter(deps, info, address),
+        ExecuteMsg::RemoveReporter { address } => execute_remove_reporter(deps, env, info, address),
+        ExecuteMsg::UpdateConfig {
+            admin,
+            denoms,
+            max_staleness_seconds,
+            max_deviation_bps,
+            min_reporters,
+            twap_window_seconds,
+        } => {
+            let mut config = CONFIG.load(deps.storage)?;
+            if info.sender != config.admin {
+                return Err(ContractError::Unauthorized {});
+            }
+            if let Some(admin) = admin {
+                config.admin = deps.api.addr_validate(&admin)?;
+            }
+            config.denoms = denoms.unwrap_or(config.denoms);
+            config.max_staleness_seconds =
+                max_staleness_seconds.unwrap_or(config.max_staleness_seconds);
+            config.max_deviation_bps = max_deviation_bps.unwrap_or(config.max_deviation_bps);
+            config.min_reporters = min_reporters.unwrap_or(config.min_reporters);
+            config.twap_window_seconds = twap_window_seconds.unwrap_or(config.twap_window_seconds);
+            validate_config(&config)?;
+            CONFIG.save(deps.storage, &config)?;
+
+            Ok(Response::new()
+                .add_attribute("method", "update_config")
+                .add_attribute("admin", config.admin))
+        }
+    }
+}
+
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    let denoms_valid =
+        !config.denoms.is_empty() && config.denoms.iter().all(|denom| !denom.trim().is_empty());
+    if !denoms_valid
+        || config.max_staleness_seconds == 0
+        || config.max_deviation_bps == 0
+        || config.max_deviation_bps > MAX_BPS
+        || config.min_reporters == 0
+        || config.twap_window_seconds == 0
+    {
+        return Err(ContractError::InvalidConfig {});
+    }
+    Ok(())
+}
+
+// ============================================================
+// REPORTING
+// ============================================================
+
+fn feed_metal(metal: Metal) -> Result<String, ContractError> {
+    if !SUPPORTED_METALS.contains(&metal) {
+        return Err(ContractError::UnsupportedMetal {
+            metal: metal.to_string(),
+        });
+    }
+    Ok(metal.to_string())
+}
+
+fn is_fresh(config: &Config, timestamp: u64, now: u64) -> bool {
+    now.saturating_sub(timestamp) <= config.max_staleness_seconds
+}
+
+fn execute_submit_prices(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    prices: Vec<PriceInput>,
+) -> Result<Response, ContractError> {
+    if !REPORTERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or(false)
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+
+    let mut published = vec![];
+    for input in &prices {
+        let metal = feed_metal(input.metal)?;
+        if !config.denoms.contains(&input.denom) {
+            return Err(ContractError::UnsupportedDenom {
+                denom: input.denom.clone(),
+            });
+        }
+        if input.price.is_zero() {
+            return Err(ContractError::ZeroPrice {});
+        }
+        if submit(
+            deps.storage,
+            &config,
+            &metal,
+            &input.denom,
+            &info.sender,
+            input.price,
+            now,
+        )? {
+            published.push(format!("{}/{}", metal, input.denom));
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "submit_prices")
+        .add_attribute("reporter", info.sender)
+        .add_attribute("submitted", prices.len().to_string())
+        .add_attribute("published", published.join(",")))
+}
+
+// Records the report and republishes the feed once enough fresh reports are in.
+// Returns whether a price was published.
+fn submit(
+    storage: &mut dyn Storage,
+    config: &Config,
+    metal: &str,
+    denom: &str,
+    reporter: &Addr,
+    price: Decimal,
+    now: u64,
+) -> Result<bool, ContractError> {
+    // Guard against a single report dragging a live price too far
+    if let Some(current) = PRICES.may_load(storage, (metal, denom))? {
+        let live = is_fresh(config, current.updated_at, now);
+        if live && aggregate::exceeds_deviation(current.price, price, config.max_deviation_bps) {
+            return Err(ContractError::PriceDeviation {
+                price,
+                current: current.price,
+                max_deviation_bps: config.max_deviation_bps,
+            });
+        }
+    }
+    REPORTS.save(
+        storage,
+        (metal, denom, reporter),
+        &Report {
+            price,
+            reported_at: now,
+        },
+    )?;
+
+    let spot = match fresh_median(storage, config, metal, denom, now)? {
+        Some(spot) => spot,
+        None => return Ok(false),
+    };
+    PRICES.save(storage, (metal, denom), &spot)?;
+    let mut observations = OBSERVATIONS
+        .may_load(storage, (metal, denom))?
+        .unwrap_or_default();
+    aggregate::observe(
+        &mut observations,
+        spot.price,
+        now,
+        config.twap_window_seconds,
+    );
+    OBSERVATIONS.save(storage, (metal, denom), &observations)?;
+    Ok(true)
+}
+
+// Median of the feed's fresh reports, or None while there are fewer than min_reporters
+fn fresh_median(
+    storage: &dyn Storage,
+    config: &Config,
+    metal: &str,
+    denom: &str,
+    now: u64,
+) -> StdResult<Option<SpotPrice>> {
+    let fresh = REPORTS
+        .prefix((metal, denom))
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, report)| report))
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .filter(|report| is_fresh(config, report.reported_at, now))
+        .map(|report| report.price)
+        .collect::<Vec<_>>();
+    let reporters = fresh.len() as u32;
+    if reporters < config.min_reporters {
+        return Ok(None);
+    }
+    Ok(aggregate::median(fresh).map(|price| SpotPrice {
+        price,
+        updated_at: now,
+        reporters,
+    }))
+}
+
+// ============================================================
+// REPORTERS
+// ============================================================
+
+fn execute_add_reporter(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let reporter = deps.api.addr_validate(&address)?;
+    REPORTERS.save(deps.storage, &reporter, &true)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "add_reporter")
+        .add_attribute("reporter", reporter))
+}
+
+// Drops the reporter and its reports. Every feed it reported on is
+// recomputed from the remaining fresh reports, or withdrawn when too few are
+// left, and its TWAP restarts so the removed prices carry no weight.
+fn execute_remove_reporter(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let reporter = deps.api.addr_validate(&address)?;
+    REPORTERS.remove(deps.storage, &reporter);
+
+    let now = env.block.time.seconds();
+    let mut republished = vec![];
+    let mut withdrawn = vec![];
+    for metal in SUPPORTED_METALS {
+        let metal = metal.to_string();
+        for denom in &config.denoms {
+            let key = (metal.as_str(), denom.as_str());
+            if !REPORTS.has(deps.storage, (key.0, key.1, &reporter)) {
+                continue;
+            }
+            REPORTS.remove(deps.storage, (key.0, key.1, &reporter));
+            if !PRICES.has(deps.storage, key) {
+                continue;
+            }
+
+            let feed = format!("{}/{}", metal, denom);
+            match fresh_median(deps.storage, &config, key.0, key.1, now)? {
+                Some(spot) => {
+                    let restart = vec![Observation {
+                        price: spot.price,
+                        timestamp: now,
+                    }];
+                    PRICES.save(deps.storage, key, &spot)?;
+                    OBSERVATIONS.save(deps.storage, key, &restart)?;
+                    republished.push(feed);
+                }
+                None => {
+                    PRICES.remove(deps.storage, key);
+                    OBSERVATIONS.remove(deps.storage, key);
+                    withdrawn.push(feed);
+                }
+            }
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "remove_reporter")
+        .add_attribute("reporter", reporter)
+        .add_attribute("republished", republished.join(","))
+        .add_attribute("withdrawn", withdrawn.join(",")))
+}
+
+// ============================================================
+// QUERIES
+// ============================================================
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_json_binary(&ConfigResponse {
+                admin: config.admin,
+                denoms: config.denoms,
+                max_staleness_seconds: config.max_staleness_seconds,
+                max_deviation_bps: config.max_deviation_bps,
+                min_reporters: config.min_reporters,
+                twap_window_seconds: config.twap_window_seconds,
+            })
+        }
+        QueryMsg::Price { metal, denom } => {
+            let price = query_price(deps, &env, metal, denom)?
+                .ok_or_else(|| StdError::generic_err("No price published"))?;
+            if price.stale {
+                return Err(StdError::generic_err(format!(
+                    "Price for {} in {} is stale",
+                    price.metal, price.denom
+                )));
+            }
+            to_json_binary(&price)
+        }
+        QueryMsg::Twap { metal, denom } => to_json_binary(&query_twap(deps, &env, metal, denom)?),
+        QueryMsg::Prices {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let mut prices = vec![];
+            for metal in SUPPORTED_METALS {
+                for denom in &config.denoms {
+                    if let Some(price) = query_price(deps, &env, metal, denom.clone())? {
+                        prices.push(price);
+                    }
+                }
+            }
+            to_json_binary(&PricesResponse { prices })
+        }
+        QueryMsg::Reporters {} => {
+            let reporters = REPORTERS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&ReportersResponse { reporters })
+        }
+    }
+}
+
+fn query_price(
+    deps: Deps,
+    env: &Env,
+    metal: Metal,
+    denom: String,
+) -> StdResult<Option<PriceResponse>> {
+    let config = CONFIG.load(deps.storage)?;
+    let key = metal.to_string();
+    let price = match PRICES.may_load(deps.storage, (key.as_str(), denom.as_str()))? {
+        Some(price) => price,
+        None => return Ok(None),
+    };
+    Ok(Some(PriceResponse {
+        metal,
+        stale: !is_fresh(&config, price.updated_at, env.block.time.seconds()),
+        denom,
+        price: price.price,
+        updated_at: price.updated_at,
+        reporters: price.reporters,
+    }))
+}
+
+// A stale feed has no meaningful average either
+fn query_twap(deps: Deps, env: &Env, metal: Metal, denom: String) -> StdResult<TwapResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let key = metal.to_string();
+    let spot = PRICES
+        .may_load(deps.storage, (key.as_str(), denom.as_str()))?
+        .filter(|spot| is_fresh(&config, spot.updated_at, now));
+    if spot.is_none() {
+        return Err(StdError::generic_err(format!(
+            "No fresh price for {} in {}",
+            metal, denom
+        )));
+    }
+
+    let observations = OBSERVATIONS
+        .may_load(deps.storage, (key.as_str(), denom.as_str()))?
+        .unwrap_or_default();
+    let (price, from) = aggregate::twap(&observations, now, config.twap_window_seconds)?
+        .ok_or_else(|| StdError::generic_err("No observations"))?;
+    Ok(TwapResponse {
+        metal,
+        denom,
+        price,
+        from,
+        to: now,
+    })
+}
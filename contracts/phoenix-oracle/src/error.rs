@@ -0,0 +1,31 @@
+use cosmwasm_std::{Decimal, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid oracle config")]
+    InvalidConfig {},
+
+    // Submission errors
+    #[error("No price feed for {metal}")]
+    UnsupportedMetal { metal: String },
+
+    #[error("No price feed in {denom}")]
+    UnsupportedDenom { denom: String },
+
+    #[error("Price must be greater than zero")]
+    ZeroPrice {},
+
+    #[error("Price {price} deviates more than {max_deviation_bps} bps from the current {current}")]
+    PriceDeviation {
+        price: Decimal,
+        current: Decimal,
+        max_deviation_bps: u16,
+    },
+}
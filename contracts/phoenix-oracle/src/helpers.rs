@@ -0,0 +1,44 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult};
+use phoenix_metals::Metal;
+
+use crate::msg::{PriceResponse, QueryMsg, TwapResponse};
+
+// Address of a deployed spot-price oracle, used by the marketplace contracts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SpotOracle(pub Addr);
+
+impl SpotOracle {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    // Fails when the feed is missing or stale, so callers never act on an old price
+    pub fn price(
+        &self,
+        querier: &QuerierWrapper,
+        metal: Metal,
+        denom: &str,
+    ) -> StdResult<PriceResponse> {
+        let msg = QueryMsg::Price {
+            metal,
+            denom: denom.to_string(),
+        };
+        querier.query_wasm_smart(self.addr(), &msg)
+    }
+
+    pub fn twap(
+        &self,
+        querier: &QuerierWrapper,
+        metal: Metal,
+        denom: &str,
+    ) -> StdResult<TwapResponse> {
+        let msg = QueryMsg::Twap {
+            metal,
+            denom: denom.to_string(),
+        };
+        querier.query_wasm_smart(self.addr(), &msg)
+    }
+}
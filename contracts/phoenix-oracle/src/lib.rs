@@ -0,0 +1,6 @@
+pub mod aggregate;
+pub mod contract;
+pub mod error;
+pub mod helpers;
+pub mod msg;
+pub mod state;
@@ -0,0 +1,106 @@
+use cosmwasm_std::{Addr, Decimal};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub use phoenix_metals::Metal;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub admin: String,
+    pub denoms: Vec<String>,
+    pub reporters: Vec<String>,
+    // Defaults to 10 minutes
+    pub max_staleness_seconds: Option<u64>,
+    // Defaults to 5%
+    pub max_deviation_bps: Option<u16>,
+    // Fresh reports needed to publish; no default so a deployment never
+    // trusts a single reporter by accident
+    pub min_reporters: u32,
+    // Defaults to 1 hour
+    pub twap_window_seconds: Option<u64>,
+}
+
+// Price of one troy ounce in the smallest unit of `denom`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceInput {
+    pub metal: Metal,
+    pub denom: String,
+    pub price: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    // Reporters push one or more prices at once
+    SubmitPrices {
+        prices: Vec<PriceInput>,
+    },
+    AddReporter {
+        address: String,
+    },
+    // Also drops the reporter's reports; the feeds they fed are recomputed or withdrawn
+    RemoveReporter {
+        address: String,
+    },
+    UpdateConfig {
+        admin: Option<String>,
+        denoms: Option<Vec<String>>,
+        max_staleness_seconds: Option<u64>,
+        max_deviation_bps: Option<u16>,
+        min_reporters: Option<u32>,
+        twap_window_seconds: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    // Fails when the feed has no price or it is stale
+    Price { metal: Metal, denom: String },
+    // Time-weighted average over the rolling window
+    Twap { metal: Metal, denom: String },
+    // Every feed with a published price, flagged when stale, for market data views
+    Prices {},
+    Reporters {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub admin: Addr,
+    pub denoms: Vec<String>,
+    pub max_staleness_seconds: u64,
+    pub max_deviation_bps: u16,
+    pub min_reporters: u32,
+    pub twap_window_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceResponse {
+    pub metal: Metal,
+    pub denom: String,
+    pub price: Decimal,
+    pub updated_at: u64,
+    pub reporters: u32,
+    pub stale: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TwapResponse {
+    pub metal: Metal,
+    pub denom: String,
+    pub price: Decimal,
+    // Period the average covers; shorter than the window while the feed is new
+    pub from: u64,
+    pub to: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PricesResponse {
+    pub prices: Vec<PriceResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReportersResponse {
+    pub reporters: Vec<Addr>,
+}
@@ -0,0 +1,51 @@
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub admin: Addr,
+    // Settlement denoms prices are quoted in
+    pub denoms: Vec<String>,
+    // Reports older than this are ignored, and a price not refreshed within it is stale
+    pub max_staleness_seconds: u64,
+    // Largest move from the current price a single report may make, in basis points
+    pub max_deviation_bps: u16,
+    // Fresh reports needed before a price is published
+    pub min_reporters: u32,
+    // Length of the rolling window for the time-weighted average
+    pub twap_window_seconds: u64,
+}
+
+// A reporter's latest price for a feed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Report {
+    pub price: Decimal,
+    pub reported_at: u64,
+}
+
+// Median of the fresh reports, as last published
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SpotPrice {
+    pub price: Decimal,
+    pub updated_at: u64,
+    // Reports the median was taken over
+    pub reporters: u32,
+}
+
+// Published price from `timestamp` until the next observation
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Observation {
+    pub price: Decimal,
+    pub timestamp: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const REPORTERS: Map<&Addr, bool> = Map::new("reporters");
+
+// Feeds are keyed by (metal, denom)
+pub const REPORTS: Map<(&str, &str, &Addr), Report> = Map::new("reports");
+pub const PRICES: Map<(&str, &str), SpotPrice> = Map::new("prices");
+// Observations covering the TWAP window, oldest first
+pub const OBSERVATIONS: Map<(&str, &str), Vec<Observation>> = Map::new("observations");
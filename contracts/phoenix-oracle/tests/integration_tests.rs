@@ -0,0 +1,356 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{from_json, Addr, Decimal, Env, OwnedDeps, Response};
+    use phoenix_oracle::contract::{execute, instantiate, query};
+    use phoenix_oracle::error::ContractError;
+    use phoenix_oracle::msg::{
+        ExecuteMsg, InstantiateMsg, Metal, PriceInput, PriceResponse, PricesResponse, QueryMsg,
+        ReportersResponse, TwapResponse,
+    };
+
+    const DENOM: &str = "urlusd";
+
+    type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+    fn setup(min_reporters: u32, max_staleness_seconds: u64) -> Deps {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            admin: "admin".to_string(),
+            denoms: vec![DENOM.to_string(), "utestcore".to_string()],
+            reporters: vec![
+                "feed_a".to_string(),
+                "feed_b".to_string(),
+                "feed_c".to_string(),
+            ],
+            max_staleness_seconds: Some(max_staleness_seconds),
+            max_deviation_bps: None,
+            min_reporters,
+            twap_window_seconds: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+        deps
+    }
+
+    fn usd(amount: u128) -> Decimal {
+        Decimal::from_ratio(amount, 1u128)
+    }
+
+    // Mock reporter pushing a single price
+    fn report(
+        deps: &mut Deps,
+        env: &Env,
+        reporter: &str,
+        metal: Metal,
+        price: Decimal,
+    ) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::SubmitPrices {
+            prices: vec![PriceInput {
+                metal,
+                denom: DENOM.to_string(),
+                price,
+            }],
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(reporter, &[]), msg)
+    }
+
+    fn published(res: &Response) -> String {
+        res.attributes
+            .iter()
+            .find(|attr| attr.key == "published")
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    fn price(deps: &Deps, env: &Env, metal: Metal) -> PriceResponse {
+        let msg = QueryMsg::Price {
+            metal,
+            denom: DENOM.to_string(),
+        };
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap()
+    }
+
+    fn twap(deps: &Deps, env: &Env, metal: Metal) -> TwapResponse {
+        let msg = QueryMsg::Twap {
+            metal,
+            denom: DENOM.to_string(),
+        };
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_median_across_reporters() {
+        let mut deps = setup(2, 600);
+        let env = mock_env();
+
+        // One report is not enough to publish
+        let res = report(&mut deps, &env, "feed_a", Metal::Gold, usd(2_650)).unwrap();
+        assert_eq!(published(&res), "");
+        let msg = QueryMsg::Price {
+            metal: Metal::Gold,
+            denom: DENOM.to_string(),
+        };
+        assert!(query(deps.as_ref(), env.clone(), msg).is_err());
+
+        // Even count takes the mean of the middle two, odd count the middle one
+        let res = report(&mut deps, &env, "feed_b", Metal::Gold, usd(2_660)).unwrap();
+        assert_eq!(published(&res), "gold/urlusd");
+        assert_eq!(price(&deps, &env, Metal::Gold).price, usd(2_655));
+        report(&mut deps, &env, "feed_c", Metal::Gold, usd(2_700)).unwrap();
+        let gold = price(&deps, &env, Metal::Gold);
+        assert_eq!(gold.price, usd(2_660));
+        assert_eq!(gold.reporters, 3);
+        assert_eq!(gold.updated_at, env.block.time.seconds());
+
+        // A reporter updating its own price replaces its earlier report
+        report(&mut deps, &env, "feed_a", Metal::Gold, usd(2_680)).unwrap();
+        assert_eq!(price(&deps, &env, Metal::Gold).price, usd(2_680));
+
+        let prices: PricesResponse =
+            from_json(query(deps.as_ref(), env, QueryMsg::Prices {}).unwrap()).unwrap();
+        assert_eq!(prices.prices.len(), 1);
+        assert_eq!(prices.prices[0].metal, Metal::Gold);
+        assert!(!prices.prices[0].stale);
+    }
+
+    #[test]
+    fn test_submissions_are_validated() {
+        let mut deps = setup(1, 600);
+        let env = mock_env();
+
+        let err = report(&mut deps, &env, "stranger", Metal::Gold, usd(2_650)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = report(&mut deps, &env, "feed_a", Metal::Copper, usd(4)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedMetal {
+                metal: "copper".to_string()
+            }
+        );
+        let err = report(&mut deps, &env, "feed_a", Metal::Silver, Decimal::zero()).unwrap_err();
+        assert_eq!(err, ContractError::ZeroPrice {});
+        let msg = ExecuteMsg::SubmitPrices {
+            prices: vec![PriceInput {
+                metal: Metal::Gold,
+                denom: "uatom".to_string(),
+                price: usd(300),
+            }],
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info("feed_a", &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedDenom {
+                denom: "uatom".to_string()
+            }
+        );
+
+        // One message can carry every feed
+        let msg = ExecuteMsg::SubmitPrices {
+            prices: vec![
+                PriceInput {
+                    metal: Metal::Gold,
+                    denom: DENOM.to_string(),
+                    price: usd(2_650),
+                },
+                PriceInput {
+                    metal: Metal::Gold,
+                    denom: "utestcore".to_string(),
+                    price: usd(5_300),
+                },
+                PriceInput {
+                    metal: Metal::Platinum,
+                    denom: DENOM.to_string(),
+                    price: usd(980),
+                },
+            ],
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("feed_a", &[]), msg).unwrap();
+        assert_eq!(
+            published(&res),
+            "gold/urlusd,gold/utestcore,platinum/urlusd"
+        );
+
+        let msg = ExecuteMsg::UpdateConfig {
+            admin: None,
+            denoms: None,
+            max_staleness_seconds: None,
+            max_deviation_bps: Some(0),
+            min_reporters: None,
+            twap_window_seconds: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("feed_a", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = execute(deps.as_mut(), env, mock_info("admin", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidConfig {});
+    }
+
+    #[test]
+    fn test_deviation_guard_and_staleness() {
+        let mut deps = setup(1, 600);
+        let mut env = mock_env();
+        report(&mut deps, &env, "feed_a", Metal::Gold, usd(2_650)).unwrap();
+
+        // More than 5% away from a live price is rejected, within it is accepted
+        let err = report(&mut deps, &env, "feed_b", Metal::Gold, usd(2_800)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::PriceDeviation {
+                price: usd(2_800),
+                current: usd(2_650),
+                max_deviation_bps: 500,
+            }
+        );
+        report(&mut deps, &env, "feed_b", Metal::Gold, usd(2_700)).unwrap();
+        assert_eq!(price(&deps, &env, Metal::Gold).price, usd(2_675));
+
+        // Once stale the price is withheld from consumers but still listed, flagged
+        env.block.time = env.block.time.plus_seconds(601);
+        let msg = QueryMsg::Price {
+            metal: Metal::Gold,
+            denom: DENOM.to_string(),
+        };
+        assert!(query(deps.as_ref(), env.clone(), msg).is_err());
+        let msg = QueryMsg::Twap {
+            metal: Metal::Gold,
+            denom: DENOM.to_string(),
+        };
+        assert!(query(deps.as_ref(), env.clone(), msg).is_err());
+        let prices: PricesResponse =
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::Prices {}).unwrap()).unwrap();
+        assert!(prices.prices[0].stale);
+
+        // Stale reports drop out of the median and the guard no longer applies
+        report(&mut deps, &env, "feed_c", Metal::Gold, usd(2_800)).unwrap();
+        let gold = price(&deps, &env, Metal::Gold);
+        assert_eq!(gold.price, usd(2_800));
+        assert_eq!(gold.reporters, 1);
+    }
+
+    #[test]
+    fn test_twap_over_rolling_window() {
+        let mut deps = setup(1, 3_600);
+        let mut env = mock_env();
+        let start = env.block.time.seconds();
+        report(&mut deps, &env, "feed_a", Metal::Silver, usd(30)).unwrap();
+
+        // A feed younger than the window averages over its own lifetime
+        env.block.time = env.block.time.plus_seconds(1_800);
+        let average = twap(&deps, &env, Metal::Silver);
+        assert_eq!(average.price, usd(30));
+        assert_eq!(average.from, start);
+
+        report(
+            &mut deps,
+            &env,
+            "feed_a",
+            Metal::Silver,
+            Decimal::percent(3_120),
+        )
+        .unwrap();
+        env.block.time = env.block.time.plus_seconds(1_800);
+        let average = twap(&deps, &env, Metal::Silver);
+        assert_eq!(average.price, Decimal::percent(3_060));
+        assert_eq!((average.from, average.to), (start, start + 3_600));
+
+        // The window rolls forward: the first price no longer counts
+        env.block.time = env.block.time.plus_seconds(1_800);
+        report(
+            &mut deps,
+            &env,
+            "feed_a",
+            Metal::Silver,
+            Decimal::percent(3_150),
+        )
+        .unwrap();
+        env.block.time = env.block.time.plus_seconds(900);
+        let average = twap(&deps, &env, Metal::Silver);
+        // 2,700s at 31.20 then 900s at 31.50
+        assert_eq!(average.price, Decimal::permille(31_275));
+        assert_eq!(average.from, start + 2_700);
+    }
+
+    #[test]
+    fn test_removed_reporter_leaves_the_median() {
+        let mut deps = setup(2, 600);
+        let env = mock_env();
+        report(&mut deps, &env, "feed_a", Metal::Palladium, usd(1_000)).unwrap();
+        report(&mut deps, &env, "feed_b", Metal::Palladium, usd(1_040)).unwrap();
+        assert_eq!(price(&deps, &env, Metal::Palladium).price, usd(1_020));
+
+        let remove = ExecuteMsg::RemoveReporter {
+            address: "feed_b".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("feed_a", &[]),
+            remove.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let res = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), remove).unwrap();
+        let err = report(&mut deps, &env, "feed_b", Metal::Palladium, usd(1_040)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Too few reports remain, so the price feed_b helped set is withdrawn
+        assert_eq!(res.attributes[3].value, "palladium/urlusd");
+        let msg = QueryMsg::Price {
+            metal: Metal::Palladium,
+            denom: DENOM.to_string(),
+        };
+        assert!(query(deps.as_ref(), env.clone(), msg).is_err());
+        let res = report(&mut deps, &env, "feed_a", Metal::Palladium, usd(1_010)).unwrap();
+        assert_eq!(published(&res), "");
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::AddReporter {
+                address: "feed_d".to_string(),
+            },
+        )
+        .unwrap();
+        let res: ReportersResponse =
+            from_json(query(deps.as_ref(), env, QueryMsg::Reporters {}).unwrap()).unwrap();
+        let expected = ["feed_a", "feed_c", "feed_d"].map(Addr::unchecked).to_vec();
+        assert_eq!(res.reporters, expected);
+    }
+
+    #[test]
+    fn test_removed_reporter_is_dropped_from_live_prices() {
+        let mut deps = setup(2, 3_600);
+        let mut env = mock_env();
+        report(&mut deps, &env, "feed_a", Metal::Gold, usd(2_600)).unwrap();
+        report(&mut deps, &env, "feed_b", Metal::Gold, usd(2_620)).unwrap();
+        report(&mut deps, &env, "feed_c", Metal::Gold, usd(2_700)).unwrap();
+        assert_eq!(price(&deps, &env, Metal::Gold).price, usd(2_620));
+
+        // The median is recomputed without the removed reporter right away
+        env.block.time = env.block.time.plus_seconds(600);
+        let remove = ExecuteMsg::RemoveReporter {
+            address: "feed_c".to_string(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), remove).unwrap();
+        assert_eq!(res.attributes[2].value, "gold/urlusd");
+        let gold = price(&deps, &env, Metal::Gold);
+        assert_eq!(gold.price, usd(2_610));
+        assert_eq!(gold.reporters, 2);
+
+        // and the TWAP restarts from the recomputed price
+        let average = twap(&deps, &env, Metal::Gold);
+        assert_eq!(average.price, usd(2_610));
+        assert_eq!(average.from, env.block.time.seconds());
+        env.block.time = env.block.time.plus_seconds(600);
+        assert_eq!(twap(&deps, &env, Metal::Gold).price, usd(2_610));
+    }
+}